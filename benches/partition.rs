@@ -0,0 +1,98 @@
+//! Benchmarks for converting partitions, with a fixture shaped like a wide query result
+//!
+//! Compare the single-threaded and parallel parsing with:
+//!
+//! ```sh
+//! cargo bench --bench partition
+//! cargo bench --bench partition --features rayon
+//! ```
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+use light_snowflake_connector::{ColumnType, Partition};
+
+fn column(name: &str, data_type: &str, scale: u32) -> ColumnType {
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "type": data_type,
+        "scale": scale,
+        "precision": 38,
+        "nullable": true,
+    }))
+    .unwrap()
+}
+
+/// An integer, a decimal, a float, a string, and a boolean column
+fn columns() -> Vec<ColumnType> {
+    vec![
+        column("ID", "fixed", 0),
+        column("PRICE", "fixed", 2),
+        column("SCORE", "real", 0),
+        column("NAME", "text", 0),
+        column("ACTIVE", "boolean", 0),
+    ]
+}
+
+/// Rows of strings in Snowflake's wire format for [`columns`]
+fn raw(rows: usize) -> Vec<Vec<Option<String>>> {
+    (0..rows)
+        .map(|ix| {
+            vec![
+                Some(ix.to_string()),
+                Some(format!("{}.{:02}", ix / 100, ix % 100)),
+                Some(format!("{}", ix as f64 / 7.0)),
+                Some(format!("customer number {ix}")),
+                (ix % 3 != 0).then(|| (ix % 2 == 0).to_string()),
+            ]
+        })
+        .collect()
+}
+
+fn fixture(rows: usize) -> Partition {
+    Partition::from_raw(columns(), raw(rows))
+}
+
+fn convert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("partition");
+    for rows in [1_000, 100_000, 500_000] {
+        let partition = fixture(rows);
+        group.throughput(Throughput::Elements(rows as u64));
+        group.bench_with_input(BenchmarkId::new("cells", rows), &partition, |b, p| {
+            b.iter(|| black_box(p.cells()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("json_objects", rows),
+            &partition,
+            |b, p| b.iter(|| black_box(p.json_objects())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("json_objects_shared", rows),
+            &partition,
+            |b, p| b.iter(|| black_box(p.json_objects_shared())),
+        );
+    }
+    group.finish();
+}
+
+/// Converting a million cells from a partition that is dropped afterwards, like when
+/// streaming rows, by borrowing it or consuming it
+fn million_cells(c: &mut Criterion) {
+    let rows = 1_000_000 / columns().len();
+    let mut group = c.benchmark_group("million_cells");
+    group.throughput(Throughput::Elements(1_000_000));
+    group.bench_function("cells", |b| {
+        b.iter_batched(
+            || fixture(rows),
+            |partition| black_box(partition.cells()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = convert, million_cells
+}
+criterion_main!(benches);
@@ -0,0 +1,240 @@
+//! Conversion of results to Arrow record batches and IPC streams (behind the `arrow` feature)
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float64Builder, Int64Builder,
+    StringBuilder, Time64NanosecondBuilder, TimestampNanosecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use futures::{AsyncWrite, AsyncWriteExt, TryStreamExt};
+
+use crate::cells::{parse_epoch, RawCell};
+use crate::errors::SnowflakeResult;
+use crate::partition::Partition;
+use crate::statement::{ColumnType, QueryResponse};
+
+/// The largest precision that always fits in an `i64`
+const MAX_INT64_PRECISION: u32 = 18;
+
+/// The Arrow type used for a column
+///
+/// Numbers with a scale become exact `Decimal128`s, rather than the lossy floats in [`Cell`](crate::Cell),
+/// and timestamps are nanoseconds since the epoch (in UTC for `TIMESTAMP_LTZ`).
+/// `TIMESTAMP_TZ`, `VECTOR`, and `INTERVAL` columns are passed through as text in Snowflake's wire format,
+/// and semi-structured columns as JSON text.
+fn data_type(column: &ColumnType) -> DataType {
+    let precision = column.precision.unwrap_or(38).clamp(1, 38);
+    match column.data_type {
+        RawCell::Fixed => match column.scale.unwrap_or(0) {
+            0 if precision <= MAX_INT64_PRECISION => DataType::Int64,
+            scale => DataType::Decimal128(precision as u8, scale.clamp(0, 38) as i8),
+        },
+        RawCell::Real => DataType::Float64,
+        RawCell::Text
+        | RawCell::TimestampTz
+        | RawCell::Variant
+        | RawCell::Object
+        | RawCell::Array
+        | RawCell::Vector
+        | RawCell::IntervalYearMonth
+        | RawCell::IntervalDayTime => DataType::Utf8,
+        RawCell::Binary => DataType::Binary,
+        RawCell::Boolean => DataType::Boolean,
+        RawCell::Date => DataType::Date32,
+        RawCell::Time => DataType::Time64(TimeUnit::Nanosecond),
+        RawCell::TimestampLtz => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        RawCell::TimestampNtz => DataType::Timestamp(TimeUnit::Nanosecond, None),
+    }
+}
+
+/// The Arrow schema of a result
+pub(crate) fn schema(columns: &[ColumnType]) -> SchemaRef {
+    let fields: Vec<_> = columns
+        .iter()
+        .map(|column| Field::new(&column.name, data_type(column), column.nullable))
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+fn invalid(column: &ColumnType, value: &str) -> ArrowError {
+    ArrowError::ParseError(format!("invalid value {value:?} in column {}", column.name))
+}
+
+/// Parse a decimal like `-12.5` into an integer scaled by `10^scale`
+fn parse_decimal(value: &str, scale: i8) -> Option<i128> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let scale = scale.max(0) as usize;
+    let mut digits = format!("{whole}{:0<scale$}", &fraction[..fraction.len().min(scale)]);
+    if digits.starts_with('+') {
+        digits.remove(0);
+    }
+    digits.parse().ok()
+}
+
+/// Parse seconds with a fraction, like `3661.5`, into nanoseconds
+fn parse_nanos(value: &str) -> Option<i64> {
+    value.parse::<f64>().ok()?;
+    let (seconds, nanos) = parse_epoch(value)?;
+    seconds
+        .checked_mul(1_000_000_000)?
+        .checked_add(nanos as i64)
+}
+
+/// Build one column of a record batch from the raw strings Snowflake sent
+fn column(
+    partition: &Partition,
+    index: usize,
+    column: &ColumnType,
+) -> Result<ArrayRef, ArrowError> {
+    // Like `RawCell::to_cell`, read "null" as NULL unless it can be a value of this type
+    let null_text_is_null = column.data_type.reads_null_text_as_null(partition.nullable);
+    let values = partition
+        .raw_cells()
+        .iter()
+        .map(|row| row[index].as_deref())
+        .map(|value| value.filter(|value| !null_text_is_null || *value != "null"));
+    macro_rules! build {
+        ($builder:expr, $parse:expr) => {{
+            let mut builder = $builder;
+            for value in values {
+                match value {
+                    Some(value) => {
+                        builder.append_value($parse(value).ok_or_else(|| invalid(column, value))?)
+                    }
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish()) as ArrayRef
+        }};
+    }
+    Ok(match data_type(column) {
+        DataType::Int64 => build!(Int64Builder::new(), |v: &str| v.parse::<i64>().ok()),
+        DataType::Decimal128(precision, scale) => build!(
+            Decimal128Builder::new().with_precision_and_scale(precision, scale)?,
+            |v: &str| parse_decimal(v, scale)
+        ),
+        DataType::Float64 => build!(Float64Builder::new(), |v: &str| v.parse::<f64>().ok()),
+        DataType::Utf8 => build!(StringBuilder::new(), Some),
+        DataType::Binary => build!(BinaryBuilder::new(), |v: &str| hex::decode(v).ok()),
+        DataType::Boolean => build!(BooleanBuilder::new(), |v: &str| v.parse::<bool>().ok()),
+        DataType::Date32 => build!(Date32Builder::new(), |v: &str| v.parse::<i32>().ok()),
+        DataType::Time64(_) => build!(Time64NanosecondBuilder::new(), parse_nanos),
+        DataType::Timestamp(_, zone) => build!(
+            TimestampNanosecondBuilder::new().with_timezone_opt(zone),
+            parse_nanos
+        ),
+        other => unreachable!("no builder for {other}"),
+    })
+}
+
+impl Partition {
+    /// Convert this partition into an Arrow record batch
+    ///
+    /// This parses Snowflake's raw strings directly, so numbers with a scale are exact decimals.
+    pub fn to_record_batch(&self) -> SnowflakeResult<RecordBatch> {
+        let columns = &self.meta_data.row_type;
+        let arrays = columns
+            .iter()
+            .enumerate()
+            .map(|(index, meta)| column(self, index, meta))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RecordBatch::try_new(schema(columns), arrays)?)
+    }
+}
+
+impl QueryResponse {
+    /// The Arrow schema of this result
+    pub fn arrow_schema(&self) -> SchemaRef {
+        schema(self.column_types())
+    }
+
+    /// Write every partition as a record batch in an Arrow IPC stream, returning the number of rows
+    ///
+    /// Partitions are fetched and written one at a time, so this doesn't hold the whole
+    /// result in memory. The stream can be read by DuckDB, pandas (via pyarrow), Polars, etc.
+    pub async fn write_arrow_ipc<W: Write>(&self, writer: W) -> SnowflakeResult<usize> {
+        let mut writer = StreamWriter::try_new(writer, &self.arrow_schema())?;
+        let mut rows = 0;
+        let partitions = self.partitions().into_stream();
+        futures::pin_mut!(partitions);
+        while let Some(partition) = partitions.try_next().await? {
+            rows += partition.num_rows();
+            writer.write(&partition.to_record_batch()?)?;
+        }
+        writer.finish()?;
+        Ok(rows)
+    }
+
+    /// Like [`QueryResponse::write_arrow_ipc`], but for an async writer
+    ///
+    /// Each record batch is encoded into a buffer, then written to the writer.
+    pub async fn write_arrow_ipc_async<W: AsyncWrite + Unpin>(
+        &self,
+        mut writer: W,
+    ) -> SnowflakeResult<usize> {
+        let mut encoder = StreamWriter::try_new(Vec::new(), &self.arrow_schema())?;
+        let mut rows = 0;
+        let partitions = self.partitions().into_stream();
+        futures::pin_mut!(partitions);
+        while let Some(partition) = partitions.try_next().await? {
+            rows += partition.num_rows();
+            encoder.write(&partition.to_record_batch()?)?;
+            writer.write_all(encoder.get_ref()).await?;
+            encoder.get_mut().clear();
+        }
+        encoder.finish()?;
+        writer.write_all(encoder.get_ref()).await?;
+        writer.flush().await?;
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Decimal128Type, Int64Type, TimestampNanosecondType};
+    use arrow_array::Array;
+
+    use super::*;
+
+    #[test]
+    fn parse_raw_values() {
+        assert_eq!(parse_decimal("-12.5", 2), Some(-1250));
+        assert_eq!(parse_decimal("3", 2), Some(300));
+        assert_eq!(parse_nanos("1.000000001"), Some(1_000_000_001));
+        assert_eq!(parse_nanos("nope"), None);
+    }
+
+    #[test]
+    fn record_batch_types() -> SnowflakeResult<()> {
+        let meta_data = serde_json::from_value(serde_json::json!({
+            "numRows": 2,
+            "rowType": [
+                {"name": "ID", "type": "fixed", "precision": 9, "scale": 0, "nullable": false},
+                {"name": "PRICE", "type": "fixed", "precision": 10, "scale": 2, "nullable": true},
+                {"name": "AT", "type": "timestamp_ntz", "nullable": true},
+            ],
+            "partitionInfo": [{}]
+        }))?;
+        let data = vec![
+            vec![
+                Some("1".into()),
+                Some("9.99".into()),
+                Some("1672534861.5".into()),
+            ],
+            vec![Some("2".into()), None, None],
+        ];
+        let batch = Partition::new(0, meta_data, Arc::new(data)).to_record_batch()?;
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).as_primitive::<Int64Type>().value(1), 2);
+        let prices = batch.column(1).as_primitive::<Decimal128Type>();
+        assert_eq!(prices.value(0), 999);
+        assert!(prices.is_null(1));
+        let at = batch.column(2).as_primitive::<TimestampNanosecondType>();
+        assert_eq!(at.value(0), 1_672_534_861_500_000_000);
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// A record of one executed statement, passed to an [`AuditHook`]
+///
+/// The SQL is only included as a fingerprint, with literals replaced by `?`,
+/// and binding values are never included, so records are safe to ship to log pipelines.
+/// Requests by the [`Catalog`](crate::Catalog) are recorded too, with their method and path
+/// (like `POST /api/v2/warehouses`) as the fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// When the statement was submitted
+    pub timestamp: SystemTime,
+    /// How long it took to finish, including polling and fetching the first partition
+    pub elapsed: Duration,
+    /// The Snowflake user that ran the statement
+    pub user: String,
+    /// The role the statement ran as, if the client specifies one
+    pub role: Option<String>,
+    /// The SQL with comments removed, whitespace collapsed, and literals replaced by `?`
+    pub fingerprint: String,
+    /// The client-generated request id, which also identifies the statement in retries
+    pub request_id: uuid::Uuid,
+    /// Snowflake's query id, if the statement got far enough to be assigned one
+    pub query_id: Option<String>,
+    /// Whether the statement succeeded
+    pub outcome: AuditOutcome,
+}
+
+/// How an audited statement finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOutcome {
+    /// The statement succeeded
+    Succeeded,
+    /// The statement failed, with the error's message
+    Failed(String),
+}
+
+/// A callback invoked with an [`AuditRecord`] for every executed statement
+///
+/// Attach one to a client with
+/// [`SnowflakeClient::with_audit_hook`](crate::SnowflakeClient::with_audit_hook).
+/// Unlike debug logging, this is always invoked, once per statement, whether it succeeds or not.
+#[derive(Clone)]
+pub struct AuditHook(Arc<dyn Fn(&AuditRecord) + Send + Sync>);
+
+impl AuditHook {
+    /// Wrap a function that receives each audit record
+    pub fn new(hook: impl Fn(&AuditRecord) + Send + Sync + 'static) -> Self {
+        AuditHook(Arc::new(hook))
+    }
+
+    pub(crate) fn record(&self, record: &AuditRecord) {
+        (self.0)(record)
+    }
+}
+
+impl fmt::Debug for AuditHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuditHook")
+    }
+}
+
+/// Redact SQL down to its shape: comments dropped, whitespace collapsed,
+/// and string, `$$`, and numeric literals replaced by `?`
+pub fn fingerprint(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut pending_space = false;
+    while let Some(c) = chars.next() {
+        let next = chars.peek().copied();
+        let token = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                pending_space = !out.is_empty();
+                continue;
+            }
+            ('-', Some('-')) | ('/', Some('/')) => {
+                chars.by_ref().find(|c| *c == '\n');
+                pending_space = !out.is_empty();
+                continue;
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                chars
+                    .by_ref()
+                    .find(|c| std::mem::replace(&mut last, *c) == '*' && *c == '/');
+                pending_space = !out.is_empty();
+                continue;
+            }
+            ('\'', _) => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                        }
+                        '\'' => break,
+                        _ => {}
+                    }
+                }
+                "?".to_owned()
+            }
+            ('$', Some('$')) => {
+                chars.next();
+                let mut last = ' ';
+                chars
+                    .by_ref()
+                    .find(|c| std::mem::replace(&mut last, *c) == '$' && *c == '$');
+                "?".to_owned()
+            }
+            (c, _) if c.is_ascii_digit() && !ends_with_identifier(&out, pending_space) => {
+                while chars
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '.')
+                {
+                    chars.next();
+                }
+                "?".to_owned()
+            }
+            (c, _) => c.to_string(),
+        };
+        if std::mem::take(&mut pending_space) {
+            out.push(' ');
+        }
+        out.push_str(&token);
+    }
+    out
+}
+
+/// Whether a digit would continue an identifier like `t1`, rather than start a number
+fn ends_with_identifier(out: &str, pending_space: bool) -> bool {
+    !pending_space
+        && out
+            .chars()
+            .last()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_redact_literals() {
+        assert_eq!(
+            fingerprint("SELECT * FROM t1\n  WHERE name = 'O''Brien' AND id = 42 -- the user"),
+            "SELECT * FROM t1 WHERE name = ? AND id = ?"
+        );
+        assert_eq!(
+            fingerprint("/* a */ CALL p($$ secret $$, 1.5e3, ?)"),
+            "CALL p(?, ?, ?)"
+        );
+    }
+}
@@ -1,5 +1,8 @@
+#[cfg(feature = "chrono")]
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
+use crate::interval::Interval;
+
 /// Binding types, used for serialization and sending data to Snowflake.
 ///
 /// These don't round trip because the format Snowflake returns is different,
@@ -7,13 +10,63 @@ use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 #[derive(Clone, Debug, serde::Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type")]
 pub enum Binding {
-    Boolean { value: String },
-    Fixed { value: String },
-    Real { value: String },
-    Text { value: String },
-    TimestampNtz { value: String },
-    Date { value: String },
-    Time { value: String },
+    Boolean {
+        value: String,
+    },
+    Fixed {
+        value: String,
+    },
+    Real {
+        value: String,
+    },
+    Text {
+        value: String,
+    },
+    TimestampNtz {
+        value: String,
+    },
+    Date {
+        value: String,
+    },
+    Time {
+        value: String,
+    },
+    /// Bytes, encoded as hex
+    Binary {
+        value: String,
+    },
+    /// SQL `NULL`, which Snowflake accepts as text with a null value
+    #[serde(rename = "TEXT")]
+    Null {
+        value: (),
+    },
+}
+
+/// Types that can be passed to [`Statement::add_binding`](crate::Statement::add_binding)
+///
+/// Everything that converts into a [`Binding`] already implements this, so implement it for
+/// your own types (like IDs, amounts, or enums) to bind them directly, usually by converting
+/// to one of the supported types:
+///
+/// ```rust
+/// use light_snowflake_connector::{Binding, ToBinding};
+///
+/// struct UserId(u64);
+///
+/// impl ToBinding for UserId {
+///     fn to_binding(self) -> Binding {
+///         self.0.into()
+///     }
+/// }
+/// ```
+pub trait ToBinding {
+    fn to_binding(self) -> Binding;
+}
+
+impl<T: Into<Binding>> ToBinding for T {
+    fn to_binding(self) -> Binding {
+        self.into()
+    }
 }
 
 // impl From<&str> for BindingValue {
@@ -49,14 +102,173 @@ impl_binding!(f64, Real);
 impl_binding!(char, Text);
 impl_binding!(String, Text);
 impl_binding!(&str, Text);
+#[cfg(feature = "chrono")]
 impl_binding!(NaiveDateTime, Text);
+#[cfg(feature = "chrono")]
 impl_binding!(NaiveDate, Text);
+#[cfg(feature = "chrono")]
 impl_binding!(NaiveTime, Text);
 
+#[cfg(feature = "time")]
+impl_binding!(time::Date, Text);
+
+#[cfg(feature = "time")]
+impl From<time::Time> for Binding {
+    fn from(value: time::Time) -> Self {
+        Binding::Text {
+            value: crate::cells::iso_time(value),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::PrimitiveDateTime> for Binding {
+    fn from(value: time::PrimitiveDateTime) -> Self {
+        Binding::Text {
+            value: format!("{} {}", value.date(), crate::cells::iso_time(value.time())),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Binding {
+    fn from(value: time::OffsetDateTime) -> Self {
+        let offset = value.offset();
+        Binding::Text {
+            value: format!(
+                "{} {} {}{:02}:{:02}",
+                value.date(),
+                crate::cells::iso_time(value.time()),
+                if offset.is_negative() { '-' } else { '+' },
+                offset.whole_hours().abs(),
+                offset.minutes_past_hour().abs(),
+            ),
+        }
+    }
+}
+
+/// Snowflake can't bind intervals, so they're bound as text like `1-2` or `1 02:03:04.500`,
+/// which SQL can cast with `?::INTERVAL YEAR TO MONTH` or `?::INTERVAL DAY TO SECOND`
+impl From<Interval> for Binding {
+    fn from(value: Interval) -> Self {
+        Binding::Text {
+            value: value.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::Duration> for Binding {
+    fn from(value: chrono::Duration) -> Self {
+        Interval::from(value).into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Duration> for Binding {
+    fn from(value: time::Duration) -> Self {
+        Interval::from(value).into()
+    }
+}
+
+impl<T: ToBinding> From<Option<T>> for Binding {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.to_binding(),
+            None => Binding::Null { value: () },
+        }
+    }
+}
+
 impl From<&[u8]> for Binding {
     fn from(value: &[u8]) -> Self {
-        Binding::Text {
+        Binding::Binary {
             value: hex::encode(value),
         }
     }
 }
+
+impl From<Vec<u8>> for Binding {
+    fn from(value: Vec<u8>) -> Self {
+        value.as_slice().into()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for Binding {
+    fn from(value: bytes::Bytes) -> Self {
+        value.as_ref().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UserId(u64);
+
+    impl ToBinding for UserId {
+        fn to_binding(self) -> Binding {
+            self.0.into()
+        }
+    }
+
+    #[test]
+    fn custom_bindings() {
+        let json = |binding: Binding| serde_json::to_value(binding).unwrap();
+        assert_eq!(
+            json(UserId(7).to_binding()),
+            serde_json::json!({"type": "FIXED", "value": "7"})
+        );
+        assert_eq!(
+            json(Some(UserId(7)).into()),
+            serde_json::json!({"type": "FIXED", "value": "7"})
+        );
+    }
+
+    #[test]
+    fn bytes_bind_as_binary() {
+        let expected = serde_json::json!({"type": "BINARY", "value": "666f6f"});
+        let json = |binding: Binding| serde_json::to_value(binding).unwrap();
+        assert_eq!(json(b"foo".as_slice().into()), expected);
+        assert_eq!(json(b"foo".to_vec().into()), expected);
+        #[cfg(feature = "bytes")]
+        assert_eq!(json(bytes::Bytes::from_static(b"foo").into()), expected);
+        assert_eq!(
+            json(None::<Vec<u8>>.into()),
+            serde_json::json!({"type": "TEXT", "value": null})
+        );
+    }
+
+    #[test]
+    fn intervals_bind_as_text() {
+        let json = |binding: Binding| serde_json::to_value(binding).unwrap();
+        assert_eq!(
+            json(Interval::from_months(14).into()),
+            serde_json::json!({"type": "TEXT", "value": "1-2"})
+        );
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            json(chrono::Duration::milliseconds(93_784_500).into()),
+            serde_json::json!({"type": "TEXT", "value": "1 02:03:04.500"})
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_bindings_are_iso_text() {
+        use time::macros::datetime;
+        let text = |binding: Binding| match binding {
+            Binding::Text { value } => value,
+            other => panic!("expected text, got {:?}", other),
+        };
+        let timestamp = datetime!(2023-01-01 01:02:03.5 -05:30);
+        assert_eq!(text(timestamp.date().into()), "2023-01-01");
+        assert_eq!(text(timestamp.time().into()), "01:02:03.500");
+        assert_eq!(
+            text(time::PrimitiveDateTime::new(timestamp.date(), timestamp.time()).into()),
+            "2023-01-01 01:02:03.500"
+        );
+        assert_eq!(text(timestamp.into()), "2023-01-01 01:02:03.500 -05:30");
+    }
+}
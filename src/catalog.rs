@@ -0,0 +1,370 @@
+use std::time::Duration;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SnowflakeResult;
+use crate::transport::Transport;
+use crate::SnowflakeClient;
+
+/// The timeout for each request, the same as a statement with the default timeout
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// Snowflake's REST endpoints for databases, schemas, and warehouses
+///
+/// These are created by [`SnowflakeClient::catalog`], and use the same key pair, failover,
+/// [`StatementPolicy`](crate::StatementPolicy), and audit hook as statements. They're an
+/// alternative to `SHOW` and `CREATE` statements that don't need a running warehouse and
+/// respond with typed objects:
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// use light_snowflake_connector::{CreateMode, Database};
+///
+/// let catalog = client.catalog();
+/// for database in catalog.list_databases(Some("ANALYTICS%")).await? {
+///     println!("{} owned by {:?}", database.name, database.owner);
+/// }
+/// catalog
+///     .create_database(&Database::new("scratch"), CreateMode::IfNotExists)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Names are used as written, so quote case sensitive names yourself, like `"\"Scratch\""`.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    client: SnowflakeClient,
+}
+
+/// What to do when creating an object that already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CreateMode {
+    /// Fail with an error
+    #[default]
+    ErrorIfExists,
+    /// Replace the existing object
+    OrReplace,
+    /// Keep the existing object, and succeed without changing it
+    IfNotExists,
+}
+
+impl CreateMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            CreateMode::ErrorIfExists => "errorIfExists",
+            CreateMode::OrReplace => "orReplace",
+            CreateMode::IfNotExists => "ifNotExists",
+        }
+    }
+}
+
+/// A database, as listed, described, or created by the [`Catalog`]
+///
+/// Properties this crate doesn't name are kept in `other`, and are sent back when creating.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Database {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// When the database was created, as an ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_retention_time_in_days: Option<u32>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Database {
+    /// A database with a name and nothing else, for creating one
+    pub fn new(name: &str) -> Database {
+        Database {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A schema, as listed, described, or created by the [`Catalog`]
+///
+/// Properties this crate doesn't name are kept in `other`, and are sent back when creating.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    pub name: String,
+    /// The database the schema is in (this is ignored when creating)
+    #[serde(default, skip_serializing)]
+    pub database_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// When the schema was created, as an ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_retention_time_in_days: Option<u32>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Schema {
+    /// A schema with a name and nothing else, for creating one
+    pub fn new(name: &str) -> Schema {
+        Schema {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A warehouse, as listed, described, or created by the [`Catalog`]
+///
+/// Properties this crate doesn't name are kept in `other`, and are sent back when creating.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Warehouse {
+    pub name: String,
+    /// Like `XSMALL` or `LARGE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warehouse_size: Option<String>,
+    /// Like `STANDARD` or `SNOWPARK-OPTIMIZED`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warehouse_type: Option<String>,
+    /// Like `STARTED` or `SUSPENDED` (this is ignored when creating)
+    #[serde(default, skip_serializing)]
+    pub state: Option<String>,
+    /// Seconds of inactivity before the warehouse suspends itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_suspend: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_resume: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// When the warehouse was created, as an ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_on: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Warehouse {
+    /// A warehouse with a name and nothing else, for creating one
+    pub fn new(name: &str) -> Warehouse {
+        Warehouse {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct WireStatus {
+    status: String,
+}
+
+impl Catalog {
+    pub(crate) fn new(client: &SnowflakeClient) -> Catalog {
+        Catalog {
+            client: client.clone(),
+        }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> SnowflakeResult<T> {
+        self.transport().rest(Method::GET, path, None).await
+    }
+
+    /// Create an object, returning Snowflake's status message
+    async fn create<T: Serialize>(
+        &self,
+        path: &str,
+        object: &T,
+        mode: CreateMode,
+    ) -> SnowflakeResult<String> {
+        let body = serde_json::to_value(object)?;
+        let path = format!("{path}?createMode={}", mode.as_str());
+        let status: WireStatus = self
+            .transport()
+            .rest(Method::POST, &path, Some(&body))
+            .await?;
+        Ok(status.status)
+    }
+
+    fn transport(&self) -> Transport<'_> {
+        Transport::new(&self.client, REQUEST_TIMEOUT)
+    }
+
+    /// List databases, optionally with names matching a `LIKE` pattern (case-insensitive)
+    pub async fn list_databases(&self, like: Option<&str>) -> SnowflakeResult<Vec<Database>> {
+        self.get(&format!("/api/v2/databases{}", like_query(like)))
+            .await
+    }
+
+    /// Describe one database
+    pub async fn describe_database(&self, name: &str) -> SnowflakeResult<Database> {
+        self.get(&format!("/api/v2/databases/{}", segment(name)))
+            .await
+    }
+
+    /// Create a database, returning Snowflake's status message
+    pub async fn create_database(
+        &self,
+        database: &Database,
+        mode: CreateMode,
+    ) -> SnowflakeResult<String> {
+        self.create("/api/v2/databases", database, mode).await
+    }
+
+    /// List the schemas in a database, optionally with names matching a `LIKE` pattern
+    pub async fn list_schemas(
+        &self,
+        database: &str,
+        like: Option<&str>,
+    ) -> SnowflakeResult<Vec<Schema>> {
+        self.get(&format!(
+            "/api/v2/databases/{}/schemas{}",
+            segment(database),
+            like_query(like)
+        ))
+        .await
+    }
+
+    /// Describe one schema
+    pub async fn describe_schema(&self, database: &str, name: &str) -> SnowflakeResult<Schema> {
+        self.get(&format!(
+            "/api/v2/databases/{}/schemas/{}",
+            segment(database),
+            segment(name)
+        ))
+        .await
+    }
+
+    /// Create a schema in a database, returning Snowflake's status message
+    pub async fn create_schema(
+        &self,
+        database: &str,
+        schema: &Schema,
+        mode: CreateMode,
+    ) -> SnowflakeResult<String> {
+        let path = format!("/api/v2/databases/{}/schemas", segment(database));
+        self.create(&path, schema, mode).await
+    }
+
+    /// List warehouses, optionally with names matching a `LIKE` pattern (case-insensitive)
+    pub async fn list_warehouses(&self, like: Option<&str>) -> SnowflakeResult<Vec<Warehouse>> {
+        self.get(&format!("/api/v2/warehouses{}", like_query(like)))
+            .await
+    }
+
+    /// Describe one warehouse
+    pub async fn describe_warehouse(&self, name: &str) -> SnowflakeResult<Warehouse> {
+        self.get(&format!("/api/v2/warehouses/{}", segment(name)))
+            .await
+    }
+
+    /// Create a warehouse, returning Snowflake's status message
+    pub async fn create_warehouse(
+        &self,
+        warehouse: &Warehouse,
+        mode: CreateMode,
+    ) -> SnowflakeResult<String> {
+        self.create("/api/v2/warehouses", warehouse, mode).await
+    }
+}
+
+/// Percent-encode a name for use in a path or query string
+fn segment(name: &str) -> String {
+    name.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn like_query(like: Option<&str>) -> String {
+    like.map(|pattern| format!("?like={}", segment(pattern)))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{AuditOutcome, SnowflakeError};
+
+    #[test]
+    fn names_are_percent_encoded() {
+        assert_eq!(segment("my_db"), "my_db");
+        assert_eq!(segment("\"My DB\""), "%22My%20DB%22");
+        assert_eq!(like_query(Some("ANALYTICS%")), "?like=ANALYTICS%25");
+        assert_eq!(like_query(None), "");
+    }
+
+    #[tokio::test]
+    async fn read_only_clients_cannot_create() {
+        let records = Arc::new(Mutex::new(vec![]));
+        let catalog = {
+            let records = records.clone();
+            crate::test_client()
+                .with_statement_policy(crate::StatementPolicy::read_only())
+                .with_audit_hook(move |record| records.lock().unwrap().push(record.clone()))
+                .catalog()
+        };
+        // The account doesn't exist, so this would fail differently if it were sent
+        let result = catalog
+            .create_warehouse(&Warehouse::new("scratch"), CreateMode::ErrorIfExists)
+            .await;
+        assert!(matches!(
+            result,
+            Err(SnowflakeError::PolicyViolation(v))
+                if v.keyword == "CREATE" && v.statement.starts_with("POST /api/v2/warehouses?")
+        ));
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].fingerprint, "POST /api/v2/warehouses");
+        assert_eq!(records[0].role.as_deref(), Some("ROLE"));
+        assert!(matches!(records[0].outcome, AuditOutcome::Failed(_)));
+    }
+
+    #[test]
+    fn catalog_objects_round_trip() -> SnowflakeResult<()> {
+        let warehouse: Warehouse = serde_json::from_value(serde_json::json!({
+            "name": "ETL_WH",
+            "warehouse_size": "XSMALL",
+            "state": "SUSPENDED",
+            "auto_suspend": 60,
+            "auto_resume": "true",
+            "max_cluster_count": 2,
+        }))?;
+        assert_eq!(warehouse.warehouse_size.as_deref(), Some("XSMALL"));
+        assert_eq!(warehouse.auto_suspend, Some(60));
+        assert_eq!(warehouse.other["max_cluster_count"], 2);
+        assert_eq!(
+            serde_json::to_value(&warehouse)?,
+            serde_json::json!({
+                "name": "ETL_WH",
+                "warehouse_size": "XSMALL",
+                "auto_suspend": 60,
+                "auto_resume": "true",
+                "max_cluster_count": 2,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(Database::new("scratch"))?,
+            serde_json::json!({"name": "scratch"})
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+use std::sync::Arc;
+
+use crate::cells::{Cell, FromCell};
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::row::Row;
+use crate::statement::{quote_object_name, ChangesSummary, ColumnType};
+use crate::SnowflakeClient;
+
+const ACTION: &str = "METADATA$ACTION";
+const IS_UPDATE: &str = "METADATA$ISUPDATE";
+const ROW_ID: &str = "METADATA$ROW_ID";
+
+/// A stream, which records the changes made to a table since it was last consumed
+///
+/// These are created by [`SnowflakeClient::change_stream`]. The name is quoted if it needs
+/// to be, like in [`SnowflakeClient::get_ddl`].
+///
+/// Reading a stream with `SELECT` (like [`ChangeStream::changes`]) doesn't move its offset.
+/// The offset only moves when a DML statement that reads from the stream commits, so a
+/// consumer usually looks at the changes, then applies them with [`ChangeStream::consume`]:
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// let stream = client.change_stream("sales.public.orders_stream");
+/// if stream.has_data().await? {
+///     stream
+///         .consume(&[
+///             "INSERT INTO order_events SELECT id, METADATA$ACTION, CURRENT_TIMESTAMP() \
+///              FROM sales.public.orders_stream",
+///         ])
+///         .await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChangeStream {
+    client: SnowflakeClient,
+    name: String,
+}
+
+/// Whether a [`StreamChange`] added or removed a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Insert,
+    Delete,
+}
+
+/// A changed row read from a [`ChangeStream`]
+///
+/// An update appears as two changes with the same `row_id`: a `Delete` of the old values
+/// and an `Insert` of the new ones, both with `is_update` set.
+#[derive(Debug, Clone)]
+pub struct StreamChange {
+    /// `METADATA$ACTION`
+    pub action: ChangeAction,
+    /// `METADATA$ISUPDATE`
+    pub is_update: bool,
+    /// `METADATA$ROW_ID`, which stays the same for a row across changes
+    pub row_id: String,
+    /// The table's columns, without the metadata columns
+    pub row: Row,
+}
+
+impl ChangeStream {
+    pub(crate) fn new(client: &SnowflakeClient, name: &str) -> ChangeStream {
+        ChangeStream {
+            client: client.clone(),
+            name: quote_object_name(name),
+        }
+    }
+
+    /// The name of the stream, quoted if it needs to be
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the stream has changes to consume, with `SYSTEM$STREAM_HAS_DATA`
+    ///
+    /// This is cheap, and doesn't need a running warehouse, so check it before reading.
+    pub async fn has_data(&self) -> SnowflakeResult<bool> {
+        self.client
+            .prepare("SELECT SYSTEM$STREAM_HAS_DATA(?)")
+            .add_binding(self.name.as_str())
+            .fetch_scalar()
+            .await
+    }
+
+    /// Read every pending change, without consuming them
+    pub async fn changes(&self) -> SnowflakeResult<Vec<StreamChange>> {
+        use futures::TryStreamExt;
+        let response = self
+            .client
+            .prepare(&format!("SELECT * FROM {}", self.name))
+            .query()
+            .await?;
+        let rows: Vec<Row> = response.rows().try_collect().await?;
+        split_changes(rows)
+    }
+
+    /// Run DML statements that read from the stream in one transaction, which moves the
+    /// stream's offset past everything they read when it commits
+    ///
+    /// The statements are sent together between `BEGIN` and `COMMIT`, so if any of them
+    /// fails, none of their changes are kept and the stream's offset stays where it was.
+    /// Use them as written, and avoid DDL in them, since that commits the transaction early.
+    pub async fn consume(&self, statements: &[&str]) -> SnowflakeResult<ChangesSummary> {
+        let sql = std::iter::once("BEGIN")
+            .chain(statements.iter().copied())
+            .chain(std::iter::once("COMMIT"))
+            .collect::<Vec<_>>()
+            .join(";\n");
+        let statement = self
+            .client
+            .prepare(&sql)
+            .with_parameter("MULTI_STATEMENT_COUNT", (statements.len() + 2).to_string());
+        Ok(statement.manipulate_multi().await?.into_iter().collect())
+    }
+
+    /// Discard every pending change, by moving the stream's offset to now
+    ///
+    /// This inserts nothing from the stream into a temporary table, which counts as
+    /// consuming it. The table disappears with the session.
+    pub async fn advance(&self) -> SnowflakeResult<()> {
+        let table = format!("STREAM_ADVANCE_{}", uuid::Uuid::new_v4().simple());
+        let sql = format!(
+            "CREATE TEMPORARY TABLE {table} AS SELECT * FROM {name} WHERE 1 = 0;\n\
+             INSERT INTO {table} SELECT * FROM {name} WHERE 1 = 0",
+            name = self.name
+        );
+        self.client
+            .prepare(&sql)
+            .with_parameter("MULTI_STATEMENT_COUNT", "2".into())
+            .manipulate_multi()
+            .await?;
+        Ok(())
+    }
+}
+
+/// Separate the metadata columns of a stream's rows from the table's columns
+fn split_changes(rows: Vec<Row>) -> SnowflakeResult<Vec<StreamChange>> {
+    let Some(first) = rows.first() else {
+        return Ok(vec![]);
+    };
+    let position = |name: &str| {
+        first
+            .columns()
+            .iter()
+            .position(|column| column.name == name)
+            .ok_or_else(|| {
+                SnowflakeError::UnexpectedResult(format!("stream rows have no {name} column"))
+            })
+    };
+    let metadata = [position(ACTION)?, position(IS_UPDATE)?, position(ROW_ID)?];
+    let columns: Arc<[ColumnType]> = first
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(ix, _)| !metadata.contains(ix))
+        .map(|(_, column)| column.clone())
+        .collect();
+    rows.into_iter()
+        .map(|row| {
+            let mut cells = row.into_cells();
+            let [action, is_update, row_id] =
+                metadata.map(|ix| std::mem::replace(&mut cells[ix], Cell::Null));
+            let action = match String::from_cell(&action)?.as_str() {
+                "INSERT" => ChangeAction::Insert,
+                "DELETE" => ChangeAction::Delete,
+                other => {
+                    return Err(SnowflakeError::UnexpectedResult(format!(
+                        "unknown stream action {other}"
+                    )))
+                }
+            };
+            let cells = cells
+                .into_iter()
+                .enumerate()
+                .filter(|(ix, _)| !metadata.contains(ix))
+                .map(|(_, cell)| cell)
+                .collect();
+            Ok(StreamChange {
+                action,
+                is_update: bool::from_cell(&is_update)?,
+                row_id: String::from_cell(&row_id)?,
+                row: Row::new(columns.clone(), cells),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_stream_rows() -> SnowflakeResult<()> {
+        let column = |name: &str, data_type: &str| -> ColumnType {
+            serde_json::from_value(serde_json::json!({"name": name, "type": data_type})).unwrap()
+        };
+        let columns: Arc<[ColumnType]> = vec![
+            column("ID", "fixed"),
+            column(ACTION, "text"),
+            column(IS_UPDATE, "boolean"),
+            column("STATUS", "text"),
+            column(ROW_ID, "text"),
+        ]
+        .into();
+        let row = |id, action: &str, is_update, status: &str| {
+            Row::new(
+                columns.clone(),
+                vec![
+                    Cell::Int(id),
+                    Cell::Varchar(action.into()),
+                    Cell::Boolean(is_update),
+                    Cell::Varchar(status.into()),
+                    Cell::Varchar(format!("row-{id}").into()),
+                ],
+            )
+        };
+        let changes = split_changes(vec![
+            row(1, "DELETE", true, "open"),
+            row(1, "INSERT", true, "closed"),
+            row(2, "INSERT", false, "open"),
+        ])?;
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].action, ChangeAction::Delete);
+        assert!(changes[1].is_update);
+        assert!(!changes[2].is_update);
+        assert_eq!(changes[1].row_id, "row-1");
+        let names: Vec<&str> = changes[1]
+            .row
+            .columns()
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect();
+        assert_eq!(names, ["ID", "STATUS"]);
+        assert_eq!(changes[1].row.get_as::<String, _>("STATUS")?, "closed");
+        assert!(split_changes(vec![])?.is_empty());
+        Ok(())
+    }
+}
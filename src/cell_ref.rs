@@ -0,0 +1,264 @@
+#[cfg(feature = "chrono")]
+use chrono::{
+    naive::{NaiveDate, NaiveDateTime, NaiveTime},
+    DateTime, FixedOffset,
+};
+#[cfg(feature = "time")]
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+use crate::cells::{parse_vector, Cell, NumberMode, RawCell, TimestampZone};
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::interval::Interval;
+
+/// A cell that borrows its text from a [`Partition`](crate::Partition)
+///
+/// These are created by [`Partition::cells_ref`](crate::Partition::cells_ref) and
+/// [`RawCell::to_cell_ref`]. Numbers, booleans, dates, and times are parsed like in [`Cell`],
+/// but text, binary, and JSON are views of the partition's strings, so nothing is allocated for them.
+/// That suits code that inspects values and forwards them somewhere else.
+///
+/// Like [`Cell`], this is `#[non_exhaustive]` because its date and time variants depend on features.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum CellRef<'a> {
+    /// A `NULL` value, like [`Cell::Null`]
+    Null,
+    /// An integer, like [`Cell::Int`]
+    Int(i128),
+    /// A float, like [`Cell::Float`]
+    Float(f64),
+    /// An exact number, like [`Cell::Number`]
+    Number { mantissa: i128, scale: i32 },
+    /// Text, borrowed from the partition
+    Varchar(&'a str),
+    /// Binary, borrowed from the partition, so it's still hex-encoded the way Snowflake sent
+    /// it. [`CellRef::to_cell`] decodes it.
+    Binary(&'a str),
+    /// A boolean, like [`Cell::Boolean`]
+    Boolean(bool),
+    /// JSON text, borrowed from the partition. [`CellRef::to_cell`] parses it.
+    Json(&'a str),
+    /// The text of a vector, like `[1.5,2,3]`, borrowed from the partition.
+    /// [`CellRef::to_cell`] parses it.
+    Vector(&'a str),
+    /// An interval, like [`Cell::Interval`]
+    Interval(Interval),
+    /// A date without a time zone.
+    #[cfg(feature = "chrono")]
+    Date(NaiveDate),
+    /// A time without a time zone.
+    #[cfg(feature = "chrono")]
+    Time(NaiveTime),
+    /// A timestamp with the local time zone, like [`Cell::TimestampLtz`]
+    #[cfg(feature = "chrono")]
+    TimestampLtz(DateTime<FixedOffset>),
+    /// A timestamp without a time zone.
+    #[cfg(feature = "chrono")]
+    TimestampNtz(NaiveDateTime),
+    /// A timestamp with its own offset from UTC.
+    #[cfg(feature = "chrono")]
+    TimestampTz(DateTime<FixedOffset>),
+    /// A date as a `time` type, like [`Cell::TimeDate`]
+    #[cfg(feature = "time")]
+    TimeDate(Date),
+    /// A time as a `time` type, like [`Cell::TimeTime`]
+    #[cfg(feature = "time")]
+    TimeTime(Time),
+    /// A timestamp with the local time zone as a `time` type, like [`Cell::TimeTimestampLtz`]
+    #[cfg(feature = "time")]
+    TimeTimestampLtz(OffsetDateTime),
+    /// A timestamp without a time zone as a `time` type, like [`Cell::TimeTimestampNtz`]
+    #[cfg(feature = "time")]
+    TimeTimestampNtz(PrimitiveDateTime),
+    /// A timestamp with its own offset as a `time` type, like [`Cell::TimeTimestampTz`]
+    #[cfg(feature = "time")]
+    TimeTimestampTz(OffsetDateTime),
+}
+
+impl CellRef<'_> {
+    /// Copy the cell into an owned [`Cell`], decoding binary and parsing JSON
+    ///
+    /// This fails with [`SnowflakeError::InvalidCell`] if binary isn't valid hex or JSON isn't
+    /// valid, which Snowflake doesn't send.
+    pub fn to_cell(&self) -> SnowflakeResult<Cell> {
+        Ok(match *self {
+            CellRef::Null => Cell::Null,
+            CellRef::Int(value) => Cell::Int(value),
+            CellRef::Float(value) => Cell::Float(value),
+            CellRef::Number { mantissa, scale } => Cell::Number { mantissa, scale },
+            CellRef::Varchar(value) => Cell::Varchar(value.into()),
+            CellRef::Binary(value) => {
+                Cell::Binary(hex::decode(value).map_err(|_| SnowflakeError::InvalidCell {
+                    data_type: RawCell::Binary,
+                    value: value.to_owned(),
+                })?)
+            }
+            CellRef::Boolean(value) => Cell::Boolean(value),
+            CellRef::Vector(value) => {
+                Cell::Vector(
+                    parse_vector(value).ok_or_else(|| SnowflakeError::InvalidCell {
+                        data_type: RawCell::Vector,
+                        value: value.to_owned(),
+                    })?,
+                )
+            }
+            CellRef::Interval(value) => Cell::Interval(value),
+            CellRef::Json(value) => Cell::Json(serde_json::from_str(value).map_err(|_| {
+                SnowflakeError::InvalidCell {
+                    data_type: RawCell::Variant,
+                    value: value.to_owned(),
+                }
+            })?),
+            #[cfg(feature = "chrono")]
+            CellRef::Date(value) => Cell::Date(value),
+            #[cfg(feature = "chrono")]
+            CellRef::Time(value) => Cell::Time(value),
+            #[cfg(feature = "chrono")]
+            CellRef::TimestampLtz(value) => Cell::TimestampLtz(value),
+            #[cfg(feature = "chrono")]
+            CellRef::TimestampNtz(value) => Cell::TimestampNtz(value),
+            #[cfg(feature = "chrono")]
+            CellRef::TimestampTz(value) => Cell::TimestampTz(value),
+            #[cfg(feature = "time")]
+            CellRef::TimeDate(value) => Cell::TimeDate(value),
+            #[cfg(feature = "time")]
+            CellRef::TimeTime(value) => Cell::TimeTime(value),
+            #[cfg(feature = "time")]
+            CellRef::TimeTimestampLtz(value) => Cell::TimeTimestampLtz(value),
+            #[cfg(feature = "time")]
+            CellRef::TimeTimestampNtz(value) => Cell::TimeTimestampNtz(value),
+            #[cfg(feature = "time")]
+            CellRef::TimeTimestampTz(value) => Cell::TimeTimestampTz(value),
+        })
+    }
+
+    /// The text of a `Varchar`, or `None` for any other kind of cell
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CellRef::Varchar(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl RawCell {
+    /// Convert a raw value into a [`CellRef`], which borrows text, binary, and JSON from it
+    ///
+    /// Other values are parsed like in [`RawCell::to_cell`], and fail the same way. Binary and
+    /// JSON aren't checked until [`CellRef::to_cell`] decodes them.
+    pub fn to_cell_ref<'a>(&self, value: &'a Option<String>) -> SnowflakeResult<CellRef<'a>> {
+        self.to_cell_ref_with(value, None, NumberMode::Lossy, TimestampZone::Local, true)
+    }
+
+    /// Like [`RawCell::to_cell_ref`], but parses NUMBER cells with this mode, converts
+    /// TIMESTAMP_LTZ cells to this zone, and reads `"null"` like [`RawCell::to_cell_with`]
+    pub(crate) fn to_cell_ref_with<'a>(
+        self,
+        value: &'a Option<String>,
+        scale: Option<i32>,
+        number_mode: NumberMode,
+        timestamp_zone: TimestampZone,
+        nullable: bool,
+    ) -> SnowflakeResult<CellRef<'a>> {
+        let text = match value {
+            Some(text) if text == "null" && self.reads_null_text_as_null(nullable) => {
+                return Ok(CellRef::Null)
+            }
+            Some(text) => text.as_str(),
+            None => return Ok(CellRef::Null),
+        };
+        match self {
+            RawCell::Text => return Ok(CellRef::Varchar(text)),
+            RawCell::Binary => return Ok(CellRef::Binary(text)),
+            RawCell::Variant | RawCell::Object | RawCell::Array => return Ok(CellRef::Json(text)),
+            RawCell::Vector => return Ok(CellRef::Vector(text)),
+            // Without chrono or time, dates and times are passed through as text
+            #[cfg(not(any(feature = "chrono", feature = "time")))]
+            RawCell::Date
+            | RawCell::Time
+            | RawCell::TimestampLtz
+            | RawCell::TimestampNtz
+            | RawCell::TimestampTz => return Ok(CellRef::Varchar(text)),
+            _ => {}
+        }
+        // Everything else is parsed without allocating
+        let cell = self.to_cell_with(value, scale, number_mode, timestamp_zone, nullable)?;
+        Ok(match cell {
+            Cell::Null => CellRef::Null,
+            Cell::Int(value) => CellRef::Int(value),
+            Cell::Float(value) => CellRef::Float(value),
+            Cell::Number { mantissa, scale } => CellRef::Number { mantissa, scale },
+            Cell::Boolean(value) => CellRef::Boolean(value),
+            Cell::Interval(value) => CellRef::Interval(value),
+            #[cfg(feature = "chrono")]
+            Cell::Date(value) => CellRef::Date(value),
+            #[cfg(feature = "chrono")]
+            Cell::Time(value) => CellRef::Time(value),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampLtz(value) => CellRef::TimestampLtz(value),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampNtz(value) => CellRef::TimestampNtz(value),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampTz(value) => CellRef::TimestampTz(value),
+            #[cfg(feature = "time")]
+            Cell::TimeDate(value) => CellRef::TimeDate(value),
+            #[cfg(feature = "time")]
+            Cell::TimeTime(value) => CellRef::TimeTime(value),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampLtz(value) => CellRef::TimeTimestampLtz(value),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampNtz(value) => CellRef::TimeTimestampNtz(value),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampTz(value) => CellRef::TimeTimestampTz(value),
+            Cell::Varchar(_) | Cell::Binary(_) | Cell::Json(_) | Cell::Vector(_) => {
+                unreachable!("text is borrowed above")
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_refs_borrow_text() -> SnowflakeResult<()> {
+        let text = Some("hello".to_owned());
+        match RawCell::Text.to_cell_ref(&text)? {
+            CellRef::Varchar(value) => assert!(std::ptr::eq(value, text.as_deref().unwrap())),
+            other => panic!("unexpected cell {other:?}"),
+        }
+        let binary = Some("cafe".to_owned());
+        assert!(matches!(
+            RawCell::Binary.to_cell_ref(&binary)?,
+            CellRef::Binary("cafe")
+        ));
+        assert!(
+            matches!(RawCell::Binary.to_cell_ref(&binary)?.to_cell()?, Cell::Binary(bytes) if bytes == [0xca, 0xfe])
+        );
+        assert!(matches!(
+            RawCell::Fixed.to_cell_ref(&Some("12.0".into()))?,
+            CellRef::Int(12)
+        ));
+        assert!(matches!(
+            RawCell::Boolean.to_cell_ref(&Some("null".into()))?,
+            CellRef::Null
+        ));
+        assert_eq!(
+            RawCell::Text.to_cell_ref(&Some("null".into()))?.as_str(),
+            Some("null")
+        );
+        assert!(matches!(RawCell::Real.to_cell_ref(&None)?, CellRef::Null));
+        assert!(RawCell::Fixed.to_cell_ref(&Some("twelve".into())).is_err());
+        assert!(CellRef::Binary("xyz").to_cell().is_err());
+        let json = Some(r#"{"a": [1, 2]}"#.to_owned());
+        match RawCell::Object.to_cell_ref(&json)? {
+            CellRef::Json(value) => assert!(std::ptr::eq(value, json.as_deref().unwrap())),
+            other => panic!("unexpected cell {other:?}"),
+        }
+        assert!(
+            matches!(RawCell::Object.to_cell_ref(&json)?.to_cell()?, Cell::Json(value) if value["a"][1] == 2)
+        );
+        Ok(())
+    }
+}
@@ -1,14 +1,208 @@
+#[cfg(feature = "chrono")]
 use chrono::{
     naive::{NaiveDate, NaiveDateTime, NaiveTime},
-    DateTime, Duration, Local, TimeZone,
+    DateTime, FixedOffset, Local, Offset, TimeZone, Utc,
 };
+#[cfg(feature = "time")]
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::errors::{CellConversionError, SnowflakeError, SnowflakeResult};
+use crate::interval::{parse_day_time, parse_year_month, Interval};
+
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+/// Split a wire timestamp like `1672534861.123000000` into whole seconds and nanoseconds
+///
+/// Parsing through `f64` would lose precision, since the seconds alone use most of its digits.
+pub(crate) fn parse_epoch(value: &str) -> Option<(i64, u32)> {
+    let (seconds, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let fraction = fraction.get(..fraction.len().min(9))?;
+    if !fraction.bytes().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u32>().ok()? * 10u32.pow(9 - fraction.len() as u32)
+    };
+    let seconds: i64 = seconds.parse().ok()?;
+    if value.starts_with('-') && nanos > 0 {
+        Some((seconds - 1, 1_000_000_000 - nanos))
+    } else {
+        Some((seconds, nanos))
+    }
+}
+
+/// Parse a wire timestamp like `1672534861.123000000` into an instant in UTC
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+fn parse_instant(value: &str) -> Option<OffsetDateTime> {
+    let (seconds, nanos) = parse_epoch(value)?;
+    OffsetDateTime::from_unix_timestamp_nanos(seconds as i128 * 1_000_000_000 + nanos as i128).ok()
+}
+
+/// Split a wire TIMESTAMP_TZ like `1672534861.000000000 1500` into the timestamp (in UTC) and
+/// its offset from UTC in minutes
+///
+/// Snowflake adds 1440 minutes to the offset so that it's never negative. Without an offset,
+/// the timestamp is in UTC.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn split_offset(value: &str) -> Option<(&str, i32)> {
+    match value.split_once(' ') {
+        Some((epoch, offset)) => Some((epoch, offset.parse::<i32>().ok()? - 1440)),
+        None => Some((value, 0)),
+    }
+}
+
+/// Parse a VECTOR cell like `[1.5,2,3]`
+pub(crate) fn parse_vector(value: &str) -> Option<Vec<f64>> {
+    let elements = value.trim().strip_prefix('[')?.strip_suffix(']')?.trim();
+    if elements.is_empty() {
+        return Some(Vec::new());
+    }
+    elements
+        .split(',')
+        .map(|element| parse_float(element.trim()))
+        .collect()
+}
+
+/// Parse a NUMBER cell, as an integer if it has no fractional part (like `1` or `1.0`)
+fn parse_fixed(value: &str) -> Option<Cell> {
+    let integer = value.strip_suffix(".0").unwrap_or(value);
+    match parse_int(integer) {
+        Some(value) => Some(Cell::Int(value)),
+        None => Some(Cell::Float(parse_float(value)?)),
+    }
+}
+
+/// Parse a NUMBER cell exactly, as an integer if it has no fractional part, or otherwise as
+/// a mantissa and the number of digits after the decimal point (like `150` and `2` for `1.50`)
+fn parse_exact(value: &str) -> Option<Cell> {
+    let (integer, fraction) = match value.split_once('.') {
+        None => return Some(Cell::Int(parse_int(value)?)),
+        Some((integer, "0")) => return Some(Cell::Int(parse_int(integer)?)),
+        Some(parts) => parts,
+    };
+    if !fraction.bytes().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    Some(Cell::Number {
+        mantissa: parse_int(&format!("{integer}{fraction}"))?,
+        scale: fraction.len().try_into().ok()?,
+    })
+}
+
+/// Parse a NUMBER cell of a column whose scale is known, so that every cell of the column is
+/// the same kind: an integer if the scale is 0, and otherwise a float, or an exact number with
+/// the column's scale (so `1.5` is `150` and `2` in a `NUMBER(10,2)` column)
+fn parse_scaled(value: &str, scale: i32, number_mode: NumberMode) -> Option<Cell> {
+    if scale <= 0 {
+        return Some(Cell::Int(parse_int(value)?));
+    }
+    if number_mode == NumberMode::Lossy {
+        return Some(Cell::Float(parse_float(value)?));
+    }
+    let (mantissa, digits) = match parse_exact(value)? {
+        Cell::Int(mantissa) => (mantissa, 0),
+        Cell::Number { mantissa, scale } => (mantissa, scale),
+        _ => return None,
+    };
+    let factor = 10_i128.checked_pow(scale.checked_sub(digits)?.try_into().ok()?)?;
+    Some(Cell::Number {
+        mantissa: mantissa.checked_mul(factor)?,
+        scale,
+    })
+}
+
+/// Format an exact number, like `1.50` for a mantissa of `150` and a scale of `2`
+fn decimal(mantissa: i128, scale: i32) -> String {
+    let sign = if mantissa < 0 { "-" } else { "" };
+    let digits = mantissa.unsigned_abs().to_string();
+    if mantissa == 0 && scale <= 0 {
+        return digits;
+    }
+    if scale <= 0 {
+        return format!(
+            "{sign}{digits}{}",
+            "0".repeat(scale.unsigned_abs() as usize)
+        );
+    }
+    let scale = scale as usize;
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{sign}{integer}.{fraction}")
+}
+
+#[cfg(feature = "fast-parse")]
+fn parse_int(value: &str) -> Option<i128> {
+    lexical_core::parse(value.as_bytes()).ok()
+}
+
+#[cfg(not(feature = "fast-parse"))]
+fn parse_int(value: &str) -> Option<i128> {
+    value.parse().ok()
+}
+
+/// Parse a float, falling back to the standard library for spellings of `inf` and `NaN`
+/// that lexical doesn't accept
+#[cfg(feature = "fast-parse")]
+fn parse_float(value: &str) -> Option<f64> {
+    lexical_core::parse(value.as_bytes())
+        .ok()
+        .or_else(|| value.parse().ok())
+}
+
+#[cfg(not(feature = "fast-parse"))]
+fn parse_float(value: &str) -> Option<f64> {
+    value.parse().ok()
+}
+
+/// Format a time like `01:02:03` or `01:02:03.500`, the same way chrono does
+#[cfg(feature = "time")]
+pub(crate) fn iso_time(time: time::Time) -> String {
+    let seconds = format!(
+        "{:02}:{:02}:{:02}",
+        time.hour(),
+        time.minute(),
+        time.second()
+    );
+    match time.nanosecond() {
+        0 => seconds,
+        nanos if nanos % 1_000_000 == 0 => format!("{seconds}.{:03}", nanos / 1_000_000),
+        nanos if nanos % 1_000 == 0 => format!("{seconds}.{:06}", nanos / 1_000),
+        nanos => format!("{seconds}.{nanos:09}"),
+    }
+}
+
+/// Format an offset like `+01:00` (or `Z` for UTC), the same way chrono does
+#[cfg(feature = "time")]
+fn iso_offset(offset: UtcOffset) -> String {
+    if offset.is_utc() {
+        return "Z".to_owned();
+    }
+    hours_and_minutes(offset)
+}
+
+/// Format an offset like `+01:00`, even for UTC
+#[cfg(feature = "time")]
+fn hours_and_minutes(offset: UtcOffset) -> String {
+    let (hours, minutes, _) = offset.as_hms();
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    format!("{sign}{:02}:{:02}", hours.abs(), minutes.abs())
+}
 
 /// The format Snowflake used for serializing data in a column
 ///
 /// This is not usually necessary unless you intend to implement your own
 /// deserialization of Snowflake data.
-#[derive(Clone, Debug, serde::Deserialize)]
+///
+/// This is `#[non_exhaustive]` so that new Snowflake types can be added without breaking `match`es.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum RawCell {
     /// A 128-bit signed integer, 38 digits of precision.
     Fixed,
@@ -28,72 +222,241 @@ pub enum RawCell {
     TimestampLtz,
     /// A timestamp without a time zone.
     TimestampNtz,
-    /// A timestamp with a time zone for each value. This is not supported yet.
+    /// A timestamp with a time zone for each value, as the number of seconds since the epoch
+    /// (in UTC) followed by the offset in minutes plus 1440, like `1672534861.000000000 1500`.
     TimestampTz,
+    /// Semi-structured data of any type, as JSON text.
+    Variant,
+    /// A semi-structured object, as JSON text.
+    Object,
+    /// A semi-structured array, as JSON text.
+    Array,
+    /// A fixed length array of 32-bit integers or floats, like `[1.5,2,3]`.
+    Vector,
+    /// A span of years and months, as a number of months, like `14`.
+    IntervalYearMonth,
+    /// A span of days and time, as a number of seconds, like `93784.500000000`.
+    IntervalDayTime,
+}
+
+/// How NUMBER cells are parsed
+///
+/// Set it for a client with [`SnowflakeClient::with_number_mode`](crate::SnowflakeClient::with_number_mode),
+/// or for a statement with [`Statement::with_number_mode`](crate::Statement::with_number_mode).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NumberMode {
+    /// Integers are [`Cell::Int`]s and other numbers are [`Cell::Float`]s, which is convenient
+    /// but only keeps about 15 significant digits
+    #[default]
+    Lossy,
+    /// Integers are [`Cell::Int`]s and other numbers are [`Cell::Number`]s, which keep every
+    /// digit, for amounts of money and other decimals that must not be rounded
+    Exact,
+}
+
+/// The time zone that TIMESTAMP_LTZ cells are converted to
+///
+/// Snowflake sends TIMESTAMP_LTZ cells as instants, so this only decides which offset they are
+/// shown with. TIMESTAMP_NTZ cells are wall clock times without a zone, so they are never
+/// shifted, whatever the zone is.
+///
+/// Set it for a client with [`SnowflakeClient::with_timestamp_zone`](crate::SnowflakeClient::with_timestamp_zone).
+///
+/// This is `#[non_exhaustive]` because [`TimestampZone::Named`] only exists with the
+/// `chrono-tz` feature, which any crate in the build can enable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimestampZone {
+    /// The time zone of the machine running this process
+    ///
+    /// With the `time` feature, this is UTC when the local offset can't be determined, since
+    /// the time crate won't look it up in multithreaded processes on some platforms.
+    #[default]
+    Local,
+    /// UTC, which suits servers that run in one zone but serve users in others
+    Utc,
+    /// A named time zone, like the session's TIMEZONE parameter
+    ///
+    /// Names like `America/Los_Angeles`, which `SHOW PARAMETERS LIKE 'TIMEZONE'` returns,
+    /// can be parsed into a [`chrono_tz::Tz`] with `str::parse`.
+    #[cfg(feature = "chrono-tz")]
+    Named(chrono_tz::Tz),
+}
+
+impl TimestampZone {
+    /// The offset from UTC of this zone at an instant
+    #[cfg(feature = "chrono")]
+    fn offset_at(self, instant: DateTime<Utc>) -> FixedOffset {
+        let instant = instant.naive_utc();
+        match self {
+            TimestampZone::Local => Local.offset_from_utc_datetime(&instant).fix(),
+            TimestampZone::Utc => Utc.fix(),
+            #[cfg(feature = "chrono-tz")]
+            TimestampZone::Named(zone) => zone.offset_from_utc_datetime(&instant).fix(),
+        }
+    }
+
+    /// The offset from UTC of this zone at an instant
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    fn offset_at(self, instant: OffsetDateTime) -> UtcOffset {
+        match self {
+            TimestampZone::Local => UtcOffset::local_offset_at(instant).unwrap_or(UtcOffset::UTC),
+            TimestampZone::Utc => UtcOffset::UTC,
+        }
+    }
 }
 
 impl RawCell {
     /// Convert a RawCell into a Cell.
     ///
-    /// There are many possible panics in this conversion,
-    /// but they depend generally on Snowflake returning a value that can be parsed.
+    /// This fails with [`SnowflakeError::InvalidCell`] if the value can't be parsed as this
+    /// type, which Snowflake shouldn't send, so that one bad cell doesn't bring down the process.
     ///
-    /// - Decimals are not supported. Number type columns are converted to i128 if possible,
-    ///   otherwise f64. So there can be a loss of precision, which is a tradeoff for convenience.
-    /// - For the same reason, NUMBER columns can contain mixed types: Int and Float
-    pub fn to_cell(&self, value: &Option<String>) -> Cell {
-        let value = if let Some(value) = value {
-            value
-        } else {
-            return Cell::Null;
+    /// - Number type columns are converted to i128 if possible, otherwise f64. So there can be
+    ///   a loss of precision, which is a tradeoff for convenience. Statements can use
+    ///   [`NumberMode::Exact`] instead, to get [`Cell::Number`]s.
+    /// - For the same reason, NUMBER cells can be mixed types: Int and Float. Partitions know
+    ///   the scale of each column, so they avoid this: a column with a scale of 0 is all Ints,
+    ///   and any other NUMBER column is all Floats (or Numbers).
+    /// - TIMESTAMP_LTZ cells are converted to the local time zone. Clients can choose another
+    ///   [`TimestampZone`].
+    pub fn to_cell(&self, value: &Option<String>) -> SnowflakeResult<Cell> {
+        self.to_cell_with(value, None, NumberMode::Lossy, TimestampZone::Local, true)
+    }
+
+    /// Like [`RawCell::to_cell`], but parses NUMBER cells with this mode, and with the scale
+    /// of their column if it's known, and converts TIMESTAMP_LTZ cells to this zone. Results
+    /// that aren't `nullable` read `"null"` like [`RawCell::reads_null_text_as_null`].
+    pub(crate) fn to_cell_with(
+        self,
+        value: &Option<String>,
+        scale: Option<i32>,
+        number_mode: NumberMode,
+        timestamp_zone: TimestampZone,
+        nullable: bool,
+    ) -> SnowflakeResult<Cell> {
+        let value = match value {
+            Some(value) if value == "null" && self.reads_null_text_as_null(nullable) => {
+                return Ok(Cell::Null)
+            }
+            Some(value) => value,
+            None => return Ok(Cell::Null),
         };
+        self.parse(value, scale, number_mode, timestamp_zone)
+            .ok_or_else(|| SnowflakeError::InvalidCell {
+                data_type: self,
+                value: value.clone(),
+            })
+    }
+
+    /// Whether the text `"null"` is read as a `NULL` in this type's columns
+    ///
+    /// Without `nullable=true`, Snowflake sends `NULL` as `"null"`, which is only ambiguous for
+    /// text and semi-structured data. Text always reads it as text. Semi-structured data reads
+    /// it as a JSON `null`, unless the result isn't `nullable`: then it's read as a `NULL`,
+    /// like in every other type, where `"null"` can't be a value.
+    pub(crate) fn reads_null_text_as_null(self, nullable: bool) -> bool {
         match self {
-            // It seems pretty unlikely snowflake will return a value that can't be parsed.
-            // Also, you probably couldn't do much with it anyway,
-            // But would Result still be better?
-            RawCell::Fixed => match value.trim_end_matches(".0").parse() {
-                Ok(value) => Cell::Int(value),
-                Err(_) => Cell::Float(value.parse().unwrap()),
+            RawCell::Text => false,
+            RawCell::Variant | RawCell::Object | RawCell::Array => !nullable,
+            _ => true,
+        }
+    }
+
+    /// Parse a value that isn't NULL, or `None` if it isn't valid for this type
+    fn parse(
+        &self,
+        value: &str,
+        scale: Option<i32>,
+        number_mode: NumberMode,
+        timestamp_zone: TimestampZone,
+    ) -> Option<Cell> {
+        // Only dates and times use the zone
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        let _ = timestamp_zone;
+        Some(match self {
+            RawCell::Fixed => match (scale, number_mode) {
+                (Some(scale), _) => parse_scaled(value, scale, number_mode)?,
+                (None, NumberMode::Lossy) => parse_fixed(value)?,
+                (None, NumberMode::Exact) => parse_exact(value)?,
             },
-            RawCell::Real => Cell::Float(value.parse().unwrap()),
-            RawCell::Text => Cell::Varchar(value.to_owned()),
-            RawCell::Binary => Cell::Binary(hex::decode(value).unwrap()),
-            RawCell::Boolean => Cell::Boolean(value.parse().unwrap()),
+            RawCell::Real => Cell::Float(parse_float(value)?),
+            RawCell::Text => Cell::Varchar(value.into()),
+            RawCell::Binary => Cell::Binary(hex::decode(value).ok()?),
+            RawCell::Boolean => Cell::Boolean(value.parse().ok()?),
+            RawCell::Variant | RawCell::Object | RawCell::Array => {
+                Cell::Json(serde_json::from_str(value).ok()?)
+            }
+            RawCell::Vector => Cell::Vector(parse_vector(value)?),
+            RawCell::IntervalYearMonth => Cell::Interval(parse_year_month(value)?),
+            RawCell::IntervalDayTime => Cell::Interval(parse_day_time(value)?),
+            #[cfg(feature = "chrono")]
             RawCell::Date => Cell::Date(
-                NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
-                    + Duration::days(value.parse().unwrap()),
+                NaiveDate::from_ymd_opt(1970, 1, 1)
+                    .unwrap()
+                    .checked_add_signed(chrono::Duration::try_days(value.parse().ok()?)?)?,
             ),
+            #[cfg(feature = "chrono")]
             RawCell::Time => {
-                let seconds_since_epoch: f64 = value.parse().unwrap();
-                Cell::Time(
-                    NaiveTime::from_num_seconds_from_midnight_opt(
-                        seconds_since_epoch as u32,
-                        (seconds_since_epoch.fract() * 1e9) as u32,
-                    )
-                    .unwrap(),
-                )
+                let seconds_since_epoch: f64 = value.parse().ok()?;
+                Cell::Time(NaiveTime::from_num_seconds_from_midnight_opt(
+                    seconds_since_epoch as u32,
+                    (seconds_since_epoch.fract() * 1e9) as u32,
+                )?)
             }
+            #[cfg(feature = "chrono")]
             RawCell::TimestampLtz => {
-                let seconds_since_epoch: f64 = value.parse().unwrap();
-                Cell::TimestampLtz(Local.timestamp_nanos(
-                    seconds_since_epoch as i64 + (seconds_since_epoch.fract() * 1e9) as i64,
-                ))
+                let (seconds, nanos) = parse_epoch(value)?;
+                let instant = DateTime::from_timestamp(seconds, nanos)?;
+                Cell::TimestampLtz(instant.with_timezone(&timestamp_zone.offset_at(instant)))
             }
+            #[cfg(feature = "chrono")]
             RawCell::TimestampNtz => {
-                let seconds_since_epoch: f64 = value.parse().unwrap();
-                Cell::TimestampNtz(
-                    NaiveDateTime::from_timestamp_opt(
-                        seconds_since_epoch as i64,
-                        (seconds_since_epoch.fract() * 1e9) as u32,
-                    )
-                    .unwrap(),
-                )
+                let (seconds, nanos) = parse_epoch(value)?;
+                Cell::TimestampNtz(DateTime::from_timestamp(seconds, nanos)?.naive_utc())
             }
+            #[cfg(feature = "chrono")]
             RawCell::TimestampTz => {
-                // This is just too complex to support yet
-                Cell::Null
+                let (epoch, offset) = split_offset(value)?;
+                let (seconds, nanos) = parse_epoch(epoch)?;
+                let offset = FixedOffset::east_opt(offset * 60)?;
+                Cell::TimestampTz(DateTime::from_timestamp(seconds, nanos)?.with_timezone(&offset))
             }
-        }
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            RawCell::Date => Cell::TimeDate(
+                Date::from_julian_day(UNIX_EPOCH_JULIAN_DAY.checked_add(value.parse().ok()?)?)
+                    .ok()?,
+            ),
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            RawCell::Time => Cell::TimeTime(
+                Time::MIDNIGHT + time::Duration::checked_seconds_f64(value.parse().ok()?)?,
+            ),
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            RawCell::TimestampLtz => {
+                let instant = parse_instant(value)?;
+                Cell::TimeTimestampLtz(instant.to_offset(timestamp_zone.offset_at(instant)))
+            }
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            RawCell::TimestampNtz => {
+                let timestamp = parse_instant(value)?;
+                Cell::TimeTimestampNtz(PrimitiveDateTime::new(timestamp.date(), timestamp.time()))
+            }
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            RawCell::TimestampTz => {
+                let (epoch, offset) = split_offset(value)?;
+                let timestamp = parse_instant(epoch)?;
+                Cell::TimeTimestampTz(
+                    timestamp.to_offset(UtcOffset::from_whole_seconds(offset * 60).ok()?),
+                )
+            }
+            // Without chrono or time, pass the wire format through (e.g. seconds since the epoch)
+            #[cfg(not(any(feature = "chrono", feature = "time")))]
+            RawCell::Date
+            | RawCell::Time
+            | RawCell::TimestampLtz
+            | RawCell::TimestampNtz
+            | RawCell::TimestampTz => Cell::Varchar(value.into()),
+        })
     }
 }
 
@@ -101,37 +464,258 @@ impl RawCell {
 ///
 /// Snowflake returns these as a list of Strings; these are the result of parsing those strings,
 /// and as such there are some caveats to be aware of.
-#[derive(Clone, Debug)]
+///
+/// The date and time variants require the `chrono` feature (enabled by default).
+/// If you prefer the `time` crate, disable default features and enable `time` instead, and
+/// date and time columns are returned as the `Time` variants, like `Cell::TimeDate`.
+/// Without either, they are returned as `Varchar`s in Snowflake's wire format.
+///
+/// This is `#[non_exhaustive]` because the date and time variants depend on features that any
+/// crate in the build can enable, so a `match` needs a `_` arm. Enabling `chrono` also changes
+/// which variants are returned, since `chrono` takes precedence, so rather than matching on the
+/// `Time` variants, convert cells with [`FromCell`]: `time` types convert from either kind.
+///
+/// Cells implement `Serialize` and `Deserialize` with a tag for each variant, like `{"Int": 1}`
+/// or `"Null"`, so they round trip through a cache or another service without losing their
+/// types. (Unlike converting to a `serde_json::Value`, which is simpler to consume but doesn't
+/// round trip.) A float that is NaN or infinite can't be represented in JSON, so use a format
+/// that can, like MessagePack, for such results.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
 pub enum Cell {
     /// A `NULL` value. Any column could be null unless it is declared as `NOT NULL`,
     /// but the driver is not aware of this information from the metadata.
     Null,
     /// A 128-bit signed integer, 38 digits of precision.
     /// Any NUMBER cell that can be represented as an integer will be, but
-    /// this means that NUMBER columns can contain mixed types: Int and Float.
+    /// without the column's scale (like in [`RawCell::to_cell`]), this means that NUMBER
+    /// cells can be mixed types: Int and Float.
     ///
-    /// e.g. `["1", "1.0", "1.1"]` will be parsed as `[Int(1), Int(1), Float(1.1)]`
+    /// e.g. `["1", "1.0", "1.1"]` will be parsed as `[Int(1), Int(1), Float(1.1)]`, while in a
+    /// partition they are all Floats if the column's scale is 1.
     Int(i128),
     /// A 64-bit floating point number, 15 digits of precision.
     /// Any NUMBER cell that cannot be represented as an integer will be parsed as a float.
     /// Additionally, all REAL columns will be parsed as floats.
     /// This is lossy, but intended for convenience.
     Float(f64),
+    /// An exact decimal number, worth `mantissa / 10^scale`, so `1.50` has a mantissa of
+    /// `150` and a scale of `2`.
+    /// These are only used with [`NumberMode::Exact`], for NUMBER cells that aren't integers,
+    /// and in a partition they have the scale of their column.
+    Number { mantissa: i128, scale: i32 },
     /// A variable length string. It must be valid UTF-8.
-    Varchar(String),
+    ///
+    /// The text is reference counted, so cloning a cell (or a [`Row`](crate::Row)) to send it
+    /// to other tasks shares the text rather than copying it.
+    Varchar(Arc<str>),
     /// A variable length binary string.
     /// (This is serialized over the wire as a hex string, so these are not bandwidth efficient.)
     Binary(Vec<u8>),
     /// A boolean value.
     Boolean(bool),
+    /// Semi-structured data from a VARIANT, OBJECT, or ARRAY column.
+    /// A JSON `null` is read as `Json(Value::Null)`, and only SQL `NULL` is read as `Null`,
+    /// except in results from [`Statement::with_non_nullable_results`], which can't tell them
+    /// apart.
+    ///
+    /// [`Statement::with_non_nullable_results`]: crate::Statement::with_non_nullable_results
+    Json(serde_json::Value),
+    /// The elements of a VECTOR, like an embedding.
+    /// Both INT and FLOAT vectors are held as `f64`s, which represent their elements exactly.
+    Vector(Vec<f64>),
+    /// A span of time, from an `INTERVAL YEAR TO MONTH` or `INTERVAL DAY TO SECOND` cell.
+    Interval(Interval),
     /// A date without a time zone.
+    #[cfg(feature = "chrono")]
     Date(NaiveDate),
     /// A time without a time zone.
+    #[cfg(feature = "chrono")]
     Time(NaiveTime),
-    /// A timestamp with the local time zone. (This is not extensively tested)
-    TimestampLtz(DateTime<Local>),
-    /// A timestamp without a time zone. Presumably this is UTC, but it is not specified.
+    /// A timestamp with the local time zone, with the offset of the client's [`TimestampZone`].
+    #[cfg(feature = "chrono")]
+    TimestampLtz(DateTime<FixedOffset>),
+    /// A timestamp without a time zone, as the wall clock time that was stored.
+    #[cfg(feature = "chrono")]
     TimestampNtz(NaiveDateTime),
+    /// A timestamp with its own offset from UTC.
+    #[cfg(feature = "chrono")]
+    TimestampTz(DateTime<FixedOffset>),
+    /// A date without a time zone, as a `time` type, like [`Cell::Date`].
+    #[cfg(feature = "time")]
+    TimeDate(Date),
+    /// A time without a time zone, as a `time` type, like [`Cell::Time`].
+    #[cfg(feature = "time")]
+    TimeTime(Time),
+    /// A timestamp with the local time zone, as a `time` type, like [`Cell::TimestampLtz`].
+    #[cfg(feature = "time")]
+    TimeTimestampLtz(OffsetDateTime),
+    /// A timestamp without a time zone, as a `time` type, like [`Cell::TimestampNtz`].
+    #[cfg(feature = "time")]
+    TimeTimestampNtz(PrimitiveDateTime),
+    /// A timestamp with its own offset from UTC, as a `time` type, like [`Cell::TimestampTz`].
+    #[cfg(feature = "time")]
+    TimeTimestampTz(OffsetDateTime),
+}
+
+/// Cells are equal if they are the same variant with equal values, so `Int(1)` doesn't equal
+/// `Float(1.0)`.
+///
+/// Floats (including the elements of vectors) are compared by their bits, like
+/// [`f64::total_cmp`], rather than with `==`: NaN equals itself, and `0.0` doesn't equal `-0.0`.
+/// That makes cells `Eq` and `Hash`, so they can be used as map keys. Timestamps with time
+/// zones are equal if they are the same instant, whatever their offsets. Exact numbers are
+/// equal if they have the same digits, so `1.50` doesn't equal `1.5`.
+impl PartialEq for Cell {
+    fn eq(&self, other: &Cell) -> bool {
+        match (self, other) {
+            (Cell::Null, Cell::Null) => true,
+            (Cell::Int(a), Cell::Int(b)) => a == b,
+            (Cell::Float(a), Cell::Float(b)) => a.to_bits() == b.to_bits(),
+            (
+                Cell::Number { mantissa, scale },
+                Cell::Number {
+                    mantissa: other_mantissa,
+                    scale: other_scale,
+                },
+            ) => mantissa == other_mantissa && scale == other_scale,
+            (Cell::Varchar(a), Cell::Varchar(b)) => a == b,
+            (Cell::Binary(a), Cell::Binary(b)) => a == b,
+            (Cell::Boolean(a), Cell::Boolean(b)) => a == b,
+            (Cell::Json(a), Cell::Json(b)) => a == b,
+            (Cell::Vector(a), Cell::Vector(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+            }
+            (Cell::Interval(a), Cell::Interval(b)) => a == b,
+            #[cfg(feature = "chrono")]
+            (Cell::Date(a), Cell::Date(b)) => a == b,
+            #[cfg(feature = "chrono")]
+            (Cell::Time(a), Cell::Time(b)) => a == b,
+            #[cfg(feature = "chrono")]
+            (Cell::TimestampLtz(a), Cell::TimestampLtz(b)) => a == b,
+            #[cfg(feature = "chrono")]
+            (Cell::TimestampNtz(a), Cell::TimestampNtz(b)) => a == b,
+            #[cfg(feature = "chrono")]
+            (Cell::TimestampTz(a), Cell::TimestampTz(b)) => a == b,
+            #[cfg(feature = "time")]
+            (Cell::TimeDate(a), Cell::TimeDate(b)) => a == b,
+            #[cfg(feature = "time")]
+            (Cell::TimeTime(a), Cell::TimeTime(b)) => a == b,
+            #[cfg(feature = "time")]
+            (Cell::TimeTimestampLtz(a), Cell::TimeTimestampLtz(b)) => a == b,
+            #[cfg(feature = "time")]
+            (Cell::TimeTimestampNtz(a), Cell::TimeTimestampNtz(b)) => a == b,
+            #[cfg(feature = "time")]
+            (Cell::TimeTimestampTz(a), Cell::TimeTimestampTz(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Cell {}
+
+impl Hash for Cell {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Cell::Null => {}
+            Cell::Int(value) => value.hash(state),
+            Cell::Float(value) => value.to_bits().hash(state),
+            Cell::Number { mantissa, scale } => {
+                mantissa.hash(state);
+                scale.hash(state);
+            }
+            Cell::Varchar(value) => value.hash(state),
+            Cell::Binary(value) => value.hash(state),
+            Cell::Boolean(value) => value.hash(state),
+            Cell::Json(value) => value.hash(state),
+            Cell::Vector(value) => {
+                value.len().hash(state);
+                for element in value {
+                    element.to_bits().hash(state);
+                }
+            }
+            Cell::Interval(value) => value.hash(state),
+            #[cfg(feature = "chrono")]
+            Cell::Date(value) => value.hash(state),
+            #[cfg(feature = "chrono")]
+            Cell::Time(value) => value.hash(state),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampLtz(value) => value.hash(state),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampNtz(value) => value.hash(state),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampTz(value) => value.hash(state),
+            #[cfg(feature = "time")]
+            Cell::TimeDate(value) => value.hash(state),
+            #[cfg(feature = "time")]
+            Cell::TimeTime(value) => value.hash(state),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampLtz(value) => value.hash(state),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampNtz(value) => value.hash(state),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampTz(value) => value.hash(state),
+        }
+    }
+}
+
+/// Cells display the way Snowflake prints them by default, for CSV files and log lines
+///
+/// `NULL` is empty, binary is uppercase hex, semi-structured data is compact JSON, and dates
+/// and times are ISO 8601 with a space between the date and the time, like
+/// `2023-01-01 01:01:01.500 +01:00`. Nothing is quoted or escaped, so text containing the
+/// delimiter needs to be handled by the caller.
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Null => Ok(()),
+            Cell::Int(value) => write!(f, "{value}"),
+            Cell::Float(value) => write!(f, "{value}"),
+            Cell::Number { mantissa, scale } => f.write_str(&decimal(*mantissa, *scale)),
+            Cell::Varchar(value) => f.write_str(value),
+            Cell::Binary(value) => f.write_str(&hex::encode_upper(value)),
+            Cell::Boolean(value) => write!(f, "{value}"),
+            Cell::Json(value) => write!(f, "{value}"),
+            Cell::Vector(value) => {
+                f.write_str("[")?;
+                for (ix, element) in value.iter().enumerate() {
+                    if ix > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                f.write_str("]")
+            }
+            Cell::Interval(value) => write!(f, "{value}"),
+            #[cfg(feature = "chrono")]
+            Cell::Date(value) => write!(f, "{value}"),
+            #[cfg(feature = "chrono")]
+            Cell::Time(value) => write!(f, "{value}"),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampLtz(value) => write!(f, "{value}"),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampNtz(value) => write!(f, "{value}"),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampTz(value) => write!(f, "{value}"),
+            #[cfg(feature = "time")]
+            Cell::TimeDate(value) => write!(f, "{value}"),
+            #[cfg(feature = "time")]
+            Cell::TimeTime(value) => f.write_str(&iso_time(*value)),
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampNtz(value) => {
+                write!(f, "{} {}", value.date(), iso_time(value.time()))
+            }
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampLtz(value) | Cell::TimeTimestampTz(value) => write!(
+                f,
+                "{} {} {}",
+                value.date(),
+                iso_time(value.time()),
+                hours_and_minutes(value.offset())
+            ),
+        }
+    }
 }
 
 impl From<Cell> for serde_json::Value {
@@ -147,13 +731,1084 @@ impl From<Cell> for serde_json::Value {
             Int(value) if value.abs() < (1 << 53) => json!(value as i64),
             Int(value) => json!(value.to_string()),
             Float(value) => json!(value),
-            Varchar(value) => json!(value),
+            // The same goes for exact numbers, which are written with all of their digits
+            Number { mantissa, scale } if mantissa.abs() < (1 << 53) => {
+                match decimal(mantissa, scale).parse() {
+                    Ok(number) => serde_json::Value::Number(number),
+                    Err(_) => json!(decimal(mantissa, scale)),
+                }
+            }
+            Number { mantissa, scale } => json!(decimal(mantissa, scale)),
+            Varchar(value) => json!(&*value),
             Binary(value) => json!(hex::encode(value)),
             Boolean(value) => json!(value),
+            Json(value) => value,
+            Vector(value) => json!(value),
+            Interval(value) => json!(value.to_string()),
+            #[cfg(feature = "chrono")]
             Date(value) => json!(value),
+            #[cfg(feature = "chrono")]
             Time(value) => json!(value),
+            #[cfg(feature = "chrono")]
             TimestampLtz(value) => json!(value),
+            #[cfg(feature = "chrono")]
             TimestampNtz(value) => json!(value),
+            #[cfg(feature = "chrono")]
+            TimestampTz(value) => json!(value),
+            // Match chrono's ISO 8601 format, rather than time's default serde format
+            #[cfg(feature = "time")]
+            TimeDate(value) => json!(value.to_string()),
+            #[cfg(feature = "time")]
+            TimeTime(value) => json!(iso_time(value)),
+            #[cfg(feature = "time")]
+            TimeTimestampNtz(value) => {
+                json!(format!("{}T{}", value.date(), iso_time(value.time())))
+            }
+            #[cfg(feature = "time")]
+            TimeTimestampLtz(value) | TimeTimestampTz(value) => json!(format!(
+                "{}T{}{}",
+                value.date(),
+                iso_time(value.time()),
+                iso_offset(value.offset())
+            )),
+        }
+    }
+}
+
+/// Accessors, like the ones of `serde_json::Value`, which return `None` for other kinds of cells
+///
+/// Numbers are converted like [`FromCell`]: integers only if they fit, and floats from either.
+/// Dates and times are `chrono` types here; for `time` types, use [`FromCell`], like
+/// `time::Date::try_from(&cell)`.
+impl Cell {
+    /// Whether the cell is `NULL`
+    pub fn is_null(&self) -> bool {
+        matches!(self, Cell::Null)
+    }
+
+    /// The value of an integer cell, if it fits in an `i64`
+    pub fn as_i64(&self) -> Option<i64> {
+        i64::from_cell(self).ok()
+    }
+
+    /// The value of an integer cell, if it fits in a `u64`
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::from_cell(self).ok()
+    }
+
+    /// The value of an integer cell
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Cell::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The mantissa and scale of an exact number, like `(150, 2)` for `1.50`
+    pub fn as_number(&self) -> Option<(i128, i32)> {
+        match self {
+            Cell::Number { mantissa, scale } => Some((*mantissa, *scale)),
+            _ => None,
+        }
+    }
+
+    /// The value of a number cell, as an `f64` (which is lossy for large integers and exact
+    /// numbers)
+    pub fn as_f64(&self) -> Option<f64> {
+        f64::from_cell(self).ok()
+    }
+
+    /// The text of a varchar cell
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Cell::Varchar(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a boolean cell
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Cell::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The bytes of a binary cell
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Cell::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a semi-structured cell
+    pub fn as_json(&self) -> Option<&serde_json::Value> {
+        match self {
+            Cell::Json(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The elements of a vector cell
+    pub fn as_vector(&self) -> Option<&[f64]> {
+        match self {
+            Cell::Vector(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of an interval cell
+    pub fn as_interval(&self) -> Option<Interval> {
+        match self {
+            Cell::Interval(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value of a date cell
+    #[cfg(feature = "chrono")]
+    pub fn as_date(&self) -> Option<NaiveDate> {
+        NaiveDate::from_cell(self).ok()
+    }
+
+    /// The value of a time cell
+    #[cfg(feature = "chrono")]
+    pub fn as_time(&self) -> Option<NaiveTime> {
+        NaiveTime::from_cell(self).ok()
+    }
+
+    /// The value of a TIMESTAMP_NTZ cell
+    #[cfg(feature = "chrono")]
+    pub fn as_timestamp_ntz(&self) -> Option<NaiveDateTime> {
+        NaiveDateTime::from_cell(self).ok()
+    }
+
+    /// The instant of a TIMESTAMP_LTZ or TIMESTAMP_TZ cell, in UTC
+    #[cfg(feature = "chrono")]
+    pub fn as_timestamp(&self) -> Option<DateTime<chrono::Utc>> {
+        DateTime::<chrono::Utc>::from_cell(self).ok()
+    }
+
+    /// A short name for the kind of cell, for error messages
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Cell::Null => "NULL",
+            Cell::Int(_) => "an integer",
+            Cell::Float(_) => "a float",
+            Cell::Number { .. } => "a number",
+            Cell::Varchar(_) => "a varchar",
+            Cell::Binary(_) => "binary",
+            Cell::Boolean(_) => "a boolean",
+            Cell::Json(_) => "JSON",
+            Cell::Vector(_) => "a vector",
+            Cell::Interval(_) => "an interval",
+            #[cfg(feature = "chrono")]
+            Cell::Date(_) => "a date",
+            #[cfg(feature = "chrono")]
+            Cell::Time(_) => "a time",
+            #[cfg(feature = "chrono")]
+            Cell::TimestampLtz(_) => "a timestamp_ltz",
+            #[cfg(feature = "chrono")]
+            Cell::TimestampNtz(_) => "a timestamp_ntz",
+            #[cfg(feature = "chrono")]
+            Cell::TimestampTz(_) => "a timestamp_tz",
+            #[cfg(feature = "time")]
+            Cell::TimeDate(_) => "a date",
+            #[cfg(feature = "time")]
+            Cell::TimeTime(_) => "a time",
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampLtz(_) => "a timestamp_ltz",
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampNtz(_) => "a timestamp_ntz",
+            #[cfg(feature = "time")]
+            Cell::TimeTimestampTz(_) => "a timestamp_tz",
+        }
+    }
+}
+
+/// Types that can be extracted from a [`Cell`], used by [`Row::get_as`](crate::Row::get_as)
+/// and [`Statement::fetch_scalar`](crate::Statement::fetch_scalar)
+///
+/// This is implemented for integers (with a range check), floats, `bool`, `String`, `Vec<u8>`,
+/// the date and time types of the enabled feature, `serde_json::Value`, and `Cell` itself.
+/// Use `Option<T>` to accept `NULL`. These types (and their `Option`s) also implement
+/// `TryFrom<Cell>` and `TryFrom<&Cell>`, so `i64::try_from(cell)?` works too.
+///
+/// Implement it for your own types (like IDs or enums) to extract them directly, usually by
+/// starting from one of the supported types:
+///
+/// ```rust
+/// use light_snowflake_connector::{Cell, CellConversionError, FromCell};
+///
+/// struct UserId(u64);
+///
+/// impl FromCell for UserId {
+///     fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+///         u64::from_cell(cell).map(UserId)
+///     }
+/// }
+/// ```
+pub trait FromCell: Sized {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError>;
+}
+
+impl CellConversionError {
+    fn new(expected: &'static str, cell: &Cell) -> CellConversionError {
+        CellConversionError {
+            expected,
+            found: cell.kind(),
+        }
+    }
+}
+
+impl FromCell for Cell {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        Ok(cell.clone())
+    }
+}
+
+impl<T: FromCell> FromCell for Option<T> {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::Null => Ok(None),
+            cell => T::from_cell(cell).map(Some),
+        }
+    }
+}
+
+macro_rules! impl_from_cell_int {
+    ($($ty: ty),*) => {$(
+        impl FromCell for $ty {
+            fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+                match cell {
+                    Cell::Int(value) => (*value)
+                        .try_into()
+                        .map_err(|_| CellConversionError::new(stringify!($ty), cell)),
+                    cell => Err(CellConversionError::new(stringify!($ty), cell)),
+                }
+            }
+        }
+    )*};
+}
+impl_from_cell_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl FromCell for f64 {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::Float(value) => Ok(*value),
+            Cell::Int(value) => Ok(*value as f64),
+            Cell::Number { mantissa, scale } => decimal(*mantissa, *scale)
+                .parse()
+                .map_err(|_| CellConversionError::new("f64", cell)),
+            cell => Err(CellConversionError::new("f64", cell)),
+        }
+    }
+}
+
+impl FromCell for f32 {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        f64::from_cell(cell)
+            .map(|value| value as f32)
+            .map_err(|_| CellConversionError::new("f32", cell))
+    }
+}
+
+macro_rules! impl_from_cell {
+    ($ty: ty, $variant: ident) => {
+        impl FromCell for $ty {
+            fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+                match cell {
+                    Cell::$variant(value) => Ok(value.clone()),
+                    cell => Err(CellConversionError::new(stringify!($ty), cell)),
+                }
+            }
+        }
+    };
+}
+impl_from_cell!(bool, Boolean);
+
+impl FromCell for String {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::Varchar(value) => Ok(value.to_string()),
+            cell => Err(CellConversionError::new("String", cell)),
+        }
+    }
+}
+
+impl_from_cell!(Arc<str>, Varchar);
+impl_from_cell!(Vec<u8>, Binary);
+impl_from_cell!(Vec<f64>, Vector);
+impl_from_cell!(Interval, Interval);
+
+/// Embeddings are often `f32`s, so vectors convert to them too, rounding like `as f32`
+impl FromCell for Vec<f32> {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::Vector(value) => Ok(value.iter().map(|element| *element as f32).collect()),
+            cell => Err(CellConversionError::new("Vec<f32>", cell)),
+        }
+    }
+}
+#[cfg(feature = "chrono")]
+impl_from_cell!(NaiveDate, Date);
+#[cfg(feature = "chrono")]
+impl_from_cell!(NaiveTime, Time);
+#[cfg(feature = "chrono")]
+impl_from_cell!(NaiveDateTime, TimestampNtz);
+
+/// `time` types convert from the `Time` variants, and from the `chrono` ones too, in case
+/// another crate enables `chrono`
+#[cfg(feature = "time")]
+impl FromCell for Date {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        let date = match cell {
+            Cell::TimeDate(value) => Some(*value),
+            #[cfg(feature = "chrono")]
+            Cell::Date(value) => chrono_to_time::date(*value),
+            _ => None,
+        };
+        date.ok_or_else(|| CellConversionError::new("Date", cell))
+    }
+}
+
+#[cfg(feature = "time")]
+impl FromCell for Time {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        let time = match cell {
+            Cell::TimeTime(value) => Some(*value),
+            #[cfg(feature = "chrono")]
+            Cell::Time(value) => chrono_to_time::time(*value),
+            _ => None,
+        };
+        time.ok_or_else(|| CellConversionError::new("Time", cell))
+    }
+}
+
+#[cfg(feature = "time")]
+impl FromCell for PrimitiveDateTime {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        let timestamp = match cell {
+            Cell::TimeTimestampNtz(value) => Some(*value),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampNtz(value) => chrono_to_time::date(value.date())
+                .zip(chrono_to_time::time(value.time()))
+                .map(|(date, time)| PrimitiveDateTime::new(date, time)),
+            _ => None,
+        };
+        timestamp.ok_or_else(|| CellConversionError::new("PrimitiveDateTime", cell))
+    }
+}
+
+/// Both kinds of timestamps with time zones convert, keeping their offsets
+#[cfg(feature = "time")]
+impl FromCell for OffsetDateTime {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        let timestamp = match cell {
+            Cell::TimeTimestampLtz(value) | Cell::TimeTimestampTz(value) => Some(*value),
+            #[cfg(feature = "chrono")]
+            Cell::TimestampLtz(value) | Cell::TimestampTz(value) => {
+                chrono_to_time::offset_date_time(*value)
+            }
+            _ => None,
+        };
+        timestamp.ok_or_else(|| CellConversionError::new("OffsetDateTime", cell))
+    }
+}
+
+/// Conversions for when both `chrono` and `time` are enabled, and cells hold `chrono` types
+#[cfg(all(feature = "chrono", feature = "time"))]
+mod chrono_to_time {
+    use chrono::{Datelike, Timelike};
+
+    pub(super) fn date(date: chrono::NaiveDate) -> Option<time::Date> {
+        let month = time::Month::try_from(date.month() as u8).ok()?;
+        time::Date::from_calendar_date(date.year(), month, date.day() as u8).ok()
+    }
+
+    /// Leap seconds, which chrono represents and time doesn't, don't convert
+    pub(super) fn time(time: chrono::NaiveTime) -> Option<time::Time> {
+        let (hour, minute, second) = (time.hour(), time.minute(), time.second());
+        time::Time::from_hms_nano(hour as u8, minute as u8, second as u8, time.nanosecond()).ok()
+    }
+
+    pub(super) fn offset_date_time(
+        timestamp: chrono::DateTime<chrono::FixedOffset>,
+    ) -> Option<time::OffsetDateTime> {
+        let offset = time::UtcOffset::from_whole_seconds(timestamp.offset().local_minus_utc());
+        let nanos = timestamp.timestamp() as i128 * 1_000_000_000
+            + timestamp.timestamp_subsec_nanos() as i128;
+        let utc = time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?;
+        Some(utc.to_offset(offset.ok()?))
+    }
+}
+
+/// Both kinds of timestamps with time zones convert, keeping their offsets
+#[cfg(feature = "chrono")]
+impl FromCell for DateTime<FixedOffset> {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::TimestampLtz(value) | Cell::TimestampTz(value) => Ok(*value),
+            cell => Err(CellConversionError::new("DateTime<FixedOffset>", cell)),
+        }
+    }
+}
+
+/// TIMESTAMP_LTZ cells convert to this process's zone, whatever the client's [`TimestampZone`]
+#[cfg(feature = "chrono")]
+impl FromCell for DateTime<Local> {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::TimestampLtz(value) => Ok(value.with_timezone(&Local)),
+            cell => Err(CellConversionError::new("DateTime<Local>", cell)),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromCell for DateTime<Utc> {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::TimestampLtz(value) | Cell::TimestampTz(value) => Ok(value.with_timezone(&Utc)),
+            cell => Err(CellConversionError::new("DateTime<Utc>", cell)),
+        }
+    }
+}
+
+/// Intervals of days and time convert, but intervals of months don't, since months have no
+/// fixed length
+#[cfg(feature = "chrono")]
+impl FromCell for chrono::Duration {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::Interval(value) => chrono::Duration::try_from(*value)
+                .map_err(|_| CellConversionError::new("Duration", cell)),
+            cell => Err(CellConversionError::new("Duration", cell)),
+        }
+    }
+}
+
+/// Intervals of days and time convert, but intervals of months don't, since months have no
+/// fixed length
+#[cfg(feature = "time")]
+impl FromCell for time::Duration {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        match cell {
+            Cell::Interval(value) => time::Duration::try_from(*value)
+                .map_err(|_| CellConversionError::new("Duration", cell)),
+            cell => Err(CellConversionError::new("Duration", cell)),
+        }
+    }
+}
+
+impl FromCell for serde_json::Value {
+    fn from_cell(cell: &Cell) -> Result<Self, CellConversionError> {
+        Ok(cell.clone().into())
+    }
+}
+
+/// `TryFrom<Cell>` and `TryFrom<&Cell>` for a type and for its `Option`, using [`FromCell`]
+///
+/// A generic implementation would overlap with `TryFrom`'s own, so each type is listed.
+macro_rules! impl_try_from_cell {
+    ($($ty: ty),* $(,)?) => {$(
+        impl TryFrom<Cell> for $ty {
+            type Error = CellConversionError;
+
+            fn try_from(cell: Cell) -> Result<Self, Self::Error> {
+                <$ty>::from_cell(&cell)
+            }
+        }
+
+        impl TryFrom<&Cell> for $ty {
+            type Error = CellConversionError;
+
+            fn try_from(cell: &Cell) -> Result<Self, Self::Error> {
+                <$ty>::from_cell(cell)
+            }
+        }
+
+        impl TryFrom<Cell> for Option<$ty> {
+            type Error = CellConversionError;
+
+            fn try_from(cell: Cell) -> Result<Self, Self::Error> {
+                Option::<$ty>::from_cell(&cell)
+            }
+        }
+
+        impl TryFrom<&Cell> for Option<$ty> {
+            type Error = CellConversionError;
+
+            fn try_from(cell: &Cell) -> Result<Self, Self::Error> {
+                Option::<$ty>::from_cell(cell)
+            }
+        }
+    )*};
+}
+impl_try_from_cell!(
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    bool,
+    String,
+    Arc<str>,
+    Vec<u8>,
+    Vec<f32>,
+    Vec<f64>,
+    Interval,
+);
+#[cfg(feature = "chrono")]
+impl_try_from_cell!(
+    NaiveDate,
+    NaiveTime,
+    NaiveDateTime,
+    DateTime<Local>,
+    DateTime<FixedOffset>,
+    DateTime<chrono::Utc>,
+    chrono::Duration,
+);
+#[cfg(feature = "time")]
+impl_try_from_cell!(
+    Date,
+    Time,
+    PrimitiveDateTime,
+    OffsetDateTime,
+    time::Duration
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_nullable_nulls() {
+        let null = Some("null".to_owned());
+        assert!(matches!(RawCell::Fixed.to_cell(&null), Ok(Cell::Null)));
+        assert!(matches!(RawCell::Boolean.to_cell(&null), Ok(Cell::Null)));
+        assert!(matches!(RawCell::Binary.to_cell(&null), Ok(Cell::Null)));
+        assert!(matches!(RawCell::Text.to_cell(&null), Ok(Cell::Varchar(ref x)) if &**x == "null"));
+    }
+
+    #[test]
+    fn numbers() {
+        let cell = |raw: RawCell, value: &str| raw.to_cell(&Some(value.to_owned())).unwrap();
+        assert!(matches!(cell(RawCell::Fixed, "42"), Cell::Int(42)));
+        assert!(matches!(cell(RawCell::Fixed, "-42.0"), Cell::Int(-42)));
+        assert!(matches!(
+            cell(RawCell::Fixed, "99999999999999999999999999999999999999"),
+            Cell::Int(99_999_999_999_999_999_999_999_999_999_999_999_999)
+        ));
+        assert!(matches!(cell(RawCell::Fixed, "1.50"), Cell::Float(x) if x == 1.5));
+        assert!(matches!(cell(RawCell::Real, "1.5e-3"), Cell::Float(x) if x == 0.0015));
+        assert!(matches!(cell(RawCell::Real, "-inf"), Cell::Float(x) if x == f64::NEG_INFINITY));
+        assert!(matches!(cell(RawCell::Real, "NaN"), Cell::Float(x) if x.is_nan()));
+    }
+
+    #[test]
+    fn exact_numbers() {
+        let cell = |value: &str| {
+            RawCell::Fixed
+                .to_cell_with(
+                    &Some(value.to_owned()),
+                    None,
+                    NumberMode::Exact,
+                    TimestampZone::Local,
+                    true,
+                )
+                .unwrap()
+        };
+        assert!(matches!(cell("42"), Cell::Int(42)));
+        assert!(matches!(cell("-42.0"), Cell::Int(-42)));
+        let price = cell("1234567890123456789.05");
+        assert_eq!(price.as_number(), Some((123456789012345678905, 2)));
+        assert_eq!(price.to_string(), "1234567890123456789.05");
+        assert_eq!(serde_json::Value::from(price), "1234567890123456789.05");
+        let cents = cell("-0.50");
+        assert_eq!(
+            cents,
+            Cell::Number {
+                mantissa: -50,
+                scale: 2
+            }
+        );
+        assert_eq!(cents.to_string(), "-0.50");
+        assert_eq!(cents.as_f64(), Some(-0.5));
+        assert_eq!(serde_json::Value::from(cents).to_string(), "-0.50");
+        assert_ne!(cell("1.50"), cell("1.5"));
+        assert!(RawCell::Fixed
+            .to_cell_with(
+                &Some("1.5e3".into()),
+                None,
+                NumberMode::Exact,
+                TimestampZone::Local,
+                true,
+            )
+            .is_err());
+        assert!(matches!(
+            RawCell::Real.to_cell_with(
+                &Some("1.5".into()),
+                None,
+                NumberMode::Exact,
+                TimestampZone::Local,
+                true,
+            ),
+            Ok(Cell::Float(x)) if x == 1.5
+        ));
+    }
+
+    #[test]
+    fn numbers_with_a_scale() {
+        let cell = |value: &str, scale: i32, number_mode: NumberMode| {
+            RawCell::Fixed.to_cell_with(
+                &Some(value.to_owned()),
+                Some(scale),
+                number_mode,
+                TimestampZone::Local,
+                true,
+            )
+        };
+        // A column's cells are all the same kind, whatever their digits
+        assert!(matches!(cell("1", 0, NumberMode::Lossy), Ok(Cell::Int(1))));
+        assert!(matches!(cell("1", 0, NumberMode::Exact), Ok(Cell::Int(1))));
+        assert!(matches!(cell("1.0", 1, NumberMode::Lossy), Ok(Cell::Float(x)) if x == 1.0));
+        assert!(matches!(cell("1.5", 1, NumberMode::Lossy), Ok(Cell::Float(x)) if x == 1.5));
+        assert_eq!(
+            cell("1.0", 2, NumberMode::Exact).unwrap(),
+            Cell::Number {
+                mantissa: 100,
+                scale: 2
+            }
+        );
+        assert_eq!(
+            cell("-12", 2, NumberMode::Exact).unwrap(),
+            Cell::Number {
+                mantissa: -1200,
+                scale: 2
+            }
+        );
+        assert!(cell("1.5", 0, NumberMode::Lossy).is_err());
+        assert!(cell("1.555", 2, NumberMode::Exact).is_err());
+        assert!(cell("1.5", 39, NumberMode::Exact).is_err());
+    }
+
+    #[test]
+    fn intervals() {
+        let cell = |raw: RawCell, value: &str| raw.to_cell(&Some(value.to_owned())).unwrap();
+        let months = cell(RawCell::IntervalYearMonth, "14");
+        assert_eq!(months.as_interval(), Some(Interval::from_months(14)));
+        assert_eq!(months.to_string(), "1-2");
+        assert_eq!(serde_json::Value::from(months), "1-2");
+        let raw: RawCell = serde_json::from_value(serde_json::json!("interval_day_time")).unwrap();
+        let day = cell(raw, "86400.000000000");
+        assert_eq!(day.to_string(), "1 00:00:00");
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            chrono::Duration::try_from(&day).unwrap(),
+            chrono::Duration::days(1)
+        );
+        #[cfg(feature = "time")]
+        assert_eq!(
+            time::Duration::try_from(&day).unwrap(),
+            time::Duration::days(1)
+        );
+        assert!(RawCell::IntervalDayTime
+            .to_cell(&Some("soon".into()))
+            .is_err());
+    }
+
+    #[test]
+    fn semi_structured() {
+        let cell = |raw: RawCell, value: &str| raw.to_cell(&Some(value.to_owned())).unwrap();
+        let object = cell(RawCell::Object, "{\n  \"a\": [1, \"b\"]\n}");
+        assert!(matches!(&object, Cell::Json(value) if value["a"][1] == "b"));
+        assert_eq!(
+            serde_json::Value::from(cell(RawCell::Array, "[1, 2]")),
+            serde_json::json!([1, 2])
+        );
+        assert!(matches!(
+            cell(RawCell::Variant, "\"text\""),
+            Cell::Json(serde_json::Value::String(_))
+        ));
+        assert!(matches!(
+            cell(RawCell::Variant, "null"),
+            Cell::Json(serde_json::Value::Null)
+        ));
+        assert!(matches!(RawCell::Variant.to_cell(&None), Ok(Cell::Null)));
+        assert!(matches!(
+            RawCell::Array.to_cell_with(
+                &Some("null".into()),
+                None,
+                NumberMode::Lossy,
+                TimestampZone::Local,
+                false
+            ),
+            Ok(Cell::Null)
+        ));
+        assert!(RawCell::Object.to_cell(&Some("{".into())).is_err());
+        let column: crate::ColumnType =
+            serde_json::from_value(serde_json::json!({"name": "V", "type": "variant"})).unwrap();
+        assert_eq!(column.data_type, RawCell::Variant);
+    }
+
+    #[test]
+    fn vectors() {
+        let cell = |value: &str| RawCell::Vector.to_cell(&Some(value.to_owned()));
+        assert!(matches!(cell("[1.5,2,-3e2]"), Ok(Cell::Vector(v)) if v == [1.5, 2.0, -300.0]));
+        assert!(matches!(cell("[ 1, 2 ]"), Ok(Cell::Vector(v)) if v == [1.0, 2.0]));
+        assert!(matches!(cell("[]"), Ok(Cell::Vector(v)) if v.is_empty()));
+        assert!(cell("1,2").is_err());
+        assert!(cell("[1,,2]").is_err());
+        let vector = cell("[0.1,2]").unwrap();
+        assert_eq!(Vec::<f32>::from_cell(&vector), Ok(vec![0.1, 2.0]));
+        assert_eq!(
+            serde_json::Value::from(vector),
+            serde_json::json!([0.1, 2.0])
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        assert!(Cell::Null.is_null());
+        assert_eq!(Cell::Int(-1).as_i64(), Some(-1));
+        assert_eq!(Cell::Int(-1).as_u64(), None);
+        assert_eq!(Cell::Int(i128::MAX).as_i64(), None);
+        assert_eq!(Cell::Int(i128::MAX).as_i128(), Some(i128::MAX));
+        assert_eq!(Cell::Int(2).as_f64(), Some(2.0));
+        assert_eq!(Cell::Float(2.5).as_i64(), None);
+        assert_eq!(Cell::Varchar("a".into()).as_str(), Some("a"));
+        assert_eq!(Cell::Int(1).as_str(), None);
+        assert_eq!(Cell::Boolean(true).as_bool(), Some(true));
+        assert_eq!(Cell::Binary(vec![1]).as_bytes(), Some(&[1][..]));
+        assert_eq!(
+            Cell::Json(serde_json::json!({"a": 1})).as_json().unwrap()["a"],
+            1
+        );
+        assert_eq!(Cell::Vector(vec![1.5]).as_vector(), Some(&[1.5][..]));
+        #[cfg(feature = "chrono")]
+        {
+            let cell = |raw: RawCell, value: &str| raw.to_cell(&Some(value.to_owned())).unwrap();
+            assert!(cell(RawCell::Date, "19358").as_date().is_some());
+            assert!(cell(RawCell::Time, "3661").as_time().is_some());
+            assert!(cell(RawCell::Date, "19358").as_time().is_none());
+            assert!(cell(RawCell::TimestampNtz, "1672534861")
+                .as_timestamp_ntz()
+                .is_some());
+            let tz = cell(RawCell::TimestampTz, "1672534861 1500").as_timestamp();
+            assert_eq!(tz, cell(RawCell::TimestampLtz, "1672534861").as_timestamp());
+        }
+    }
+
+    #[test]
+    fn try_from_cells() {
+        assert_eq!(i64::try_from(Cell::Int(42)), Ok(42));
+        assert_eq!(f64::try_from(&Cell::Int(2)), Ok(2.0));
+        assert_eq!(Option::<bool>::try_from(Cell::Null), Ok(None));
+        assert_eq!(
+            Option::<String>::try_from(Cell::Varchar("a".into())),
+            Ok(Some("a".into()))
+        );
+        assert_eq!(
+            Vec::<u8>::try_from(Cell::Binary(vec![0xca, 0xfe])),
+            Ok(vec![0xca, 0xfe])
+        );
+        let error = i8::try_from(Cell::Int(128)).unwrap_err();
+        assert_eq!(error.to_string(), "expected i8, but found an integer");
+        let error = String::try_from(Cell::Null).unwrap_err();
+        assert_eq!(error.to_string(), "expected String, but found NULL");
+        #[cfg(feature = "chrono")]
+        {
+            let date = RawCell::Date.to_cell(&Some("19358".into())).unwrap();
+            assert_eq!(
+                NaiveDate::try_from(date),
+                Ok(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap())
+            );
+            assert!(NaiveDateTime::try_from(Cell::Int(1)).is_err());
+        }
+    }
+
+    #[test]
+    fn cells_round_trip_through_serde() {
+        #[cfg_attr(not(any(feature = "chrono", feature = "time")), allow(unused_mut))]
+        let mut cells = vec![
+            Cell::Null,
+            Cell::Int(i128::MAX),
+            Cell::Float(1.5),
+            Cell::Varchar("text".into()),
+            Cell::Binary(vec![0xca, 0xfe]),
+            Cell::Boolean(true),
+            Cell::Json(serde_json::json!({"a": [1, null]})),
+            Cell::Vector(vec![0.25, -2.0]),
+        ];
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        for (raw, value) in [
+            (RawCell::Date, "19358"),
+            (RawCell::Time, "3661.5"),
+            (RawCell::TimestampNtz, "1672534861.123456789"),
+            (RawCell::TimestampLtz, "1672534861.5"),
+            (RawCell::TimestampTz, "1672534861.5 1500"),
+        ] {
+            cells.push(raw.to_cell(&Some(value.to_owned())).unwrap());
+        }
+        let json = serde_json::to_string(&vec![cells.clone()]).unwrap();
+        assert!(json.starts_with(r#"[["Null",{"Int":170141183460469231731687303715884105727}"#));
+        let parsed: Vec<Vec<Cell>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![cells]);
+    }
+
+    #[test]
+    fn cells_compare_and_hash() {
+        use std::collections::HashSet;
+        assert_eq!(Cell::Int(1), Cell::Int(1));
+        assert_ne!(Cell::Int(1), Cell::Float(1.0));
+        assert_eq!(Cell::Float(f64::NAN), Cell::Float(f64::NAN));
+        assert_ne!(Cell::Float(0.0), Cell::Float(-0.0));
+        assert_eq!(Cell::Vector(vec![f64::NAN]), Cell::Vector(vec![f64::NAN]));
+        assert_ne!(Cell::Vector(vec![1.0]), Cell::Vector(vec![1.0, 2.0]));
+        assert_ne!(Cell::Varchar("".into()), Cell::Null);
+        let cells: HashSet<Cell> = [
+            Cell::Varchar("a".into()),
+            Cell::Varchar("a".into()),
+            Cell::Float(f64::NAN),
+            Cell::Float(f64::NAN),
+            Cell::Json(serde_json::json!({"a": 1, "b": 2})),
+            Cell::Json(serde_json::json!({"b": 2, "a": 1})),
+            Cell::Null,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(cells.len(), 4);
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        {
+            let tz = |value: &str| {
+                RawCell::TimestampTz
+                    .to_cell(&Some(value.to_owned()))
+                    .unwrap()
+            };
+            let instants: HashSet<Cell> = [tz("1672534861 1500"), tz("1672534861 1440")].into();
+            assert_eq!(instants.len(), 1);
+        }
+    }
+
+    #[test]
+    fn cells_display_like_snowflake() {
+        assert_eq!(Cell::Null.to_string(), "");
+        assert_eq!(Cell::Int(-42).to_string(), "-42");
+        assert_eq!(Cell::Float(1.5).to_string(), "1.5");
+        assert_eq!(Cell::Varchar("a,b".into()).to_string(), "a,b");
+        assert_eq!(Cell::Binary(vec![0xca, 0xfe]).to_string(), "CAFE");
+        assert_eq!(Cell::Boolean(false).to_string(), "false");
+        assert_eq!(
+            Cell::Json(serde_json::json!({"a": [1, "b"]})).to_string(),
+            r#"{"a":[1,"b"]}"#
+        );
+        assert_eq!(Cell::Vector(vec![1.5, 2.0]).to_string(), "[1.5,2]");
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        {
+            let cell = |raw: RawCell, value: &str| {
+                raw.to_cell(&Some(value.to_owned())).unwrap().to_string()
+            };
+            assert_eq!(cell(RawCell::Date, "19358"), "2023-01-01");
+            assert_eq!(cell(RawCell::Time, "3661.5"), "01:01:01.500");
+            assert_eq!(
+                cell(RawCell::TimestampNtz, "1672534861.000000000"),
+                "2023-01-01 01:01:01"
+            );
+            assert_eq!(
+                cell(RawCell::TimestampTz, "1672534861.500000000 1500"),
+                "2023-01-01 02:01:01.500 +01:00"
+            );
+            assert_eq!(
+                cell(RawCell::TimestampTz, "1672534861 1440"),
+                "2023-01-01 01:01:01 +00:00"
+            );
+        }
+    }
+
+    #[test]
+    fn from_cells() {
+        assert_eq!(i64::from_cell(&Cell::Int(42)), Ok(42));
+        assert_eq!(f64::from_cell(&Cell::Int(2)), Ok(2.0));
+        assert_eq!(Option::<String>::from_cell(&Cell::Null), Ok(None));
+        assert_eq!(
+            Option::<String>::from_cell(&Cell::Varchar("a".into())),
+            Ok(Some("a".into()))
+        );
+        let error = u8::from_cell(&Cell::Int(256)).unwrap_err();
+        assert_eq!(error.to_string(), "expected u8, but found an integer");
+        let error = String::from_cell(&Cell::Null).unwrap_err();
+        assert_eq!(error.to_string(), "expected String, but found NULL");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_types_convert_whichever_features_are_enabled() {
+        use time::macros::{date, datetime, time};
+        let cell = |raw: RawCell, value: &str| raw.to_cell(&Some(value.to_owned())).unwrap();
+        assert_eq!(
+            Date::try_from(cell(RawCell::Date, "19358")),
+            Ok(date!(2023 - 01 - 01))
+        );
+        assert_eq!(
+            Time::try_from(cell(RawCell::Time, "3661.5")),
+            Ok(time!(01:01:01.5))
+        );
+        assert_eq!(
+            PrimitiveDateTime::try_from(cell(RawCell::TimestampNtz, "1672534861.123456789")),
+            Ok(datetime!(2023-01-01 01:01:01.123456789))
+        );
+        let tz = OffsetDateTime::try_from(cell(RawCell::TimestampTz, "1672534861.5 1500"));
+        assert_eq!(tz, Ok(datetime!(2023-01-01 02:01:01.5 +01:00)));
+        assert_eq!(tz.unwrap().offset().whole_hours(), 1);
+        assert!(Date::try_from(Cell::Int(1)).is_err());
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn dates_and_times_as_json() {
+        let json = |raw: RawCell, value: &str| -> serde_json::Value {
+            raw.to_cell(&Some(value.to_owned())).unwrap().into()
+        };
+        assert_eq!(json(RawCell::Date, "19358"), "2023-01-01");
+        assert_eq!(json(RawCell::Time, "3661.5"), "01:01:01.500");
+        assert_eq!(
+            json(RawCell::TimestampNtz, "1672534861.000000000"),
+            "2023-01-01T01:01:01"
+        );
+        assert_eq!(
+            json(RawCell::TimestampTz, "1672534861.000000000 1500"),
+            "2023-01-01T02:01:01+01:00"
+        );
+        assert_eq!(
+            json(RawCell::TimestampTz, "1672534861.500000000 1080"),
+            "2022-12-31T19:01:01.500-06:00"
+        );
+        assert_eq!(
+            json(RawCell::TimestampTz, "1672534861.000000000"),
+            "2023-01-01T01:01:01Z"
+        );
+    }
+
+    #[test]
+    fn epochs_are_split_exactly() {
+        assert_eq!(parse_epoch("1672534861"), Some((1672534861, 0)));
+        assert_eq!(
+            parse_epoch("1672534861.123456789"),
+            Some((1672534861, 123456789))
+        );
+        assert_eq!(parse_epoch("1672534861.5"), Some((1672534861, 500_000_000)));
+        assert_eq!(parse_epoch("1.1234567899"), Some((1, 123456789)));
+        // Nanoseconds count forwards, so times before the epoch borrow a second
+        assert_eq!(parse_epoch("-1.250"), Some((-2, 750_000_000)));
+        assert_eq!(parse_epoch("-0.5"), Some((-1, 500_000_000)));
+        assert_eq!(parse_epoch("-2.000"), Some((-2, 0)));
+        assert_eq!(parse_epoch(""), None);
+        assert_eq!(parse_epoch("1.+5"), None);
+        assert_eq!(parse_epoch("1.5x"), None);
+        assert_eq!(parse_epoch("soon"), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamps_keep_nanoseconds() {
+        let cell = RawCell::TimestampLtz.to_cell(&Some("1672534861.123000000".into()));
+        match cell {
+            Ok(Cell::TimestampLtz(value)) => {
+                assert_eq!(value.timestamp(), 1672534861);
+                assert_eq!(value.timestamp_subsec_millis(), 123);
+            }
+            other => panic!("expected a timestamp, got {other:?}"),
+        }
+        let cell = RawCell::TimestampNtz.to_cell(&Some("-1.250000000".into()));
+        assert_eq!(
+            cell.unwrap().as_timestamp_ntz().map(|ntz| ntz.to_string()),
+            Some("1969-12-31 23:59:58.750".into())
+        );
+        assert!(RawCell::TimestampNtz.to_cell(&Some("1.+5".into())).is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_timestamps_keep_nanoseconds() {
+        let cell = |raw: RawCell, value: &str| raw.to_cell(&Some(value.to_owned())).unwrap();
+        let ltz = OffsetDateTime::try_from(cell(RawCell::TimestampLtz, "1672534861.123456789"));
+        assert_eq!(
+            ltz.map(OffsetDateTime::unix_timestamp_nanos),
+            Ok(1_672_534_861_123_456_789)
+        );
+        let ntz = PrimitiveDateTime::try_from(cell(RawCell::TimestampNtz, "-1.250000000"));
+        assert_eq!(ntz, Ok(time::macros::datetime!(1969-12-31 23:59:58.75)));
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn timestamp_zones() {
+        let cell = |raw: RawCell, zone: TimestampZone| {
+            raw.to_cell_with(
+                &Some("1672534861.500000000".into()),
+                None,
+                NumberMode::Lossy,
+                zone,
+                true,
+            )
+            .unwrap()
+        };
+        let utc = cell(RawCell::TimestampLtz, TimestampZone::Utc);
+        assert_eq!(utc.to_string(), "2023-01-01 01:01:01.500 +00:00");
+        // The zone only changes the offset, not the instant
+        assert_eq!(utc, cell(RawCell::TimestampLtz, TimestampZone::Local));
+        // Wall clock times are never shifted
+        assert_eq!(
+            cell(RawCell::TimestampNtz, TimestampZone::Utc),
+            cell(RawCell::TimestampNtz, TimestampZone::Local)
+        );
+        #[cfg(feature = "chrono-tz")]
+        {
+            let zone = TimestampZone::Named("America/Los_Angeles".parse().unwrap());
+            assert_eq!(
+                cell(RawCell::TimestampLtz, zone).to_string(),
+                "2022-12-31 17:01:01.500 -08:00"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_cells_are_errors() {
+        let error = |raw: RawCell, value: &str| {
+            raw.to_cell(&Some(value.to_owned()))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(RawCell::Fixed, "one"),
+            "Invalid Fixed value from Snowflake: \"one\""
+        );
+        assert_eq!(
+            error(RawCell::Binary, "xyz"),
+            "Invalid Binary value from Snowflake: \"xyz\""
+        );
+        error(RawCell::Real, "");
+        error(RawCell::Boolean, "yes");
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        for (raw, value) in [
+            (RawCell::Date, "999999999999"),
+            (RawCell::Time, "noon"),
+            (RawCell::TimestampNtz, "1672534861.12x"),
+            (RawCell::TimestampLtz, "soon"),
+            (RawCell::TimestampTz, "1672534861.000000000 UTC"),
+        ] {
+            error(raw, value);
         }
     }
 }
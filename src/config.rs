@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::errors::{ConfigError, SnowflakeResult};
+use crate::options::ClientOptions;
+use crate::SnowflakeClient;
+
+/// Connection settings that can be read from an application's own config file
+///
+/// This implements `Deserialize`, so it can be embedded in YAML, TOML, or JSON config, and
+/// [`SnowflakeConfig::into_client`] validates it like [`SnowflakeClient::builder`] does:
+///
+/// ```rust,no_run
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use light_snowflake_connector::SnowflakeConfig;
+///
+/// #[derive(serde::Deserialize)]
+/// struct AppConfig {
+///     snowflake: SnowflakeConfig,
+/// }
+///
+/// let config: AppConfig = serde_json::from_str(
+///     r#"{
+///         "snowflake": {
+///             "account": "myorg-myaccount",
+///             "user": "etl",
+///             "database": "analytics",
+///             "warehouse": "etl_wh",
+///             "private_key_path": "/run/secrets/snowflake.pem"
+///         }
+///     }"#,
+/// )?;
+/// let client = config.snowflake.into_client()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Give the key as exactly one of `private_key` (the key itself, in any format
+/// [`parse_key_pair`](crate::parse_key_pair) accepts) or `private_key_path`. Unknown fields are
+/// rejected, so typos don't silently fall back to defaults. `Debug` doesn't print the key.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnowflakeConfig {
+    /// The account identifier, like `myorg-myaccount` or `AAA00000.us-east-1`
+    pub account: String,
+    /// The user to authenticate as
+    pub user: String,
+    /// The database statements run in (also accepted as `db`), if not the user's default
+    #[serde(default, alias = "db")]
+    pub database: Option<String>,
+    /// The warehouse statements run on, if not the user's default
+    #[serde(default)]
+    pub warehouse: Option<String>,
+    /// The role statements run as, if not the user's default role
+    #[serde(default)]
+    pub role: Option<String>,
+    /// The private key itself
+    #[serde(default)]
+    pub private_key: Option<String>,
+    /// A file to read the private key from
+    #[serde(default)]
+    pub private_key_path: Option<PathBuf>,
+}
+
+impl std::fmt::Debug for SnowflakeConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnowflakeConfig")
+            .field("account", &self.account)
+            .field("user", &self.user)
+            .field("database", &self.database)
+            .field("warehouse", &self.warehouse)
+            .field("role", &self.role)
+            .field(
+                "private_key",
+                &self.private_key.as_ref().map(|_| "<redacted>"),
+            )
+            .field("private_key_path", &self.private_key_path)
+            .finish()
+    }
+}
+
+impl SnowflakeConfig {
+    /// Validate the settings, load the key, and build a client with default options
+    pub fn into_client(self) -> SnowflakeResult<SnowflakeClient> {
+        self.into_client_with_options(ClientOptions::default())
+    }
+
+    /// Like [`SnowflakeConfig::into_client`], but with other options, like rate limiting
+    pub fn into_client_with_options(
+        self,
+        options: ClientOptions,
+    ) -> SnowflakeResult<SnowflakeClient> {
+        let mut builder = SnowflakeClient::builder()
+            .account(&self.account)
+            .user(&self.user)
+            .options(options);
+        if let Some(database) = &self.database {
+            builder = builder.database(database);
+        }
+        if let Some(warehouse) = &self.warehouse {
+            builder = builder.warehouse(warehouse);
+        }
+        if let Some(role) = &self.role {
+            builder = builder.role(role);
+        }
+        builder = match (self.private_key, self.private_key_path) {
+            (Some(key), None) => {
+                #[cfg(feature = "zeroize")]
+                let key = zeroize::Zeroizing::new(key);
+                builder.key_pem(&key)
+            }
+            (None, Some(path)) => builder.key_path(path),
+            (Some(_), Some(_)) => {
+                return Err(ConfigError::Conflict("private_key", "private_key_path").into())
+            }
+            (None, None) => return Err(ConfigError::Missing("private_key").into()),
+        };
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jwt_simple::algorithms::RS256KeyPair;
+
+    use crate::errors::SnowflakeError;
+
+    use super::*;
+
+    #[test]
+    fn config_from_json() -> SnowflakeResult<()> {
+        let key = RS256KeyPair::generate(2048)?;
+        let config: SnowflakeConfig = serde_json::from_value(serde_json::json!({
+            "account": "myorg-myaccount",
+            "user": "etl",
+            "db": "analytics",
+            "warehouse": "etl_wh",
+            "role": "loader",
+            "private_key": key.to_pem()?,
+        }))?;
+        assert!(!format!("{config:?}").contains("PRIVATE KEY"));
+        let client = config.clone().into_client()?;
+        assert_eq!(client.database(), Some("ANALYTICS"));
+        assert_eq!(client.role(), Some("LOADER"));
+        assert_eq!(
+            client.key_pair().public_key().sha256_thumbprint(),
+            key.public_key().sha256_thumbprint()
+        );
+
+        let both = SnowflakeConfig {
+            private_key_path: Some("/run/secrets/snowflake.pem".into()),
+            ..config.clone()
+        };
+        assert!(matches!(
+            both.into_client(),
+            Err(SnowflakeError::Config(ConfigError::Conflict(..)))
+        ));
+        let neither = SnowflakeConfig {
+            private_key: None,
+            ..config
+        };
+        assert!(matches!(
+            neither.into_client(),
+            Err(SnowflakeError::Config(ConfigError::Missing("private_key")))
+        ));
+        assert!(
+            serde_json::from_value::<SnowflakeConfig>(serde_json::json!({
+                "account": "a", "user": "u", "database": "d", "warehouse": "w", "pasword": "x",
+            }))
+            .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SnowflakeResult;
+use crate::SnowflakeClient;
+
+/// Snowflake Cortex's LLM functions, like `SNOWFLAKE.CORTEX.COMPLETE`
+///
+/// These are created by [`SnowflakeClient::cortex`]. Prompts and text are always bound,
+/// never pasted into the SQL, so they can contain anything. The functions run on the client's
+/// warehouse, and its role needs the `SNOWFLAKE.CORTEX_USER` database role.
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// use light_snowflake_connector::{CompletionOptions, CortexMessage};
+///
+/// let cortex = client.cortex();
+/// let answer = cortex.complete("mistral-large2", "What is a warehouse?").await?;
+/// let completion = cortex
+///     .chat(
+///         "mistral-large2",
+///         &[
+///             CortexMessage::new("system", "Answer in one sentence."),
+///             CortexMessage::new("user", "What is a warehouse?"),
+///         ],
+///         &CompletionOptions {
+///             temperature: Some(0.2),
+///             ..Default::default()
+///         },
+///     )
+///     .await?;
+/// println!("{} ({} tokens)", completion.text(), completion.usage.total_tokens);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cortex {
+    client: SnowflakeClient,
+}
+
+/// One message of a conversation for [`Cortex::chat`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CortexMessage {
+    /// `system`, `user`, or `assistant`
+    pub role: String,
+    pub content: String,
+}
+
+impl CortexMessage {
+    pub fn new(role: &str, content: &str) -> CortexMessage {
+        CortexMessage {
+            role: role.to_owned(),
+            content: content.to_owned(),
+        }
+    }
+}
+
+/// Options for [`Cortex::chat`], which are left to Snowflake's defaults when `None`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CompletionOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Filter unsafe responses with Cortex Guard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guardrails: Option<bool>,
+}
+
+/// The response of [`Cortex::chat`]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Completion {
+    pub choices: Vec<CompletionChoice>,
+    /// When the response was created, in seconds since the Unix epoch
+    pub created: u64,
+    pub model: String,
+    pub usage: CompletionUsage,
+}
+
+/// One of the responses in a [`Completion`]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CompletionChoice {
+    /// The text of the response (Snowflake calls this `messages`, though it's one message)
+    pub messages: String,
+}
+
+/// How many tokens a [`Completion`] used
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct CompletionUsage {
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+    /// Tokens used by Cortex Guard, if `guardrails` was enabled
+    #[serde(default)]
+    pub guardrails_tokens: u64,
+    #[serde(default)]
+    pub total_tokens: u64,
+}
+
+impl Completion {
+    /// The text of the first response, or an empty string if there isn't one
+    pub fn text(&self) -> &str {
+        self.choices
+            .first()
+            .map_or("", |choice| choice.messages.as_str())
+    }
+}
+
+impl Cortex {
+    pub(crate) fn new(client: &SnowflakeClient) -> Cortex {
+        Cortex {
+            client: client.clone(),
+        }
+    }
+
+    /// Complete a single prompt, with `SNOWFLAKE.CORTEX.COMPLETE(model, prompt)`
+    pub async fn complete(&self, model: &str, prompt: &str) -> SnowflakeResult<String> {
+        self.client
+            .prepare("SELECT SNOWFLAKE.CORTEX.COMPLETE(?, ?)")
+            .add_binding(model)
+            .add_binding(prompt)
+            .fetch_scalar()
+            .await
+    }
+
+    /// Continue a conversation, with `SNOWFLAKE.CORTEX.COMPLETE(model, messages, options)`
+    ///
+    /// Passing options makes Snowflake respond with JSON, which includes token usage.
+    pub async fn chat(
+        &self,
+        model: &str,
+        messages: &[CortexMessage],
+        options: &CompletionOptions,
+    ) -> SnowflakeResult<Completion> {
+        let response: String = self
+            .client
+            .prepare(
+                "SELECT TO_VARCHAR(SNOWFLAKE.CORTEX.COMPLETE(?, PARSE_JSON(?), PARSE_JSON(?)))",
+            )
+            .add_binding(model)
+            .add_binding(serde_json::to_string(messages)?)
+            .add_binding(serde_json::to_string(options)?)
+            .fetch_scalar()
+            .await?;
+        Ok(serde_json::from_str(&response)?)
+    }
+
+    /// Score the sentiment of text from -1 (negative) to 1 (positive), with
+    /// `SNOWFLAKE.CORTEX.SENTIMENT`
+    pub async fn sentiment(&self, text: &str) -> SnowflakeResult<f64> {
+        self.client
+            .prepare("SELECT SNOWFLAKE.CORTEX.SENTIMENT(?)")
+            .add_binding(text)
+            .fetch_scalar()
+            .await
+    }
+
+    /// Embed text as a 768-dimension vector, with `SNOWFLAKE.CORTEX.EMBED_TEXT_768`
+    pub async fn embed_text_768(&self, model: &str, text: &str) -> SnowflakeResult<Vec<f32>> {
+        self.embed("EMBED_TEXT_768", model, text).await
+    }
+
+    /// Embed text as a 1024-dimension vector, with `SNOWFLAKE.CORTEX.EMBED_TEXT_1024`
+    pub async fn embed_text_1024(&self, model: &str, text: &str) -> SnowflakeResult<Vec<f32>> {
+        self.embed("EMBED_TEXT_1024", model, text).await
+    }
+
+    async fn embed(&self, function: &str, model: &str, text: &str) -> SnowflakeResult<Vec<f32>> {
+        // Vectors are sent as arrays, which arrive as JSON text
+        let embedding: String = self
+            .client
+            .prepare(&format!(
+                "SELECT TO_VARCHAR(SNOWFLAKE.CORTEX.{function}(?, ?)::ARRAY)"
+            ))
+            .add_binding(model)
+            .add_binding(text)
+            .fetch_scalar()
+            .await?;
+        Ok(serde_json::from_str(&embedding)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_completions() -> SnowflakeResult<()> {
+        let completion: Completion = serde_json::from_str(
+            r#"{
+                "choices": [{"messages": " A warehouse is a cluster of compute resources."}],
+                "created": 1717000000,
+                "model": "mistral-large2",
+                "usage": {"completion_tokens": 11, "prompt_tokens": 20, "total_tokens": 31}
+            }"#,
+        )?;
+        assert_eq!(
+            completion.text(),
+            " A warehouse is a cluster of compute resources."
+        );
+        assert_eq!(completion.usage.total_tokens, 31);
+        assert_eq!(completion.usage.guardrails_tokens, 0);
+        assert_eq!(
+            serde_json::to_value(CompletionOptions {
+                max_tokens: Some(100),
+                ..Default::default()
+            })?,
+            serde_json::json!({"max_tokens": 100})
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! `serde` deserialization of rows and cells, without going through `serde_json::Value`
+use std::marker::PhantomData;
+
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{self, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::cells::Cell;
+use crate::row::Row;
+
+/// Deserializes a single [`Cell`]
+///
+/// `NULL` is `None` (or `()`), numbers use the narrowest of `i64`, `u64`, and `i128` that fits,
+/// exact numbers are strings like `"1.50"`, binary is a byte buffer, JSON is deserialized as it is, vectors are sequences of `f64`s,
+/// and dates and times are ISO 8601 strings.
+pub struct CellDeserializer<E> {
+    cell: Cell,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for Cell {
+    type Deserializer = CellDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        CellDeserializer {
+            cell: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> de::Deserializer<'de> for CellDeserializer<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.cell {
+            Cell::Null => visitor.visit_unit(),
+            Cell::Int(value) => match (i64::try_from(value), u64::try_from(value)) {
+                (Ok(value), _) => visitor.visit_i64(value),
+                (_, Ok(value)) => visitor.visit_u64(value),
+                _ => visitor.visit_i128(value),
+            },
+            Cell::Float(value) => visitor.visit_f64(value),
+            Cell::Number { .. } => visitor.visit_string(self.cell.to_string()),
+            Cell::Varchar(value) => visitor.visit_str(&value),
+            Cell::Binary(value) => visitor.visit_byte_buf(value),
+            Cell::Boolean(value) => visitor.visit_bool(value),
+            Cell::Json(value) => value.deserialize_any(visitor).map_err(de::Error::custom),
+            Cell::Vector(value) => visitor.visit_seq(SeqDeserializer::new(value.into_iter())),
+            // Dates and times use the same strings as the JSON conversion
+            #[allow(unreachable_patterns)]
+            other => match serde_json::Value::from(other) {
+                serde_json::Value::String(value) => visitor.visit_string(value),
+                value => Err(de::Error::custom(format!("unexpected cell {value}"))),
+            },
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.cell {
+            Cell::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Rows deserialize as maps from column names to cells, or as sequences of cells
+///
+/// When deserializing a struct, column names are matched to field names exactly if possible,
+/// and otherwise case-insensitively, so a column `USER_ID` fills a field `user_id`.
+impl<'de> de::Deserializer<'de> for Row {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let columns = self.columns().to_vec();
+        let entries = columns
+            .into_iter()
+            .map(|column| column.name)
+            .zip(self.into_cells());
+        visitor.visit_map(MapDeserializer::new(entries))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let field_for = |name: String| {
+            fields
+                .iter()
+                .find(|field| **field == name)
+                .or_else(|| {
+                    fields
+                        .iter()
+                        .find(|field| field.eq_ignore_ascii_case(&name))
+                })
+                .map_or(name, |field| field.to_string())
+        };
+        let names: Vec<_> = self.columns().iter().map(|c| c.name.clone()).collect();
+        let entries = names.into_iter().map(field_for).zip(self.into_cells());
+        visitor.visit_map(MapDeserializer::new(entries))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut cells = SeqDeserializer::new(self.into_cells().into_iter());
+        let value = visitor.visit_seq(&mut cells)?;
+        cells.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::statement::ColumnType;
+
+    fn row() -> Row {
+        let column = |name: &str| -> ColumnType {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "database": "DB",
+                "schema": "SCHEMA",
+                "table": "TABLE",
+                "type": "text",
+                "nullable": true
+            }))
+            .unwrap()
+        };
+        Row::new(
+            vec![
+                column("ID"),
+                column("NAME"),
+                column("SCORE"),
+                column("NICKNAME"),
+                column("TAGS"),
+            ]
+            .into(),
+            vec![
+                Cell::Int(1),
+                Cell::Varchar("Henry".into()),
+                Cell::Float(0.5),
+                Cell::Null,
+                Cell::Json(serde_json::json!(["admin"])),
+            ],
+        )
+    }
+
+    #[test]
+    fn deserialize_struct_case_insensitively() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Person {
+            id: u32,
+            name: String,
+            score: f64,
+            nickname: Option<String>,
+            tags: Vec<String>,
+        }
+        let person = Person::deserialize(row()).unwrap();
+        assert_eq!(
+            person,
+            Person {
+                id: 1,
+                name: "Henry".into(),
+                score: 0.5,
+                nickname: None,
+                tags: vec!["admin".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_tuple_and_map() {
+        let (id, name, score, nickname, tags) =
+            <(i64, String, f64, Option<String>, serde_json::Value)>::deserialize(row()).unwrap();
+        assert_eq!(
+            (id, name.as_str(), score, nickname),
+            (1, "Henry", 0.5, None)
+        );
+        assert_eq!(tags, serde_json::json!(["admin"]));
+        let map =
+            std::collections::HashMap::<String, serde_json::Value>::deserialize(row()).unwrap();
+        assert_eq!(map["NAME"], "Henry");
+        assert!(<(i64, String)>::deserialize(row()).is_err());
+    }
+}
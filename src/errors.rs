@@ -1,9 +1,21 @@
+use crate::cells::RawCell;
+
 /// Error types for the Snowflake client
+///
+/// This is `#[non_exhaustive]` because the `Arrow` variant only exists with the `arrow`
+/// feature, which any crate in the build can enable.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum SnowflakeError {
     /// An error occurred while signing a request
     #[error(transparent)]
     Token(#[from] jwt_simple::Error),
+    /// A [`SnowflakeClientBuilder`](crate::SnowflakeClientBuilder) was misconfigured
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    /// A file, such as a private key, could not be read
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
     /// An error occurred while sending a request
     #[error(transparent)]
     Request(#[from] reqwest::Error),
@@ -13,12 +25,60 @@ pub enum SnowflakeError {
     /// An error occurred while parsing JSON (these may also appear wrapped in Request errors)
     #[error(transparent)]
     JSONError(#[from] serde_json::Error),
+    /// Snowflake's response didn't match the expected format
+    ///
+    /// The path points to the field that didn't match, like `resultSetMetaData.rowType[3].type`
+    #[error("Unexpected response format at {path}: {source}")]
+    Deserialize {
+        path: String,
+        source: serde_json::Error,
+    },
     /// A certain feature (like a data type) is not supported (yet)
     #[error("Unsupported feature: {0}")]
     UnsupportedFeature(&'static str),
+    /// The client's [`StatementPolicy`](crate::StatementPolicy) rejected the statement,
+    /// so it was not sent to Snowflake
+    #[error(transparent)]
+    PolicyViolation(#[from] PolicyViolation),
+    /// A statement was still running when its [`PollStrategy`](crate::PollStrategy)'s deadline
+    /// passed, so it was cancelled
+    #[error("Statement {query_id} did not finish within {deadline:?}")]
+    PollDeadlineExceeded {
+        query_id: String,
+        deadline: std::time::Duration,
+    },
+    /// A statement's overall deadline (see [`Statement::with_deadline`](crate::Statement::with_deadline))
+    /// passed before it finished, or before its partitions were fetched
+    #[error("Statement {} did not finish before its deadline", query_id.as_deref().unwrap_or("<not yet submitted>"))]
+    DeadlineExceeded { query_id: Option<String> },
+    /// A result could not be converted to Arrow
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Arrow(#[from] arrow_schema::ArrowError),
+    /// A statement run by a helper (like `get_ddl`) returned rows in an unexpected shape
+    #[error("Unexpected result: {0}")]
+    UnexpectedResult(String),
     /// The response contains multiple partitions, and you specified that you only want one
     #[error("Response contains multiple partitions")]
     MultiplePartitions,
+    /// The response is larger than the byte budget you specified
+    #[error("Response exceeds the limit of {max_bytes} bytes")]
+    ResultTooLarge { max_bytes: usize },
+    /// A cell could not be converted to the requested type
+    #[error(transparent)]
+    CellConversion(#[from] CellConversionError),
+    /// Snowflake returned a value that can't be parsed as its column's type
+    #[error("Invalid {data_type:?} value from Snowflake: {value:?}")]
+    InvalidCell { data_type: RawCell, value: String },
+    /// A row could not be deserialized into the requested type
+    #[error("Failed to deserialize row: {0}")]
+    RowDeserialize(#[from] serde::de::value::Error),
+    /// A sink rejected rows forwarded from a response
+    #[error("Failed to forward rows: {0}")]
+    Sink(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The columns of a response didn't match the expected schema
+    #[error(transparent)]
+    SchemaMismatch(#[from] SchemaMismatch),
     /// There was a problem constructing the client
     #[error(transparent)]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
@@ -26,21 +86,207 @@ pub enum SnowflakeError {
 
 pub type SnowflakeResult<T> = Result<T, SnowflakeError>;
 
+/// How a response differed from the expected schema
+///
+/// See [`QueryResponse::expect_schema`](crate::QueryResponse::expect_schema).
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SchemaMismatch {
+    /// The response has a different number of columns than expected
+    #[error("expected {expected} columns but got {actual}")]
+    ColumnCount { expected: usize, actual: usize },
+    /// A column has a different name or type than expected
+    #[error("expected column {index} to be {expected_name} ({expected_type:?}) but got {actual_name} ({actual_type:?})")]
+    Column {
+        index: usize,
+        expected_name: String,
+        expected_type: RawCell,
+        actual_name: String,
+        actual_type: RawCell,
+    },
+}
+
+/// What was wrong with a [`SnowflakeClientBuilder`](crate::SnowflakeClientBuilder)'s settings
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    /// A required setting was not given
+    #[error("missing required setting: {0}")]
+    Missing(&'static str),
+    /// A setting was given, but it is empty or only whitespace
+    #[error("setting cannot be blank: {0}")]
+    Blank(&'static str),
+    /// Two settings were given that can't be used together
+    #[error("settings cannot be used together: {0} and {1}")]
+    Conflict(&'static str, &'static str),
+    /// The private key could not be parsed
+    #[error("invalid private key: {0}")]
+    InvalidKey(#[source] jwt_simple::Error),
+    /// The private key isn't one this crate can use, like an encrypted key or a public key,
+    /// with a hint about how to fix it
+    #[error("unsupported private key: {0}")]
+    UnsupportedKey(&'static str),
+    /// The private key file could not be read or parsed
+    #[error("could not load private key from {path:?}: {message}")]
+    KeyFile {
+        path: std::path::PathBuf,
+        message: String,
+    },
+}
+
+/// A statement that a [`StatementPolicy`](crate::StatementPolicy) rejected
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("statement policy does not allow {keyword} statements: {statement}")]
+pub struct PolicyViolation {
+    /// The leading keyword of the rejected statement, uppercased (e.g. `DROP`)
+    pub keyword: String,
+    /// The rejected statement, which may be one of several in the SQL
+    pub statement: String,
+}
+
+/// A [`Cell`](crate::Cell) that a [`FromCell`](crate::FromCell) type could not be created from
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("expected {expected}, but found {found}")]
+pub struct CellConversionError {
+    /// The type that was requested, like `i64`
+    pub expected: &'static str,
+    /// The kind of cell that was found, like `NULL` or `a varchar`
+    pub found: &'static str,
+}
+
 #[derive(serde::Deserialize, Debug)]
-#[serde(untagged)]
-pub(crate) enum SnowflakeWireResult<T> {
-    Ok(T),
-    Error { code: String, message: String },
-}
-
-impl<T> SnowflakeWireResult<T> {
-    /// Convert from the custom wire format to a standard result
-    pub fn into_result(self) -> SnowflakeResult<T> {
-        match self {
-            SnowflakeWireResult::Error { code, message } => {
-                Err(SnowflakeError::ServerError { code, message })
+struct WireError {
+    code: String,
+    message: String,
+}
+
+/// Parse a response from Snowflake, converting errors into [`SnowflakeError::ServerError`]
+///
+/// Successful responses are deserialized with path-aware errors, so if Snowflake's
+/// format changes, the error says which field didn't match.
+/// The size of the response body in bytes is returned alongside the value.
+pub(crate) async fn parse_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> SnowflakeResult<(T, usize)> {
+    let body = read_body(response).await?;
+    Ok((deserialize_body(&body)?, body.len()))
+}
+
+/// Parse a response that is mostly rows of strings, like a partition
+///
+/// With the `simd-json` feature, the body is decoded with simd-json, which is much faster
+/// for big partitions. If that fails, it's parsed again with serde_json, for the same
+/// path-aware errors as [`parse_response`].
+pub(crate) async fn parse_data_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> SnowflakeResult<(T, usize)> {
+    let body = read_body(response).await?;
+    #[cfg(feature = "simd-json")]
+    {
+        // simd-json decodes in place, so it needs its own copy of the body
+        let mut buffer = body.to_vec();
+        if let Ok(value) = simd_json::serde::from_slice(&mut buffer) {
+            return Ok((value, body.len()));
+        }
+    }
+    Ok((deserialize_body(&body)?, body.len()))
+}
+
+/// Read the body of a response, or the error in it if it wasn't successful
+pub(crate) async fn read_body(response: reqwest::Response) -> SnowflakeResult<bytes::Bytes> {
+    let status = response.status();
+    let body = response.bytes().await?;
+    if !status.is_success() {
+        return Err(match serde_json::from_slice::<WireError>(&body) {
+            Ok(WireError { code, message }) => SnowflakeError::ServerError { code, message },
+            // Errors from proxies and load balancers might not be JSON at all
+            Err(_) => SnowflakeError::ServerError {
+                code: status.as_str().to_owned(),
+                message: String::from_utf8_lossy(&body).into_owned(),
+            },
+        });
+    }
+    Ok(body)
+}
+
+fn deserialize_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> SnowflakeResult<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|error| {
+        SnowflakeError::Deserialize {
+            path: error.path().to_string(),
+            source: error.into_inner(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Outer {
+        items: Vec<Inner>,
+    }
+
+    #[derive(serde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Inner {
+        count: usize,
+    }
+
+    fn response(status: u16, body: &str) -> reqwest::Response {
+        http::Response::builder()
+            .status(status)
+            .body(body.to_owned())
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn deserialize_errors_have_paths() {
+        let body = r#"{"items": [{"count": 1}, {"count": "two"}]}"#;
+        match parse_response::<Outer>(response(200, body)).await {
+            Err(SnowflakeError::Deserialize { path, .. }) => assert_eq!(path, "items[1].count"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn data_responses_are_parsed() -> SnowflakeResult<()> {
+        #[derive(serde::Deserialize, Debug)]
+        struct Data {
+            data: Vec<Vec<Option<String>>>,
+        }
+        let body = r#"{"data": [["1", null], ["2", "caf\u00e9 \"quoted\""]]}"#;
+        let (parsed, bytes) = parse_data_response::<Data>(response(200, body)).await?;
+        assert_eq!(bytes, body.len());
+        assert_eq!(
+            parsed.data,
+            [
+                vec![Some("1".to_owned()), None],
+                vec![Some("2".to_owned()), Some("café \"quoted\"".to_owned())]
+            ]
+        );
+        let body = r#"{"data": [["1"], [2]]}"#;
+        match parse_data_response::<Data>(response(200, body)).await {
+            Err(SnowflakeError::Deserialize { path, .. }) => assert_eq!(path, "data[1][0]"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_parsed() {
+        let body = r#"{"code": "002003", "message": "Object does not exist"}"#;
+        match parse_response::<Outer>(response(422, body)).await {
+            Err(SnowflakeError::ServerError { code, .. }) => assert_eq!(code, "002003"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match parse_response::<Outer>(response(502, "Bad Gateway")).await {
+            Err(SnowflakeError::ServerError { code, message }) => {
+                assert_eq!(code, "502");
+                assert_eq!(message, "Bad Gateway");
             }
-            SnowflakeWireResult::Ok(t) => Ok(t),
+            other => panic!("unexpected result: {:?}", other),
         }
     }
 }
@@ -0,0 +1,49 @@
+use std::future::Future;
+
+use crate::errors::SnowflakeResult;
+use crate::statement::{Changes, QueryResponse, Statement};
+use crate::SnowflakeClient;
+
+/// Something that can prepare and execute statements, like a [`SnowflakeClient`]
+///
+/// Write application code and helpers against this trait instead of a concrete client,
+/// so that they work with any executor and can be swapped for a mock in tests.
+///
+/// ```rust,no_run
+/// use light_snowflake_connector::{SnowflakeExecutor, SnowflakeResult};
+///
+/// async fn count_rows(executor: &impl SnowflakeExecutor, table: &str) -> SnowflakeResult<usize> {
+///     let statement = executor.prepare(&format!("SELECT * FROM {table}"));
+///     Ok(executor.query(statement).await?.num_rows())
+/// }
+/// ```
+pub trait SnowflakeExecutor {
+    /// Prepare a SQL statement for execution, without sending anything to Snowflake
+    fn prepare(&self, sql: &str) -> Statement;
+
+    /// Execute SQL that returns a result set, like [`Statement::query`]
+    fn query(
+        &self,
+        statement: Statement,
+    ) -> impl Future<Output = SnowflakeResult<QueryResponse>> + Send;
+
+    /// Execute SQL that does not return a result set, like [`Statement::manipulate`]
+    fn manipulate(
+        &self,
+        statement: Statement,
+    ) -> impl Future<Output = SnowflakeResult<Changes>> + Send;
+}
+
+impl SnowflakeExecutor for SnowflakeClient {
+    fn prepare(&self, sql: &str) -> Statement {
+        SnowflakeClient::prepare(self, sql)
+    }
+
+    async fn query(&self, statement: Statement) -> SnowflakeResult<QueryResponse> {
+        statement.query().await
+    }
+
+    async fn manipulate(&self, statement: Statement) -> SnowflakeResult<Changes> {
+        statement.manipulate().await
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+use std::sync::Arc;
+
+type FailoverHook = Arc<dyn Fn(&FailoverEvent) + Send + Sync>;
+
+/// A secondary Snowflake account to use when the primary is unreachable
+///
+/// This is meant for replication and failover setups, where the same user and key
+/// exist in both accounts. Each request is tried against the primary account first,
+/// and only sent to the secondary if the primary can't be connected to. Once a statement
+/// has failed over, the rest of its requests, like polling it, fetching its partitions, and
+/// cancelling it, go straight to the secondary, since only that account has its result.
+///
+/// Attach one to a client with
+/// [`SnowflakeClient::with_failover`](crate::SnowflakeClient::with_failover).
+#[derive(Clone)]
+pub struct Failover {
+    /// The secondary Snowflake account name, in the same format as the primary
+    pub account: String,
+    hook: Option<FailoverHook>,
+}
+
+/// Details about a request that failed over to the secondary account
+#[derive(Debug)]
+pub struct FailoverEvent<'a> {
+    /// The primary account that could not be reached
+    pub primary: &'a str,
+    /// The secondary account the request is being retried against
+    pub secondary: &'a str,
+    /// The error from the primary account
+    pub error: &'a reqwest::Error,
+}
+
+impl Failover {
+    /// Fail over to this account
+    pub fn new(account: impl Into<String>) -> Failover {
+        Failover {
+            account: account.into(),
+            hook: None,
+        }
+    }
+
+    /// Call this function every time a request fails over, e.g. to log or alert on it
+    pub fn on_failover(
+        mut self,
+        hook: impl Fn(&FailoverEvent) + Send + Sync + 'static,
+    ) -> Failover {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    pub(crate) fn notify(&self, event: &FailoverEvent) {
+        log::warn!(
+            "Snowflake account {} is unreachable, failing over to {}: {}",
+            event.primary,
+            event.secondary,
+            event.error
+        );
+        if let Some(hook) = &self.hook {
+            hook(event);
+        }
+    }
+}
+
+impl fmt::Debug for Failover {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Failover")
+            .field("account", &self.account)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,460 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::bindings::{Binding, ToBinding};
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::statement::{quote_identifier, quote_object_name, ChangesSummary};
+use crate::SnowflakeClient;
+
+/// The most bindings sent in one `INSERT`, unless set with [`BatchInsert::with_max_bindings`]
+pub const DEFAULT_MAX_BINDINGS: usize = 16_384;
+
+/// The most bytes of bindings sent in one request, unless set with
+/// [`BatchInsert::with_max_payload_bytes`]
+///
+/// This is well under the SQL API's limit, to leave room for the statement itself.
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 4 * 1024 * 1024;
+
+/// A multi-row `INSERT`, split into as many statements as it takes to stay under
+/// the SQL API's limits
+///
+/// These are created by [`SnowflakeClient::batch_insert`]. Add rows with
+/// [`BatchInsert::add_row`] or [`BatchInsert::add_structs`], then run them with
+/// [`BatchInsert::execute`], which returns the combined changes of every statement.
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// use light_snowflake_connector::Binding;
+///
+/// let changes = client
+///     .batch_insert("events")
+///     .with_columns(&["id", "name"])
+///     .add_row([Binding::from(1), "signup".into()])
+///     .add_row([Binding::from(2), "login".into()])
+///     .in_transaction()
+///     .execute()
+///     .await?;
+/// assert_eq!(changes.rows_inserted, 2);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Like [`Statement`](crate::Statement), the `Debug` output redacts the values, showing only
+/// how many rows there are.
+#[derive(Clone)]
+pub struct BatchInsert {
+    client: SnowflakeClient,
+    table: String,
+    columns: Vec<String>,
+    /// Whether each column is sent as JSON text and wrapped in `PARSE_JSON`
+    json_columns: Vec<bool>,
+    rows: Vec<Vec<Binding>>,
+    max_bindings: usize,
+    max_payload_bytes: usize,
+    transaction: bool,
+}
+
+impl std::fmt::Debug for BatchInsert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchInsert")
+            .field("table", &self.table)
+            .field("columns", &self.columns)
+            .field("rows", &self.rows.len())
+            .field("max_bindings", &self.max_bindings)
+            .field("max_payload_bytes", &self.max_payload_bytes)
+            .field("transaction", &self.transaction)
+            .finish()
+    }
+}
+
+/// One `INSERT` statement of a batch, with its bindings in order
+#[derive(Debug)]
+pub(crate) struct InsertChunk {
+    pub sql: String,
+    pub bindings: Vec<Binding>,
+}
+
+impl BatchInsert {
+    pub(crate) fn new(client: &SnowflakeClient, table: &str) -> BatchInsert {
+        BatchInsert {
+            client: client.clone(),
+            table: table.to_owned(),
+            columns: vec![],
+            json_columns: vec![],
+            rows: vec![],
+            max_bindings: DEFAULT_MAX_BINDINGS,
+            max_payload_bytes: DEFAULT_MAX_PAYLOAD_BYTES,
+            transaction: false,
+        }
+    }
+
+    /// The table the rows are inserted into, as given
+    pub(crate) fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Add columns for [`BatchInsert::add_row`], in the order their values are given
+    ///
+    /// Names are quoted if they need to be, like in [`SnowflakeClient::get_ddl`].
+    pub fn with_columns(mut self, columns: &[&str]) -> BatchInsert {
+        for column in columns {
+            self.column_index(column);
+        }
+        self
+    }
+
+    /// Add a row of values, one for each column
+    ///
+    /// Missing trailing values are inserted as `NULL`, and extra values are ignored.
+    pub fn add_row<I>(mut self, values: I) -> BatchInsert
+    where
+        I: IntoIterator,
+        I::Item: ToBinding,
+    {
+        let mut row: Vec<Binding> = values.into_iter().map(ToBinding::to_binding).collect();
+        row.resize(self.columns.len(), Binding::Null { value: () });
+        self.rows.push(row);
+        self
+    }
+
+    /// Add structs (or maps) as rows, using their fields as columns
+    ///
+    /// Each field becomes a column of the same name, in the order they first appear, and fields
+    /// missing from some items (e.g. with `skip_serializing_if`) are inserted as `NULL`.
+    /// `None` becomes `NULL`, and columns holding nested objects or arrays are inserted as
+    /// `VARIANT` with `PARSE_JSON`.
+    pub fn add_structs<T: Serialize>(mut self, items: &[T]) -> SnowflakeResult<BatchInsert> {
+        for item in items {
+            let Value::Object(object) = serde_json::to_value(item)? else {
+                return Err(SnowflakeError::UnsupportedFeature(
+                    "only structs and maps can be inserted as rows",
+                ));
+            };
+            self.add_object(object);
+        }
+        Ok(self)
+    }
+
+    /// Split the rows across at most this many bindings per statement
+    pub fn with_max_bindings(mut self, max_bindings: usize) -> BatchInsert {
+        self.max_bindings = max_bindings;
+        self
+    }
+
+    /// Split the rows so each request sends at most about this many bytes of bindings
+    ///
+    /// This is an estimate, and a single row is always sent, even if it is larger.
+    pub fn with_max_payload_bytes(mut self, max_payload_bytes: usize) -> BatchInsert {
+        self.max_payload_bytes = max_payload_bytes;
+        self
+    }
+
+    /// Run every statement of the batch in one transaction, so either all rows are inserted
+    /// or none are
+    ///
+    /// Each request to the SQL API runs in its own session, so the statements are sent
+    /// together as one multi-statement request between `BEGIN` and `COMMIT`. They are still
+    /// split to respect [`BatchInsert::with_max_bindings`], but the payload limit can't be
+    /// respected, since everything has to go in the same request.
+    pub fn in_transaction(mut self) -> BatchInsert {
+        self.transaction = true;
+        self
+    }
+
+    /// Insert the rows, returning the changes of each statement
+    ///
+    /// Without [`BatchInsert::in_transaction`], the statements run one after another,
+    /// so an error partway through leaves the earlier rows inserted.
+    pub async fn execute(&self) -> SnowflakeResult<ChangesSummary> {
+        let chunks = self.plan();
+        if chunks.is_empty() {
+            return Ok(ChangesSummary::default());
+        }
+        if !self.transaction {
+            let mut summary = ChangesSummary::default();
+            for chunk in chunks {
+                summary.push(self.prepare(chunk).manipulate().await?);
+            }
+            return Ok(summary);
+        }
+        let count = chunks.len() + 2;
+        let transaction = InsertChunk {
+            sql: std::iter::once("BEGIN".to_owned())
+                .chain(chunks.iter().map(|chunk| chunk.sql.clone()))
+                .chain(std::iter::once("COMMIT".to_owned()))
+                .collect::<Vec<_>>()
+                .join(";\n"),
+            bindings: chunks
+                .into_iter()
+                .flat_map(|chunk| chunk.bindings)
+                .collect(),
+        };
+        let statement = self
+            .prepare(transaction)
+            .with_parameter("MULTI_STATEMENT_COUNT", count.to_string());
+        Ok(statement.manipulate_multi().await?.into_iter().collect())
+    }
+
+    fn prepare(&self, chunk: InsertChunk) -> crate::Statement {
+        chunk.bindings.into_iter().fold(
+            self.client.prepare(&chunk.sql),
+            crate::Statement::add_binding,
+        )
+    }
+
+    /// Find a column by name, adding it (and padding earlier rows with `NULL`) if it is new
+    fn column_index(&mut self, column: &str) -> usize {
+        if let Some(index) = self.columns.iter().position(|c| c == column) {
+            return index;
+        }
+        self.columns.push(column.to_owned());
+        self.json_columns.push(false);
+        for row in &mut self.rows {
+            row.resize(self.columns.len(), Binding::Null { value: () });
+        }
+        self.columns.len() - 1
+    }
+
+    fn add_object(&mut self, object: Map<String, Value>) {
+        let mut row = vec![Binding::Null { value: () }; self.columns.len()];
+        for (column, value) in object {
+            let index = self.column_index(&column);
+            row.resize(self.columns.len(), Binding::Null { value: () });
+            if matches!(value, Value::Object(_) | Value::Array(_)) && !self.json_columns[index] {
+                // Earlier rows of this column were scalars, so re-encode them as JSON text
+                self.json_columns[index] = true;
+                for earlier in &mut self.rows {
+                    earlier[index] = to_json_text(&earlier[index]);
+                }
+            }
+            row[index] = to_binding(value, self.json_columns[index]);
+        }
+        self.rows.push(row);
+    }
+
+    /// Split the rows into statements that respect the binding and payload limits
+    pub(crate) fn plan(&self) -> Vec<InsertChunk> {
+        if self.columns.is_empty() {
+            return vec![];
+        }
+        let column_list = self
+            .columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let prefix = if self.json_columns.contains(&true) {
+            let select = self
+                .json_columns
+                .iter()
+                .enumerate()
+                .map(|(ix, is_json)| match is_json {
+                    true => format!("PARSE_JSON(column{})", ix + 1),
+                    false => format!("column{}", ix + 1),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "INSERT INTO {} ({column_list}) SELECT {select} FROM VALUES ",
+                quote_object_name(&self.table)
+            )
+        } else {
+            format!(
+                "INSERT INTO {} ({column_list}) VALUES ",
+                quote_object_name(&self.table)
+            )
+        };
+        let placeholders = format!("({})", vec!["?"; self.columns.len()].join(", "));
+
+        let mut chunks = vec![];
+        let mut start = 0;
+        while start < self.rows.len() {
+            let mut end = start;
+            let (mut bindings, mut bytes) = (0, 0);
+            while end < self.rows.len() {
+                let row_bytes = payload_bytes(&self.rows[end]);
+                let fits = bindings + self.columns.len() <= self.max_bindings
+                    && bytes + row_bytes <= self.max_payload_bytes;
+                if end > start && !fits {
+                    break;
+                }
+                bindings += self.columns.len();
+                bytes += row_bytes;
+                end += 1;
+            }
+            chunks.push(InsertChunk {
+                sql: format!(
+                    "{prefix}{}",
+                    vec![placeholders.as_str(); end - start].join(", ")
+                ),
+                bindings: self.rows[start..end].iter().flatten().cloned().collect(),
+            });
+            start = end;
+        }
+        chunks
+    }
+}
+
+/// Estimate how many bytes a row's bindings add to a request, including their keys
+fn payload_bytes(row: &[Binding]) -> usize {
+    row.iter()
+        .map(|binding| serde_json::to_string(binding).map_or(0, |json| json.len()) + 10)
+        .sum()
+}
+
+/// Convert one field to a binding, sending everything in a JSON column as JSON text
+fn to_binding(value: Value, is_json: bool) -> Binding {
+    match value {
+        Value::Null => Binding::Null { value: () },
+        value if is_json => value.to_string().into(),
+        Value::Bool(value) => Binding::Boolean {
+            value: value.to_string(),
+        },
+        Value::Number(number) => {
+            let value = number.to_string();
+            if value.contains(['.', 'e', 'E']) {
+                Binding::Real { value }
+            } else {
+                Binding::Fixed { value }
+            }
+        }
+        Value::String(value) => value.into(),
+        value => value.to_string().into(),
+    }
+}
+
+/// Re-encode a scalar binding as JSON text, for a column that turned out to hold JSON
+fn to_json_text(binding: &Binding) -> Binding {
+    match binding {
+        Binding::Null { .. } => binding.clone(),
+        Binding::Fixed { value } | Binding::Real { value } => value.clone().into(),
+        Binding::Boolean { value } => value.clone().into(),
+        Binding::Text { value }
+        | Binding::TimestampNtz { value }
+        | Binding::Date { value }
+        | Binding::Time { value }
+        | Binding::Binary { value } => Value::String(value.clone()).to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Event {
+        id: u64,
+        name: Option<String>,
+        score: f64,
+        #[serde(rename = "event type")]
+        kind: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
+    }
+
+    fn event(id: u64, tags: Option<Vec<String>>) -> Event {
+        Event {
+            id,
+            name: None,
+            score: 1.5,
+            kind: "click".into(),
+            tags,
+        }
+    }
+
+    fn batch(table: &str) -> BatchInsert {
+        crate::test_client().batch_insert(table)
+    }
+
+    #[test]
+    fn plan_scalar_inserts() -> SnowflakeResult<()> {
+        let batch = batch("db.public.events")
+            .add_structs(&[event(1, None), event(2, None)])?
+            .with_max_bindings(10);
+        let chunks = batch.plan();
+        // 4 columns, so only 2 rows fit in 10 bindings
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].sql,
+            r#"INSERT INTO db.public.events (id, name, score, "event type") VALUES (?, ?, ?, ?), (?, ?, ?, ?)"#
+        );
+        assert_eq!(
+            serde_json::to_value(&chunks[0].bindings[..4])?,
+            serde_json::json!([
+                {"type": "FIXED", "value": "1"},
+                {"type": "TEXT", "value": null},
+                {"type": "REAL", "value": "1.5"},
+                {"type": "TEXT", "value": "click"},
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plan_json_columns() -> SnowflakeResult<()> {
+        let items = [
+            event(1, None),
+            event(2, Some(vec!["a".into()])),
+            event(3, None),
+        ];
+        let chunks = batch("events")
+            .with_max_bindings(10)
+            .add_structs(&items)?
+            .plan();
+        // 5 columns, so 2 rows per statement
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunks[1].sql,
+            r#"INSERT INTO events (id, name, score, "event type", tags) SELECT column1, column2, column3, column4, PARSE_JSON(column5) FROM VALUES (?, ?, ?, ?, ?)"#
+        );
+        let bindings = serde_json::to_value(&chunks[0].bindings)?;
+        assert_eq!(
+            bindings[4],
+            serde_json::json!({"type": "TEXT", "value": null})
+        );
+        assert_eq!(
+            bindings[9],
+            serde_json::json!({"type": "TEXT", "value": r#"["a"]"#})
+        );
+        assert!(batch("events").add_structs(&[1, 2]).is_err());
+        assert!(batch("events").add_structs::<Event>(&[])?.plan().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn plan_respects_payload_limits() -> SnowflakeResult<()> {
+        let long = "x".repeat(100);
+        let batch = (0..10)
+            .fold(
+                batch("events").with_columns(&["id", "text"]),
+                |batch, id| batch.add_row([Binding::from(id), long.as_str().into()]),
+            )
+            .with_max_payload_bytes(400);
+        let chunks = batch.plan();
+        // Each row is about 170 bytes, so only 2 fit
+        assert_eq!(chunks.len(), 5);
+        assert!(chunks.iter().all(|chunk| chunk.bindings.len() == 4));
+        // A single row is sent even if it doesn't fit
+        assert_eq!(batch.with_max_payload_bytes(1).plan().len(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn later_json_values_reencode_earlier_rows() -> SnowflakeResult<()> {
+        let items = [
+            serde_json::json!({"payload": "text"}),
+            serde_json::json!({"payload": 1}),
+            serde_json::json!({"payload": {"nested": true}}),
+        ];
+        let chunks = batch("events").add_structs(&items)?.plan();
+        assert_eq!(
+            serde_json::to_value(&chunks[0].bindings)?,
+            serde_json::json!([
+                {"type": "TEXT", "value": r#""text""#},
+                {"type": "TEXT", "value": "1"},
+                {"type": "TEXT", "value": r#"{"nested":true}"#},
+            ])
+        );
+        Ok(())
+    }
+}
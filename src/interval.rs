@@ -0,0 +1,243 @@
+use std::fmt;
+
+use crate::cells::parse_epoch;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_DAY: i64 = 86_400 * NANOS_PER_SECOND;
+
+/// A span of time, from an `INTERVAL` cell or for binding to one
+///
+/// Months and days can't be converted to each other without a date to count from, so they're
+/// kept apart, like in Snowflake: `INTERVAL YEAR TO MONTH` cells only have months, and
+/// `INTERVAL DAY TO SECOND` cells only have days and nanoseconds. All of the fields have the
+/// same sign, and `nanos` is less than a day.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Interval {
+    /// Whole months, including years as 12 months each
+    pub months: i64,
+    /// Whole days
+    pub days: i64,
+    /// Nanoseconds after the whole days
+    pub nanos: i64,
+}
+
+impl Interval {
+    /// A span of months, like `INTERVAL '1-2' YEAR TO MONTH` for 14 months
+    pub fn from_months(months: i64) -> Interval {
+        Interval {
+            months,
+            ..Interval::default()
+        }
+    }
+
+    /// A span of nanoseconds, split into whole days and the nanoseconds after them
+    pub fn from_nanos(nanos: i128) -> Option<Interval> {
+        Some(Interval {
+            months: 0,
+            days: (nanos / NANOS_PER_DAY as i128).try_into().ok()?,
+            nanos: (nanos % NANOS_PER_DAY as i128) as i64,
+        })
+    }
+
+    /// The length of the days and nanoseconds, ignoring months
+    fn day_time_nanos(&self) -> i128 {
+        self.days as i128 * NANOS_PER_DAY as i128 + self.nanos as i128
+    }
+}
+
+/// Parse a wire `INTERVAL YEAR TO MONTH` like `14` months, or SQL's `1-2` for the same
+pub(crate) fn parse_year_month(value: &str) -> Option<Interval> {
+    let (sign, unsigned) = split_sign(value);
+    let months = match unsigned.split_once('-') {
+        Some((years, months)) => {
+            let months: i64 = months.parse().ok()?;
+            if !(0..12).contains(&months) {
+                return None;
+            }
+            years
+                .parse::<i64>()
+                .ok()?
+                .checked_mul(12)?
+                .checked_add(months)?
+        }
+        None => unsigned.parse().ok()?,
+    };
+    Some(Interval::from_months(sign * months))
+}
+
+/// Parse a wire `INTERVAL DAY TO SECOND` like `93784.500000000` seconds, or SQL's
+/// `1 02:03:04.5` for the same
+pub(crate) fn parse_day_time(value: &str) -> Option<Interval> {
+    let (sign, unsigned) = split_sign(value);
+    let nanos = match unsigned.split_once(' ') {
+        Some((days, time)) => {
+            let days: i64 = days.parse().ok()?;
+            let mut parts = time.splitn(3, ':');
+            let hours: i64 = parts.next()?.parse().ok()?;
+            let minutes: i64 = parts.next()?.parse().ok()?;
+            let (seconds, nanos) = parse_epoch(parts.next()?)?;
+            if hours >= 24 || minutes >= 60 || !(0..60).contains(&seconds) {
+                return None;
+            }
+            let seconds = days.checked_mul(86_400)? + hours * 3_600 + minutes * 60 + seconds;
+            seconds as i128 * NANOS_PER_SECOND as i128 + nanos as i128
+        }
+        None => {
+            let (seconds, nanos) = parse_epoch(unsigned)?;
+            seconds as i128 * NANOS_PER_SECOND as i128 + nanos as i128
+        }
+    };
+    Interval::from_nanos(sign as i128 * nanos)
+}
+
+/// Split a leading `-` or `+` from a value
+fn split_sign(value: &str) -> (i64, &str) {
+    match value.strip_prefix('-') {
+        Some(unsigned) => (-1, unsigned),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    }
+}
+
+/// Intervals display like Snowflake's casts from text: `1-2` for 14 months, and
+/// `1 02:03:04.500` for a day and a bit over two hours
+///
+/// An interval with both months and days shows both parts, separated by a space. That's not
+/// something Snowflake returns, and a cast needs one part at a time.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.months != 0 {
+            let sign = if self.months < 0 { "-" } else { "" };
+            let months = self.months.unsigned_abs();
+            write!(f, "{sign}{}-{}", months / 12, months % 12)?;
+            if self.days == 0 && self.nanos == 0 {
+                return Ok(());
+            }
+            f.write_str(" ")?;
+        }
+        let nanos = self.day_time_nanos();
+        let sign = if nanos < 0 { "-" } else { "" };
+        let nanos = nanos.unsigned_abs();
+        let seconds = nanos / NANOS_PER_SECOND as u128;
+        write!(
+            f,
+            "{sign}{} {:02}:{:02}:{:02}",
+            seconds / 86_400,
+            seconds / 3_600 % 24,
+            seconds / 60 % 60,
+            seconds % 60
+        )?;
+        match (nanos % NANOS_PER_SECOND as u128) as u32 {
+            0 => Ok(()),
+            nanos if nanos % 1_000_000 == 0 => write!(f, ".{:03}", nanos / 1_000_000),
+            nanos if nanos % 1_000 == 0 => write!(f, ".{:06}", nanos / 1_000),
+            nanos => write!(f, ".{nanos:09}"),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::Duration> for Interval {
+    fn from(duration: chrono::Duration) -> Interval {
+        let days = duration.num_days();
+        Interval {
+            months: 0,
+            days,
+            nanos: (duration - chrono::Duration::days(days))
+                .num_nanoseconds()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Duration> for Interval {
+    fn from(duration: time::Duration) -> Interval {
+        let days = duration.whole_days();
+        Interval {
+            months: 0,
+            days,
+            nanos: (duration - time::Duration::days(days)).whole_nanoseconds() as i64,
+        }
+    }
+}
+
+/// Intervals of days and time convert, but intervals with months fail, since months have no
+/// fixed length
+#[cfg(feature = "chrono")]
+impl TryFrom<Interval> for chrono::Duration {
+    type Error = Interval;
+
+    fn try_from(interval: Interval) -> Result<Self, Interval> {
+        match interval.months {
+            0 => chrono::Duration::try_days(interval.days)
+                .and_then(|days| days.checked_add(&chrono::Duration::nanoseconds(interval.nanos)))
+                .ok_or(interval),
+            _ => Err(interval),
+        }
+    }
+}
+
+/// Intervals of days and time convert, but intervals with months fail, since months have no
+/// fixed length
+#[cfg(feature = "time")]
+impl TryFrom<Interval> for time::Duration {
+    type Error = Interval;
+
+    fn try_from(interval: Interval) -> Result<Self, Interval> {
+        match interval.months {
+            0 => interval
+                .days
+                .checked_mul(86_400)
+                .map(time::Duration::seconds)
+                .and_then(|days| days.checked_add(time::Duration::nanoseconds(interval.nanos)))
+                .ok_or(interval),
+            _ => Err(interval),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_months() {
+        assert_eq!(parse_year_month("14"), Some(Interval::from_months(14)));
+        assert_eq!(parse_year_month("1-2"), Some(Interval::from_months(14)));
+        assert_eq!(parse_year_month("-1-2"), Some(Interval::from_months(-14)));
+        assert_eq!(parse_year_month("1-12"), None);
+        assert_eq!(parse_year_month("soon"), None);
+        assert_eq!(Interval::from_months(14).to_string(), "1-2");
+        assert_eq!(Interval::from_months(-3).to_string(), "-0-3");
+    }
+
+    #[test]
+    fn day_times() {
+        let expected = Interval {
+            months: 0,
+            days: 1,
+            nanos: 7_384_500_000_000,
+        };
+        assert_eq!(parse_day_time("93784.500000000"), Some(expected));
+        assert_eq!(parse_day_time("1 02:03:04.5"), Some(expected));
+        assert_eq!(expected.to_string(), "1 02:03:04.500");
+        let negative = parse_day_time("-93784.5").unwrap();
+        assert_eq!((negative.days, negative.nanos), (-1, -7_384_500_000_000));
+        assert_eq!(negative.to_string(), "-1 02:03:04.500");
+        assert_eq!(parse_day_time(&negative.to_string()), Some(negative));
+        assert_eq!(parse_day_time("1 24:00:00"), None);
+        assert_eq!(Interval::default().to_string(), "0 00:00:00");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_durations() {
+        let duration = chrono::Duration::hours(-25) - chrono::Duration::milliseconds(500);
+        let interval = Interval::from(duration);
+        assert_eq!((interval.days, interval.nanos), (-1, -3_600_500_000_000));
+        assert_eq!(chrono::Duration::try_from(interval), Ok(duration));
+        assert!(chrono::Duration::try_from(Interval::from_months(1)).is_err());
+    }
+}
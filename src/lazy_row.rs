@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use crate::cell_ref::CellRef;
+use crate::cells::{Cell, FromCell, NumberMode, TimestampZone};
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::partition::StringTable;
+use crate::row::{ColumnIndex, Row};
+use crate::statement::ColumnType;
+
+/// A row that keeps Snowflake's strings, and only parses a cell when it's read
+///
+/// These are created by [`Partition::lazy_rows`](crate::Partition::lazy_rows). When a query
+/// returns many columns but the consumer only reads a few of them, this skips parsing the rest,
+/// unlike [`Row`], which parses every cell up front. Each read parses the cell again, so read a
+/// cell once if it's expensive (like a timestamp) and needed more than once.
+///
+/// Lazy rows share the partition's strings and column metadata, so they're cheap to clone
+/// and can be sent to other tasks.
+#[derive(Debug, Clone)]
+pub struct LazyRow {
+    columns: Arc<[ColumnType]>,
+    data: Arc<StringTable>,
+    row: usize,
+    number_mode: NumberMode,
+    timestamp_zone: TimestampZone,
+    nullable: bool,
+}
+
+impl LazyRow {
+    pub(crate) fn new(
+        columns: Arc<[ColumnType]>,
+        data: Arc<StringTable>,
+        row: usize,
+        number_mode: NumberMode,
+        timestamp_zone: TimestampZone,
+        nullable: bool,
+    ) -> LazyRow {
+        LazyRow {
+            columns,
+            data,
+            row,
+            number_mode,
+            timestamp_zone,
+            nullable,
+        }
+    }
+
+    /// The columns of this row, in order
+    pub fn columns(&self) -> &[ColumnType] {
+        &self.columns
+    }
+
+    /// The strings of this row, just as they were returned from Snowflake
+    pub fn raw_cells(&self) -> &[Option<String>] {
+        &self.data[self.row]
+    }
+
+    /// Get the number of cells in this row
+    pub fn len(&self) -> usize {
+        self.raw_cells().len()
+    }
+
+    /// Whether this row has no cells (which only happens for results without columns)
+    pub fn is_empty(&self) -> bool {
+        self.raw_cells().is_empty()
+    }
+
+    /// Parse a cell by position or by column name, or `None` if there is no such column
+    ///
+    /// Column names are matched like in [`Row::get`]. This fails like [`RawCell::to_cell`]
+    /// if the cell can't be parsed.
+    ///
+    /// [`RawCell::to_cell`]: crate::RawCell::to_cell
+    pub fn get<I: ColumnIndex>(&self, index: I) -> SnowflakeResult<Option<Cell>> {
+        self.raw(index)
+            .map(|(value, column)| {
+                column.data_type.to_cell_with(
+                    value,
+                    column.scale,
+                    self.number_mode,
+                    self.timestamp_zone,
+                    self.nullable,
+                )
+            })
+            .transpose()
+    }
+
+    /// Parse a cell by position or by column name, failing if there is no such column
+    ///
+    /// This is like [`LazyRow::get`], for when the column is known to exist.
+    pub fn cell<I: ColumnIndex + std::fmt::Display + Copy>(
+        &self,
+        index: I,
+    ) -> SnowflakeResult<Cell> {
+        self.get(index)?
+            .ok_or_else(|| SnowflakeError::UnexpectedResult(format!("no column {index} in row")))
+    }
+
+    /// Parse a cell by position or by column name into a [`CellRef`], which borrows text
+    pub fn get_ref<I: ColumnIndex>(&self, index: I) -> SnowflakeResult<Option<CellRef<'_>>> {
+        self.raw(index)
+            .map(|(value, column)| {
+                column.data_type.to_cell_ref_with(
+                    value,
+                    column.scale,
+                    self.number_mode,
+                    self.timestamp_zone,
+                    self.nullable,
+                )
+            })
+            .transpose()
+    }
+
+    /// Parse a cell by position or by column name, converted to a [`FromCell`] type
+    ///
+    /// This fails like [`Row::get_as`].
+    pub fn get_as<T: FromCell, I: ColumnIndex + std::fmt::Display + Copy>(
+        &self,
+        index: I,
+    ) -> SnowflakeResult<T> {
+        Ok(T::from_cell(&self.cell(index)?)?)
+    }
+
+    /// Parse every cell into a [`Row`]
+    pub fn to_row(&self) -> SnowflakeResult<Row> {
+        let cells = self
+            .raw_cells()
+            .iter()
+            .zip(self.columns.iter())
+            .map(|(value, column)| {
+                column.data_type.to_cell_with(
+                    value,
+                    column.scale,
+                    self.number_mode,
+                    self.timestamp_zone,
+                    self.nullable,
+                )
+            })
+            .collect::<SnowflakeResult<_>>()?;
+        Ok(Row::new(self.columns.clone(), cells))
+    }
+
+    fn raw<I: ColumnIndex>(&self, index: I) -> Option<(&Option<String>, &ColumnType)> {
+        let ix = index.position(&self.columns)?;
+        Some((self.raw_cells().get(ix)?, &self.columns[ix]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Partition;
+
+    use super::*;
+
+    #[test]
+    fn lazy_rows_parse_on_read() -> SnowflakeResult<()> {
+        let column = |name: &str, data_type: &str| -> ColumnType {
+            serde_json::from_value(serde_json::json!({"name": name, "type": data_type})).unwrap()
+        };
+        let columns = vec![column("ID", "fixed"), column("ACTIVE", "boolean")];
+        // The booleans would fail if they were parsed
+        let data = (0..4)
+            .map(|ix| vec![Some(ix.to_string()), Some("not a boolean".to_owned())])
+            .collect();
+        let partition = Partition::from_raw(columns, data);
+        let rows: Vec<LazyRow> = partition.slice(1..3).lazy_rows().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].get_as::<i64, _>("id")?, 2);
+        assert!(matches!(rows[0].get(0)?, Some(Cell::Int(1))));
+        assert!(matches!(rows[0].get_ref("ID")?, Some(CellRef::Int(1))));
+        assert_eq!(rows[0].raw_cells()[1].as_deref(), Some("not a boolean"));
+        assert!(rows[0].get(2)?.is_none());
+        assert!(rows[0].get("ACTIVE").is_err());
+        assert!(rows[0].to_row().is_err());
+        assert!(rows[0].get_as::<i64, _>("MISSING").is_err());
+        assert_eq!(rows[0].len(), 2);
+        // Single rows can be read without iterating, and only the cells that are read are parsed
+        let row = partition.slice(1..).row(1).unwrap();
+        assert!(matches!(row.cell("ID")?, Cell::Int(2)));
+        assert!(row.cell("ACTIVE").is_err());
+        assert!(row.cell(2).is_err());
+        assert!(partition.slice(1..).row(3).is_none());
+        Ok(())
+    }
+}
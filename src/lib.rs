@@ -9,14 +9,10 @@
 //! #[tokio::main]
 //! async fn main() -> Result<(), SnowflakeError> {
 //!     let key_pair = RS256KeyPair::generate(2048)?;
-//!     let config = SnowflakeClient {
-//!         key_pair,
-//!         account: "ACCOUNT".into(),
-//!         user: "USER".into(),
-//!         database: "DB".into(),
-//!         warehouse: "WH".into(),
-//!         role: Some("ROLE".into()),
-//!     };
+//!     let config = SnowflakeClient::new(key_pair, "ACCOUNT", "USER")
+//!         .with_database("DB")
+//!         .with_warehouse("WH")
+//!         .with_role("ROLE");
 //!
 //!     let result = config
 //!         .prepare("SELECT * FROM TEST_TABLE WHERE id = ? AND name = ?")
@@ -29,7 +25,7 @@
 //!     let partition = result.only_partition()?;
 //!     
 //!     // Get the results as a Vec<Vec<Cell>>, which is a tagged enum similar to serde_json::Value
-//!     let cells = partition.cells();
+//!     let cells = partition.cells()?;
 //!     match &cells[0][0] {
 //!         Cell::Int(x) => println!("Got an integer: {}", x),
 //!         Cell::Varchar(x) => println!("Got a string: {}", x),
@@ -37,58 +33,311 @@
 //!     }
 //!
 //!     // Get the results as a Vec<Vec<serde_json::Value>>, which is a list of lists of JSON values
-//!     let json_table = partition.json_table();
+//!     let json_table = partition.json_table()?;
 //!
 //!     // Get the results as a Vec<serde_json::Value>, which is a list of JSON objects
-//!     let json_objects = partition.json_objects();
+//!     let json_objects = partition.json_objects()?;
 //!
 //!     Ok(())
 //! }
 //! ```
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
 use jwt_simple::algorithms::RS256KeyPair;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+mod audit;
 mod bindings;
+mod builder;
+mod catalog;
+mod cdc;
+mod cell_ref;
 mod cells;
+mod config;
+#[cfg(feature = "cortex")]
+mod cortex;
+mod de;
 mod errors;
+mod executor;
+mod failover;
+mod insert;
+mod interval;
+mod lazy_row;
 #[cfg(test)]
 #[cfg(feature = "live-tests")]
 mod live_tests;
+mod metrics;
+mod options;
 mod partition;
+mod policy;
+mod poll;
+mod pool;
+mod rate_limit;
+mod registry;
+mod retry;
+mod row;
+mod stage;
 mod statement;
+mod stream;
+mod table;
+mod task;
+mod temp_values;
+mod time_travel;
+mod trace;
+mod transport;
+mod unload;
 
-pub use cells::{Cell, RawCell};
-pub use errors::{SnowflakeError, SnowflakeResult};
+pub use audit::{fingerprint, AuditHook, AuditOutcome, AuditRecord};
+pub use bindings::{Binding, ToBinding};
+pub use builder::SnowflakeClientBuilder;
+pub use catalog::{Catalog, CreateMode, Database, Schema, Warehouse};
+pub use cdc::{ChangeAction, ChangeStream, StreamChange};
+pub use cell_ref::CellRef;
+pub use cells::{Cell, FromCell, NumberMode, RawCell, TimestampZone};
+pub use config::SnowflakeConfig;
+#[cfg(feature = "cortex")]
+pub use cortex::{
+    Completion, CompletionChoice, CompletionOptions, CompletionUsage, Cortex, CortexMessage,
+};
+pub use de::CellDeserializer;
+pub use errors::{
+    CellConversionError, ConfigError, PolicyViolation, SchemaMismatch, SnowflakeError,
+    SnowflakeResult,
+};
+pub use executor::SnowflakeExecutor;
+pub use failover::{Failover, FailoverEvent};
+pub use insert::{BatchInsert, DEFAULT_MAX_BINDINGS, DEFAULT_MAX_PAYLOAD_BYTES};
+pub use interval::Interval;
+pub use jwt::{load_key_pair, parse_key_pair};
 pub use jwt_simple;
-pub use partition::Partition;
-pub use statement::{Changes, QueryResponse, Statement};
+pub use lazy_row::LazyRow;
+pub use metrics::{Latency, MetricsHook, PartitionFetch, TransferStats};
+pub use options::ClientOptions;
+pub use partition::{JsonObject, Partition};
+pub use policy::StatementPolicy;
+pub use poll::PollStrategy;
+pub use pool::ConnectionPool;
+pub use rate_limit::RateLimiter;
+pub use registry::SnowflakeRegistry;
+pub use reqwest::header;
+pub use retry::WarehouseRetry;
+pub use row::{ColumnIndex, Row};
+pub use stage::StageFile;
+pub use statement::{
+    Changes, ChangesSummary, ColumnType, DmlStats, ForwardSummary, QueryResponse, QueryStats,
+    Statement, StatementStatus,
+};
+pub use stream::{PartitionStream, ResultStream, RowStream};
+pub use table::CellTable;
+pub use task::{CreateTask, Task, TaskRun, TaskState};
+pub use temp_values::TempValues;
+pub use time_travel::{TimeTravel, TimeTravelPoint};
+pub use trace::{TraceContext, TraceContextProvider};
+pub use unload::{Unload, UnloadSummary, UnloadedFile};
+pub use uuid;
 
 mod jwt;
 
 /// Configuration for making connections to Snowflake
-#[derive(Debug, Clone)]
+///
+/// The settings are shared, so cloning a client or preparing a statement with it only bumps
+/// a reference count. Changing the settings of a clone, like with
+/// [`SnowflakeClient::with_failover`], copies them first, so it doesn't affect the original.
+///
+/// The `Debug` output redacts the key pair, showing only its public key fingerprint.
+#[derive(Clone)]
 pub struct SnowflakeClient {
-    /// The RSA key pair used to sign the JWT.
-    ///
-    /// There are many ways to generate or load this key pair depending on your deployment.
-    /// * You can generate one with [`jwt_simple::algorithms::RS256KeyPair::generate`]
-    /// * You can load one from a PEM file with [`jwt_simple::algorithms::RS256KeyPair::from_pem`]
-    /// * You can load one from a DER file with [`jwt_simple::algorithms::RS256KeyPair::from_der`]
-    /// * In turn you might combine any of these with volume mounts, PVCs, Vault, Secrets Manager, etc.
-    pub key_pair: RS256KeyPair,
-    /// The Snowflake account name. This should be two parts separated by a dot,
-    /// and it might look like `AAA00000.us-east-1`
-    pub account: String,
-    /// The Snowflake user name.
-    pub user: String,
-    /// The Snowflake database name. (This is required and it cannot be `""`)
-    pub database: String,
-    /// The Snowflake warehouse name. (This is required and it cannot be `""`)
-    pub warehouse: String,
-    /// The Snowflake role name. This is optional only if you have configured your user
-    /// to have a default role.
-    pub role: Option<String>,
+    pub(crate) inner: Arc<ClientInner>,
+}
+
+/// The settings of a [`SnowflakeClient`], shared by its clones and statements
+#[derive(Clone)]
+pub(crate) struct ClientInner {
+    pub(crate) key_pair: Arc<RS256KeyPair>,
+    pub(crate) account: String,
+    pub(crate) user: String,
+    pub(crate) database: Option<String>,
+    pub(crate) warehouse: Option<String>,
+    pub(crate) role: Option<String>,
+    pub(crate) options: ClientOptions,
+    /// The HTTP client built from the options, on the first request without a connection pool
+    pub(crate) http: OnceLock<reqwest::Client>,
+}
+
+impl std::fmt::Debug for SnowflakeClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = &self.inner;
+        f.debug_struct("SnowflakeClient")
+            .field(
+                "key_pair",
+                &format_args!(
+                    "<redacted, SHA256:{}>",
+                    inner.key_pair.public_key().sha256_thumbprint()
+                ),
+            )
+            .field("account", &inner.account)
+            .field("user", &inner.user)
+            .field("database", &inner.database)
+            .field("warehouse", &inner.warehouse)
+            .field("role", &inner.role)
+            .field("options", &inner.options)
+            .finish()
+    }
 }
+
 impl SnowflakeClient {
+    /// Start building a client, with validation of its settings
+    ///
+    /// ```rust,no_run
+    /// # use light_snowflake_connector::{SnowflakeClient, SnowflakeResult};
+    /// # fn main() -> SnowflakeResult<()> {
+    /// let client = SnowflakeClient::builder()
+    ///     .account("AAA00000.us-east-1")
+    ///     .user("USER")
+    ///     .database("DB")
+    ///     .warehouse("WH")
+    ///     .key_path("/run/secrets/snowflake.pem")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> SnowflakeClientBuilder {
+        SnowflakeClientBuilder::default()
+    }
+
+    /// Create a client for a user of an account, without a default database, warehouse, or
+    /// role, and with default options
+    ///
+    /// * `key_pair` is the RSA key pair used to sign the JWT. There are many ways to generate
+    ///   or load it depending on your deployment:
+    ///   * You can generate one with [`jwt_simple::algorithms::RS256KeyPair::generate`]
+    ///   * You can load one from a PEM file with [`jwt_simple::algorithms::RS256KeyPair::from_pem`]
+    ///   * You can load one from a DER file with [`jwt_simple::algorithms::RS256KeyPair::from_der`]
+    ///   * In turn you might combine any of these with volume mounts, PVCs, Vault, Secrets Manager, etc.
+    ///   * [`load_key_pair`] reads a PEM or DER file, and with the `zeroize` feature it wipes the
+    ///     file's contents from memory after parsing.
+    ///   * [`parse_key_pair`] detects the format of a key you already have, like from an
+    ///     environment variable, and explains what's wrong if it can't be used.
+    /// * `account` is the Snowflake account name. This should be two parts separated by a dot,
+    ///   and it might look like `AAA00000.us-east-1`
+    /// * `user` is the Snowflake user name.
+    ///
+    /// Unlike [`SnowflakeClient::builder`], this doesn't validate anything.
+    pub fn new(
+        key_pair: impl Into<Arc<RS256KeyPair>>,
+        account: &str,
+        user: &str,
+    ) -> SnowflakeClient {
+        SnowflakeClient {
+            inner: Arc::new(ClientInner {
+                key_pair: key_pair.into(),
+                account: account.to_owned(),
+                user: user.to_owned(),
+                database: None,
+                warehouse: None,
+                role: None,
+                options: ClientOptions::default(),
+                http: OnceLock::new(),
+            }),
+        }
+    }
+
+    /// Use this database for statements that don't name one
+    ///
+    /// Without one, statements that need a database must be fully qualified, unless the user
+    /// has a default namespace. Like the warehouse and role, this is uppercased unless it is
+    /// quoted, so use `"\"my_db\""` for a case sensitive name.
+    pub fn with_database(mut self, database: &str) -> SnowflakeClient {
+        self.inner_mut().database = Some(database.to_owned());
+        self
+    }
+
+    /// Use this warehouse for statements
+    ///
+    /// Without one, statements that need a warehouse use the user's default warehouse. Some,
+    /// like `SHOW` and most DDL, don't need one.
+    pub fn with_warehouse(mut self, warehouse: &str) -> SnowflakeClient {
+        self.inner_mut().warehouse = Some(warehouse.to_owned());
+        self
+    }
+
+    /// Use this role for statements, rather than the user's default role
+    pub fn with_role(mut self, role: &str) -> SnowflakeClient {
+        self.inner_mut().role = Some(role.to_owned());
+        self
+    }
+
+    /// Replace all of the client's options, like rate limiting
+    pub fn with_options(mut self, options: ClientOptions) -> SnowflakeClient {
+        *self.options_mut() = options;
+        self
+    }
+
+    /// The RSA key pair used to sign the JWT
+    pub fn key_pair(&self) -> &Arc<RS256KeyPair> {
+        &self.inner.key_pair
+    }
+
+    /// The Snowflake account name
+    pub fn account(&self) -> &str {
+        &self.inner.account
+    }
+
+    /// The Snowflake user name
+    pub fn user(&self) -> &str {
+        &self.inner.user
+    }
+
+    /// The database of statements that don't name one, if any
+    pub fn database(&self) -> Option<&str> {
+        self.inner.database.as_deref()
+    }
+
+    /// The warehouse of statements, if any
+    pub fn warehouse(&self) -> Option<&str> {
+        self.inner.warehouse.as_deref()
+    }
+
+    /// The role of statements, if any
+    pub fn role(&self) -> Option<&str> {
+        self.inner.role.as_deref()
+    }
+
+    /// The client's options, like rate limiting
+    pub fn options(&self) -> &ClientOptions {
+        &self.inner.options
+    }
+
+    /// The settings, copied first if anything else shares them
+    fn inner_mut(&mut self) -> &mut ClientInner {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// The options, copied first like [`SnowflakeClient::inner_mut`]
+    ///
+    /// The HTTP client is built again on the next request, since it may depend on them.
+    pub(crate) fn options_mut(&mut self) -> &mut ClientOptions {
+        let inner = self.inner_mut();
+        inner.http = OnceLock::new();
+        &mut inner.options
+    }
+
+    /// The HTTP client to send requests with: the connection pool's, or else one built from
+    /// the options on first use, so the client and its clones reuse its connections
+    pub(crate) fn http(&self) -> SnowflakeResult<&reqwest::Client> {
+        if let Some(pool) = &self.options().connection_pool {
+            return Ok(pool.http());
+        }
+        if let Some(http) = self.inner.http.get() {
+            return Ok(http);
+        }
+        let http = pool::http_client(self.options())?;
+        Ok(self.inner.http.get_or_init(|| http))
+    }
+
     /// Prepare a SQL statement for execution
     ///
     /// This does not send anything to Snowflake and it's infallible because it does not
@@ -96,4 +345,404 @@ impl SnowflakeClient {
     pub fn prepare(&self, sql: &str) -> Statement {
         Statement::new(sql, self)
     }
+
+    /// Retry requests against a secondary account when this client's account is unreachable
+    pub fn with_failover(mut self, failover: Failover) -> SnowflakeClient {
+        self.options_mut().failover = Some(failover);
+        self
+    }
+
+    /// Propagate the current trace context to Snowflake with every request
+    ///
+    /// The provider is called before each request, so it can read the context of whichever
+    /// span is current, e.g. from OpenTelemetry. Return `None` to send no trace headers.
+    pub fn with_trace_context_provider(
+        mut self,
+        provider: impl Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    ) -> SnowflakeClient {
+        self.options_mut().trace_context_provider = Some(TraceContextProvider::new(provider));
+        self
+    }
+
+    /// Resolve a hostname to a fixed address instead of using DNS
+    ///
+    /// This is useful for PrivateLink endpoints or split-horizon DNS, e.g. pinning
+    /// `myaccount.privatelink.snowflakecomputing.com` to an address. The port of the
+    /// address is ignored in favor of the port in the URL (usually 443).
+    /// TLS still verifies the certificate against the hostname.
+    pub fn with_resolve(mut self, domain: &str, address: SocketAddr) -> SnowflakeClient {
+        self.options_mut()
+            .resolve
+            .push((domain.to_ascii_lowercase(), address));
+        self
+    }
+
+    /// Set the timeout for each request that fetches a partition after the first,
+    /// instead of using the statement's timeout
+    ///
+    /// Statements can override this with [`Statement::with_partition_timeout`].
+    pub fn with_partition_timeout(mut self, timeout: Duration) -> SnowflakeClient {
+        self.options_mut().partition_timeout = Some(timeout);
+        self
+    }
+
+    /// Parse NUMBER cells with this mode, e.g. [`NumberMode::Exact`] to never round them to
+    /// an `f64`
+    ///
+    /// Statements can override this with [`Statement::with_number_mode`].
+    pub fn with_number_mode(mut self, number_mode: NumberMode) -> SnowflakeClient {
+        self.options_mut().number_mode = number_mode;
+        self
+    }
+
+    /// Convert TIMESTAMP_LTZ cells to this zone, rather than the zone of the machine running
+    /// this process
+    ///
+    /// Servers often run in UTC but serve users elsewhere: use [`TimestampZone::Utc`] to be
+    /// independent of the machine, or (with the `chrono-tz` feature) a
+    /// [`TimestampZone::Named`] zone like the session's TIMEZONE parameter.
+    pub fn with_timestamp_zone(mut self, timestamp_zone: TimestampZone) -> SnowflakeClient {
+        self.options_mut().timestamp_zone = timestamp_zone;
+        self
+    }
+
+    /// Send TCP keepalive probes at this interval
+    ///
+    /// Statements can hold a request open for up to 45 seconds without any traffic,
+    /// which is long enough for some NATs and firewalls to silently drop the connection.
+    pub fn with_tcp_keepalive(mut self, interval: Duration) -> SnowflakeClient {
+        self.options_mut().tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Send HTTP/2 keepalive pings at this interval, even while idle, and close the
+    /// connection if a ping isn't answered within the timeout
+    #[cfg(feature = "http2")]
+    pub fn with_http2_keep_alive(
+        mut self,
+        interval: Duration,
+        timeout: Duration,
+    ) -> SnowflakeClient {
+        self.options_mut().http2_keep_alive = Some((interval, timeout));
+        self
+    }
+
+    /// Let HTTP/2 flow control windows grow to match the connection's bandwidth-delay product,
+    /// which helps large partitions download faster over long, fast links
+    #[cfg(feature = "http2")]
+    pub fn with_http2_adaptive_window(mut self) -> SnowflakeClient {
+        self.options_mut().http2_adaptive_window = true;
+        self
+    }
+
+    /// Set the largest HTTP/2 frame to accept, in bytes (between 16 KiB and 16 MiB)
+    #[cfg(feature = "http2")]
+    pub fn with_http2_max_frame_size(mut self, size: u32) -> SnowflakeClient {
+        self.options_mut().http2_max_frame_size = Some(size);
+        self
+    }
+
+    /// Only use HTTP/1.1, even if the `http2` feature is enabled
+    ///
+    /// This is an escape hatch for proxies with broken HTTP/2 support.
+    pub fn with_http1_only(mut self) -> SnowflakeClient {
+        self.options_mut().http1_only = true;
+        self
+    }
+
+    /// Compress statements with gzip when their body is at least `min_size` bytes
+    ///
+    /// Bulk bindings are repetitive JSON that usually shrinks several times over, which cuts
+    /// upload time for large batch inserts. This is off by default because some proxies
+    /// reject compressed request bodies. Responses are compressed either way.
+    pub fn with_gzip_requests(mut self, min_size: usize) -> SnowflakeClient {
+        self.options_mut().gzip_requests = Some(min_size);
+        self
+    }
+
+    /// Reject statements that the policy doesn't allow, before sending them
+    ///
+    /// For example, `client.with_statement_policy(StatementPolicy::read_only())`.
+    pub fn with_statement_policy(mut self, policy: StatementPolicy) -> SnowflakeClient {
+        self.options_mut().statement_policy = Some(policy);
+        self
+    }
+
+    /// Call this hook with an [`AuditRecord`] for every statement the client executes
+    ///
+    /// This is separate from debug logging: it's invoked exactly once per statement, and it
+    /// never sees binding values or SQL literals, only a redacted [`fingerprint`] of the SQL.
+    pub fn with_audit_hook(
+        mut self,
+        hook: impl Fn(&AuditRecord) + Send + Sync + 'static,
+    ) -> SnowflakeClient {
+        self.options_mut().audit_hook = Some(AuditHook::new(hook));
+        self
+    }
+
+    /// Choose how often to check on statements that Snowflake runs asynchronously
+    ///
+    /// Individual statements can override this with [`Statement::with_poll_strategy`].
+    pub fn with_poll_strategy(mut self, poll_strategy: PollStrategy) -> SnowflakeClient {
+        self.options_mut().poll_strategy = Some(poll_strategy);
+        self
+    }
+
+    /// Retry statements that fail because the warehouse is still resuming
+    ///
+    /// For example, `client.with_warehouse_retry(WarehouseRetry::new())`.
+    pub fn with_warehouse_retry(mut self, retry: WarehouseRetry) -> SnowflakeClient {
+        self.options_mut().warehouse_retry = Some(retry);
+        self
+    }
+
+    /// Set each statement's `QUERY_TAG` from the current `tracing` span, so entries in
+    /// `QUERY_HISTORY` can be joined back to application traces
+    ///
+    /// The tag is JSON like `{"trace_id":"4bf9...","span":"load_orders"}`, where the trace id
+    /// comes from the statement's trace context or the client's trace context provider, if any.
+    /// Statements with an explicit [`Statement::with_query_tag`] keep their own tag.
+    #[cfg(feature = "tracing")]
+    pub fn with_query_tag_from_span(mut self) -> SnowflakeClient {
+        self.options_mut().query_tag_from_span = true;
+        self
+    }
+
+    /// Call this hook with a [`PartitionFetch`] for every partition the client fetches,
+    /// including the first partition that comes with each statement's response
+    ///
+    /// The same numbers are summed up per query by [`QueryResponse::transfer_stats`].
+    pub fn with_metrics_hook(
+        mut self,
+        hook: impl Fn(&PartitionFetch) + Send + Sync + 'static,
+    ) -> SnowflakeClient {
+        self.options_mut().metrics_hook = Some(MetricsHook::new(hook));
+        self
+    }
+
+    /// Send requests through a [`ConnectionPool`] shared with other clients, reusing its
+    /// connections and cached tokens
+    ///
+    /// The pool's connection settings replace this client's, like
+    /// [`SnowflakeClient::with_tcp_keepalive`].
+    pub fn with_connection_pool(mut self, pool: ConnectionPool) -> SnowflakeClient {
+        self.options_mut().connection_pool = Some(pool);
+        self
+    }
+
+    /// Limit how quickly this client sends statements to its warehouse
+    ///
+    /// See [`RateLimiter`] for how limits are shared between clients.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> SnowflakeClient {
+        self.options_mut().rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Execute many prepared statements concurrently, with at most `max_concurrency` in flight
+    ///
+    /// The results are returned in the same order as the statements, and one failing
+    /// statement does not prevent the others from running.
+    ///
+    /// This is intended for fanning out many small queries (e.g. one per tenant),
+    /// so keep in mind that each statement still buffers its first partition.
+    pub async fn run_all<I>(
+        &self,
+        statements: I,
+        max_concurrency: usize,
+    ) -> Vec<SnowflakeResult<QueryResponse>>
+    where
+        I: IntoIterator<Item = Statement>,
+    {
+        use futures::StreamExt;
+        futures::stream::iter(statements)
+            .map(|statement| async move { statement.query().await })
+            .buffered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Run a `SHOW` command and collect every row, e.g. `client.show("TABLES IN SCHEMA PUBLIC")`
+    ///
+    /// SHOW commands name their columns in lowercase, as in Snowflake's documentation
+    /// (`created_on`, `name`, `owner`, ...), so rows can be indexed like `row["created_on"]`.
+    /// Since [`Row::get`] falls back to a case-insensitive match, `row["NAME"]` works too.
+    pub async fn show(&self, what: &str) -> SnowflakeResult<Vec<Row>> {
+        use futures::TryStreamExt;
+        let response = self.prepare(&format!("SHOW {what}")).query().await?;
+        response.rows().try_collect().await
+    }
+
+    /// List the files in a stage, optionally filtered by a regular expression
+    ///
+    /// For example, `client.list_stage("@my_stage/data", Some(r".*\.csv\.gz"))`.
+    /// The stage is used as written, so quote it yourself if it needs quoting.
+    pub async fn list_stage(
+        &self,
+        stage: &str,
+        pattern: Option<&str>,
+    ) -> SnowflakeResult<Vec<StageFile>> {
+        use futures::TryStreamExt;
+        let response = self
+            .prepare(&stage::list_sql(stage, pattern))
+            .query()
+            .await?;
+        let rows: Vec<Row> = response.rows().try_collect().await?;
+        rows.into_iter().map(StageFile::try_from).collect()
+    }
+
+    /// Prepare a Snowflake Scripting anonymous block (`DECLARE ... BEGIN ... END`) for execution
+    ///
+    /// The SQL API would otherwise treat each `;` in the block as the end of a statement,
+    /// so the block is wrapped in `EXECUTE IMMEDIATE` (unless it already is). Run it with
+    /// [`Statement::query`]: a block that returns a value responds with a single row
+    /// and column named `anonymous block`, and one that returns `TABLE(...)` responds with
+    /// the table's rows.
+    ///
+    /// Use [`Statement::call`] to tell a returned value from a returned table.
+    /// `?` bindings can't be used inside the block. A [`StatementPolicy`] sees the block as
+    /// one `EXECUTE` statement.
+    pub fn prepare_block(&self, block: &str) -> Statement {
+        Statement::new(&statement::script_sql(block), self)
+    }
+
+    /// Start a multi-row `INSERT` into a table, which is split into several statements if needed
+    ///
+    /// See [`BatchInsert`] for adding rows, limits, and transactions.
+    pub fn batch_insert(&self, table: &str) -> BatchInsert {
+        BatchInsert::new(self, table)
+    }
+
+    /// Load a long list of values into a temporary table for a query to join against,
+    /// e.g. `client.temp_values("ids", "NUMBER", ids).query("... JOIN {ids} t ON t.value = ...")`
+    ///
+    /// This avoids huge `IN` lists. See [`TempValues`] for how the table is named and managed.
+    pub fn temp_values<I>(&self, placeholder: &str, column_type: &str, values: I) -> TempValues
+    where
+        I: IntoIterator,
+        I::Item: ToBinding,
+    {
+        TempValues::new(self, placeholder, column_type, values)
+    }
+
+    /// Insert structs (or maps) as rows of a table, e.g. `client.insert_structs("events", &events)`
+    ///
+    /// This is the write-side counterpart to [`Partition::rows_as`]. Each field becomes a column
+    /// of the same name (quoted if it needs to be, so use `#[serde(rename)]` to match other names),
+    /// `None` becomes `NULL`, and nested objects and arrays are inserted as `VARIANT` with
+    /// `PARSE_JSON`. Large batches are split into several `INSERT` statements, which run one
+    /// after another and are not wrapped in a transaction, so an error partway through leaves
+    /// the earlier rows inserted. Use [`SnowflakeClient::batch_insert`] with
+    /// [`BatchInsert::add_structs`] for more control.
+    pub async fn insert_structs<T: serde::Serialize>(
+        &self,
+        table: &str,
+        items: &[T],
+    ) -> SnowflakeResult<ChangesSummary> {
+        self.batch_insert(table).add_structs(items)?.execute().await
+    }
+
+    /// Start unloading the results of a query to a stage or external location with
+    /// `COPY INTO <location>`, e.g. `client.unload("@exports/users/", "SELECT * FROM users")`
+    ///
+    /// See [`Unload`] for file formats and copy options.
+    pub fn unload(&self, location: &str, query: &str) -> Unload {
+        Unload::new(self, location, query)
+    }
+
+    /// Start a query that reads tables at points in the past with Time Travel, e.g.
+    /// `client.time_travel("SELECT * FROM {users}").with_table("users", "users", point)`
+    ///
+    /// See [`TimeTravel`] for placeholders and bindings, and [`TimeTravelPoint`] for the points.
+    pub fn time_travel(&self, sql: &str) -> TimeTravel {
+        TimeTravel::new(self, sql)
+    }
+
+    /// Use Snowflake Cortex's LLM functions, like `COMPLETE` and `EMBED_TEXT_768`
+    #[cfg(feature = "cortex")]
+    pub fn cortex(&self) -> Cortex {
+        Cortex::new(self)
+    }
+
+    /// Use Snowflake's REST endpoints for databases, schemas, and warehouses
+    pub fn catalog(&self) -> Catalog {
+        Catalog::new(self)
+    }
+
+    /// Create a zero-copy clone of a table, schema, or database, e.g.
+    /// `client.clone_object("TABLE", "prod.public.users", "test.public.users", None)`
+    ///
+    /// This runs `CREATE <kind> <target> CLONE <source>`, with an `AT` or `BEFORE` clause if a
+    /// point is given, to clone the source as it was then. The names are quoted if they need to
+    /// be, like in [`SnowflakeClient::get_ddl`], and the kind is used as written.
+    pub async fn clone_object(
+        &self,
+        kind: &str,
+        source: &str,
+        target: &str,
+        at: Option<TimeTravelPoint>,
+    ) -> SnowflakeResult<Changes> {
+        let (sql, binding) = time_travel::clone_sql(kind, source, target, at.as_ref());
+        binding
+            .into_iter()
+            .fold(self.prepare(&sql), Statement::add_binding)
+            .manipulate()
+            .await
+    }
+
+    /// Refer to an existing stream, to read and consume the changes it has recorded
+    pub fn change_stream(&self, name: &str) -> ChangeStream {
+        ChangeStream::new(self, name)
+    }
+
+    /// Refer to an existing task, to resume, suspend, or run it, or read its history
+    pub fn task(&self, name: &str) -> Task {
+        Task::new(self, name)
+    }
+
+    /// Start a `CREATE TASK` statement for a task that runs `statement`
+    ///
+    /// See [`CreateTask`] for schedules and dependencies.
+    pub fn create_task(&self, name: &str, statement: &str) -> CreateTask {
+        CreateTask::new(self, name, statement)
+    }
+
+    /// Get the DDL that would recreate an object, e.g. `client.get_ddl("TABLE", "db.public.users")`
+    ///
+    /// This runs `SELECT GET_DDL(?, ?)`. Each part of the name is quoted if it needs to be
+    /// (e.g. if it contains spaces), and parts that are already quoted are kept as they are,
+    /// so a case sensitive name can be written like `db.public."Users"`.
+    pub async fn get_ddl(&self, object_type: &str, name: &str) -> SnowflakeResult<String> {
+        let response = self
+            .prepare("SELECT GET_DDL(?, ?)")
+            .add_binding(object_type)
+            .add_binding(statement::quote_object_name(name))
+            .query()
+            .await?;
+        match response
+            .only_partition()?
+            .cells()?
+            .pop()
+            .and_then(|row| row.into_iter().next())
+        {
+            Some(Cell::Varchar(ddl)) => Ok(ddl.to_string()),
+            _ => Err(SnowflakeError::UnexpectedResult(
+                "GET_DDL did not return any text".into(),
+            )),
+        }
+    }
+}
+
+/// A client for unit tests, which never connect to Snowflake
+///
+/// Generating an RSA key is slow, so every test client shares one.
+#[cfg(test)]
+pub(crate) fn test_client() -> SnowflakeClient {
+    static KEY_PAIR: std::sync::OnceLock<Arc<RS256KeyPair>> = std::sync::OnceLock::new();
+    let key_pair = KEY_PAIR.get_or_init(|| {
+        Arc::new(RS256KeyPair::generate(2048).expect("failed to generate a test key"))
+    });
+    SnowflakeClient::new(key_pair.clone(), "ACCOUNT", "USER")
+        .with_database("DB")
+        .with_warehouse("WH")
+        .with_role("ROLE")
 }
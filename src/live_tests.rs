@@ -10,14 +10,14 @@ fn default_client() -> SnowflakeClient {
     let key_path = require("SNOWFLAKE_TRADITIONAL_RSA_KEY_PATH");
     let key_content = std::fs::read_to_string(key_path).expect("failed to read key file");
     let key_pair = RS256KeyPair::from_pem(&key_content).expect("failed to parse key");
-    SnowflakeClient {
+    SnowflakeClient::new(
         key_pair,
-        account: require("SNOWFLAKE_ACCOUNT"),
-        user: require("SNOWFLAKE_USER"),
-        database: require("SNOWFLAKE_DATABASE"),
-        warehouse: require("SNOWFLAKE_WAREHOUSE"),
-        role: Some(require("SNOWFLAKE_ROLE")),
-    }
+        &require("SNOWFLAKE_ACCOUNT"),
+        &require("SNOWFLAKE_USER"),
+    )
+    .with_database(&require("SNOWFLAKE_DATABASE"))
+    .with_warehouse(&require("SNOWFLAKE_WAREHOUSE"))
+    .with_role(&require("SNOWFLAKE_ROLE"))
 }
 
 #[tokio::test]
@@ -25,13 +25,38 @@ async fn can_login() -> SnowflakeResult<()> {
     let client = default_client();
     let sql = client.prepare("SELECT 1");
     let result = sql.query().await?;
-    let cells = result.only_partition()?.cells();
+    let cells = result.only_partition()?.cells()?;
     assert_eq!(cells.len(), 1);
     assert_eq!(cells[0].len(), 1);
     assert!(matches!(cells[0][0], Cell::Int(1)));
     Ok(())
 }
 
+#[tokio::test]
+async fn exact_numbers_keep_the_column_scale() -> SnowflakeResult<()> {
+    let client = default_client().with_number_mode(crate::NumberMode::Exact);
+    let sql = client.prepare("SELECT 1.5::NUMBER(38, 2), 12345678901234567890.12::NUMBER(38, 2)");
+    let cells = sql.query().await?.only_partition()?.cells()?;
+    assert_eq!(
+        cells[0][0],
+        Cell::Number {
+            mantissa: 150,
+            scale: 2
+        }
+    );
+    assert_eq!(cells[0][1].to_string(), "12345678901234567890.12");
+    Ok(())
+}
+
+#[tokio::test]
+async fn ltz_timestamps_use_the_client_zone() -> SnowflakeResult<()> {
+    let client = default_client().with_timestamp_zone(crate::TimestampZone::Utc);
+    let sql = client.prepare("SELECT '2023-01-01 01:01:01 +01:00'::TIMESTAMP_LTZ");
+    let cells = sql.query().await?.only_partition()?.cells()?;
+    assert_eq!(cells[0][0].to_string(), "2023-01-01 00:01:01 +00:00");
+    Ok(())
+}
+
 #[tokio::test]
 async fn can_query_many_types() -> SnowflakeResult<()> {
     let client = default_client();
@@ -47,15 +72,20 @@ async fn can_query_many_types() -> SnowflakeResult<()> {
         '2023-01-01 01:01:01'::timestamp_ltz,
         '2023-01-01 01:01:01Z'::timestamp_tz,
         '2023-01-01'::date,
-        '01:01:01'::time
+        '01:01:01'::time,
+        PARSE_JSON('{\"a\": [1, 2]}'),
+        OBJECT_CONSTRUCT('b', 'c'),
+        ARRAY_CONSTRUCT(1, 2),
+        [1.5, 2]::VECTOR(FLOAT, 2)
     ",
     );
     let result = sql.query().await?;
-    let cells = result.only_partition()?.cells();
+    let cells = result.only_partition()?.cells()?;
     assert_eq!(cells.len(), 1);
     assert!(matches!(cells[0][0], Cell::Int(1)));
-    assert!(matches!(cells[0][1], Cell::Varchar(ref x) if x == "foo"));
-    assert!(matches!(cells[0][2], Cell::Int(1)));
+    assert!(matches!(cells[0][1], Cell::Varchar(ref x) if &**x == "foo"));
+    // 1.0 is a NUMBER(2,1), so it's a float like every other cell of its column would be
+    assert!(matches!(cells[0][2], Cell::Float(x) if x == 1.0));
     assert!(matches!(cells[0][3], Cell::Boolean(true)));
     assert!(matches!(cells[0][4], Cell::Null));
     assert!(matches!(cells[0][5], Cell::Float(x) if x > 1.0 && x < 1.2));
@@ -72,8 +102,11 @@ async fn can_query_many_types() -> SnowflakeResult<()> {
     // TODO: test timezone
     // Not sure how to do this without just comparing two implementations of the same thing
     assert!(matches!(cells[0][8], Cell::TimestampLtz(_)));
-    // TIMESTAMP_TZ is just too complex to support yet
-    assert!(matches!(cells[0][9], Cell::Null));
+    assert!(matches!(cells[0][9],
+        Cell::TimestampTz(ref x)
+        if x.timestamp() == 1672534861
+        && x.offset().local_minus_utc() == 0
+    ));
     assert!(matches!(cells[0][10],
         Cell::Date(ref x)
         if x.year() == 2023
@@ -86,6 +119,10 @@ async fn can_query_many_types() -> SnowflakeResult<()> {
         && x.minute() == 1
         && x.second() == 1
     ));
+    assert!(matches!(cells[0][12], Cell::Json(ref x) if x["a"][1] == 2));
+    assert!(matches!(cells[0][13], Cell::Json(ref x) if x["b"] == "c"));
+    assert!(matches!(cells[0][14], Cell::Json(ref x) if *x == serde_json::json!([1, 2])));
+    assert!(matches!(cells[0][15], Cell::Vector(ref x) if *x == [1.5, 2.0]));
     Ok(())
 }
 
@@ -94,7 +131,7 @@ async fn can_query_many_rows() -> SnowflakeResult<()> {
     let client = default_client();
     let sql = client.prepare("SELECT seq4() FROM table(generator(rowcount => 100))");
     let result = sql.query().await?;
-    let cells = result.only_partition()?.cells();
+    let cells = result.only_partition()?.cells()?;
     assert_eq!(cells.len(), 100);
     for row in cells {
         assert_eq!(row.len(), 1);
@@ -127,10 +164,10 @@ async fn can_query_with_many_bindings() -> SnowflakeResult<()> {
         .add_binding("01:01:01")
         .add_binding("2023-01-01 01:01:01");
     let result = sql.query().await?;
-    let cells = result.only_partition()?.cells();
+    let cells = result.only_partition()?.cells()?;
     assert_eq!(cells.len(), 1);
     assert!(matches!(cells[0][0], Cell::Int(1)));
-    assert!(matches!(cells[0][1], Cell::Varchar(ref x) if x == "foo"));
+    assert!(matches!(cells[0][1], Cell::Varchar(ref x) if &**x == "foo"));
     assert!(matches!(cells[0][2], Cell::Float(ref x) if x == &1.0));
     assert!(matches!(cells[0][3], Cell::Boolean(true)));
     assert!(matches!(cells[0][4], Cell::Binary(ref x) if x == b"foo"));
@@ -171,15 +208,15 @@ async fn can_query_with_very_many_rows_and_streaming() -> SnowflakeResult<()> {
     assert!(resp.num_partitions() > 1);
     let one_partition = resp.concat_partitions().await?;
     assert_eq!(one_partition.num_rows(), 100000);
-    for (ix, row) in one_partition.cells().into_iter().enumerate() {
+    for (ix, row) in one_partition.cells()?.into_iter().enumerate() {
         assert_eq!(row.len(), 1);
         assert!(matches!(row[0], Cell::Int(x) if x == ix as i128));
     }
-    for (ix, row) in one_partition.json_objects().into_iter().enumerate() {
+    for (ix, row) in one_partition.json_objects()?.into_iter().enumerate() {
         assert!(matches!(row["IX"], serde_json::Value::Number(_)));
         assert_eq!(row["IX"].as_i64().unwrap(), ix as i64);
     }
-    for (ix, row) in one_partition.json_table().into_iter().enumerate() {
+    for (ix, row) in one_partition.json_table()?.into_iter().enumerate() {
         assert_eq!(row.len(), 1);
         assert!(matches!(row[0], serde_json::Value::Number(_)));
         assert_eq!(row[0].as_i64().unwrap(), ix as i64);
@@ -189,6 +226,7 @@ async fn can_query_with_very_many_rows_and_streaming() -> SnowflakeResult<()> {
     resp.rows()
         .try_for_each(|row| async move {
             assert_eq!(row.len(), 1);
+            assert!(matches!(row["IX"], Cell::Int(_)));
             assert!(matches!(row[0], Cell::Int(_)));
             Ok(())
         })
@@ -208,10 +246,266 @@ async fn can_query_with_very_many_rows_and_streaming() -> SnowflakeResult<()> {
         .await?;
     resp.partitions()
         .try_for_each(|partition| async move {
-            let cells = partition.cells();
+            let cells = partition.cells()?;
             assert!(cells.len() > 100);
             Ok(())
         })
         .await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn can_run_all_concurrently() -> SnowflakeResult<()> {
+    let client = default_client();
+    let statements = (0..5).map(|ix| client.prepare("SELECT ?::int").add_binding(ix));
+    let results = client.run_all(statements, 2).await;
+    assert_eq!(results.len(), 5);
+    for (ix, result) in results.into_iter().enumerate() {
+        let cells = result?.only_partition()?.cells()?;
+        assert!(matches!(cells[0][0], Cell::Int(x) if x == ix as i128));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_fetch_query_stats() -> SnowflakeResult<()> {
+    let client = default_client();
+    let resp = client
+        .prepare("SELECT seq4() FROM table(generator(rowcount => 10))")
+        .query()
+        .await?;
+    assert!(!resp.query_id().is_empty());
+    let stats = resp.stats().await?.expect("query should be in history");
+    assert_eq!(stats.rows_produced, Some(10));
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_check_statement_status() -> SnowflakeResult<()> {
+    let client = default_client();
+    let resp = client.prepare("SELECT 1").query().await?;
+    assert_eq!(resp.status().await?, crate::StatementStatus::Succeeded);
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_prefetch_all_partitions() -> SnowflakeResult<()> {
+    let client = default_client();
+    let resp = client
+        .prepare("SELECT seq4() as ix FROM table(generator(rowcount => 100000))")
+        .query()
+        .await?;
+    let partition = resp.prefetch_all(4, None).await?;
+    assert_eq!(partition.num_rows(), 100000);
+    assert!(matches!(
+        resp.prefetch_all(4, Some(1000)).await,
+        Err(crate::SnowflakeError::ResultTooLarge { .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_show_warehouses() -> SnowflakeResult<()> {
+    let client = default_client();
+    let warehouses = client.show("WAREHOUSES").await?;
+    assert!(warehouses
+        .iter()
+        .any(|row| matches!(&row["name"], Cell::Varchar(name) if client.warehouse().is_some_and(|warehouse| name.eq_ignore_ascii_case(warehouse)))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_get_ddl() -> SnowflakeResult<()> {
+    let client = default_client();
+    client
+        .prepare("CREATE OR REPLACE TEMPORARY TABLE ddl_test (id INT)")
+        .query()
+        .await?;
+    let ddl = client.get_ddl("TABLE", "ddl_test").await?;
+    assert!(ddl.to_ascii_uppercase().contains("DDL_TEST"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_list_an_empty_stage() -> SnowflakeResult<()> {
+    let client = default_client();
+    client
+        .prepare("CREATE OR REPLACE TEMPORARY STAGE list_test")
+        .query()
+        .await?;
+    let files = client.list_stage("@list_test", Some(r".*\.csv")).await?;
+    assert!(files.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_insert_structs() -> SnowflakeResult<()> {
+    #[derive(serde::Serialize)]
+    struct Event {
+        id: i64,
+        name: Option<String>,
+        payload: serde_json::Value,
+    }
+    let client = default_client();
+    client
+        .prepare(
+            "CREATE OR REPLACE TEMPORARY TABLE insert_test (id INT, name TEXT, payload VARIANT)",
+        )
+        .query()
+        .await?;
+    let events = (0..3)
+        .map(|id| Event {
+            id,
+            name: (id > 0).then(|| format!("event {id}")),
+            payload: serde_json::json!({ "id": id }),
+        })
+        .collect::<Vec<_>>();
+    let summary = client.insert_structs("insert_test", &events).await?;
+    assert_eq!(summary.rows_inserted, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_batch_insert_in_transaction() -> SnowflakeResult<()> {
+    let client = default_client();
+    client
+        .prepare("CREATE OR REPLACE TEMPORARY TABLE batch_test (id INT, name TEXT)")
+        .query()
+        .await?;
+    let batch = (0..10).fold(
+        client
+            .batch_insert("batch_test")
+            .with_columns(&["id", "name"]),
+        |batch, id| batch.add_row([crate::Binding::from(id), format!("row {id}").into()]),
+    );
+    let summary = batch
+        .with_max_bindings(6)
+        .in_transaction()
+        .execute()
+        .await?;
+    assert_eq!(summary.rows_inserted, 10);
+    assert_eq!(summary.statements.len(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_fetch_scalars() -> SnowflakeResult<()> {
+    let client = default_client();
+    let count: i64 = client
+        .prepare("SELECT COUNT(*) FROM table(generator(rowcount => 7))")
+        .fetch_scalar()
+        .await?;
+    assert_eq!(count, 7);
+    let missing: Option<String> = client
+        .prepare("SELECT 'x' WHERE FALSE")
+        .fetch_scalar()
+        .await?;
+    assert_eq!(missing, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_unload_to_a_stage() -> SnowflakeResult<()> {
+    let client = default_client();
+    client
+        .prepare("CREATE OR REPLACE TEMPORARY STAGE unload_test")
+        .query()
+        .await?;
+    let summary = client
+        .unload(
+            "@unload_test/data_",
+            "SELECT seq4() AS ix FROM table(generator(rowcount => 100))",
+        )
+        .with_format_type("CSV")
+        .with_option("SINGLE", "TRUE")
+        .execute()
+        .await?;
+    assert_eq!(summary.rows_unloaded, 100);
+    assert_eq!(summary.files.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_run_anonymous_blocks() -> SnowflakeResult<()> {
+    let client = default_client();
+    let value: Cell = client
+        .prepare_block(
+            "DECLARE
+                total INT DEFAULT 0;
+            BEGIN
+                FOR i IN 1 TO 3 DO
+                    total := total + i;
+                END FOR;
+                RETURN total;
+            END;",
+        )
+        .fetch_scalar()
+        .await?;
+    // The type of the "anonymous block" column depends on how the value was declared
+    assert!(matches!(&value, Cell::Varchar(x) if &**x == "6") || matches!(value, Cell::Int(6)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_call_blocks_returning_values_and_tables() -> SnowflakeResult<()> {
+    let client = default_client();
+    let output = client
+        .prepare_block("BEGIN RETURN 'done'; END;")
+        .call()
+        .await?;
+    assert_eq!(output.value::<String>()?, "done");
+    let output = client
+        .prepare_block(
+            "DECLARE
+                res RESULTSET DEFAULT (SELECT seq4() AS ix FROM table(generator(rowcount => 3)));
+            BEGIN
+                RETURN TABLE(res);
+            END;",
+        )
+        .call()
+        .await?;
+    let table = output.into_table().expect("a table");
+    assert_eq!(table.num_rows(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_query_against_temp_values() -> SnowflakeResult<()> {
+    let client = default_client();
+    let response = client
+        .temp_values("ids", "NUMBER", (0..20_000).step_by(2))
+        .add_binding(100)
+        .query("SELECT COUNT(*) FROM table(generator(rowcount => 20000)) g JOIN {ids} t ON seq4() = t.value WHERE t.value < ?")
+        .await?;
+    let cells = response.only_partition()?.cells()?;
+    assert!(matches!(cells[0][0], Cell::Int(50)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_list_catalog_objects() -> SnowflakeResult<()> {
+    let client = default_client();
+    let catalog = client.catalog();
+    let databases = catalog.list_databases(None).await?;
+    assert!(!databases.is_empty());
+    let database = catalog.describe_database(&databases[0].name).await?;
+    assert_eq!(database.name, databases[0].name);
+    catalog.list_warehouses(None).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_relay_raw_partitions() -> SnowflakeResult<()> {
+    let client = default_client();
+    let resp = client
+        .prepare("SELECT seq4() as ix FROM table(generator(rowcount => 100000))")
+        .query()
+        .await?;
+    let bodies: Vec<_> = resp.raw_partitions().try_collect().await?;
+    assert_eq!(bodies.len(), resp.num_partitions());
+    let first: serde_json::Value = serde_json::from_slice(&bodies[0])?;
+    assert_eq!(first["resultSetMetaData"]["rowType"][0]["name"], "IX");
+    let last: serde_json::Value = serde_json::from_slice(bodies.last().unwrap())?;
+    assert!(last["data"].as_array().is_some_and(|rows| !rows.is_empty()));
+    Ok(())
+}
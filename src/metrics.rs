@@ -0,0 +1,103 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Client-side statistics about fetching a query's result
+///
+/// Get a snapshot with [`QueryResponse::transfer_stats`](crate::QueryResponse::transfer_stats).
+/// These grow as more partitions are fetched, so take the snapshot after you've consumed
+/// the rows you need.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferStats {
+    /// The number of response body bytes received, after decompression
+    pub bytes_downloaded: usize,
+    /// How many partitions have been fetched, including the first
+    pub partitions_fetched: usize,
+    /// How long each fetched partition took, by partition index
+    ///
+    /// The first partition's duration includes running the statement.
+    pub partition_durations: Vec<(usize, Duration)>,
+    /// How many rows were received in the fetched partitions
+    pub rows_parsed: usize,
+    /// How many times the statement was resubmitted or re-executed, e.g. because the
+    /// warehouse was resuming or the result expired
+    pub retries: u32,
+    /// The time from submitting the statement until the most recent partition arrived
+    pub wall_time: Duration,
+}
+
+impl TransferStats {
+    /// Account for one fetched partition
+    pub(crate) fn record(&mut self, fetch: &PartitionFetch) {
+        self.bytes_downloaded += fetch.bytes;
+        self.partitions_fetched += 1;
+        self.partition_durations.push((fetch.index, fetch.duration));
+        self.rows_parsed += fetch.rows;
+    }
+}
+
+/// When Snowflake created a statement, and how long the client waited for it
+///
+/// These are on [`QueryResponse::latency`](crate::QueryResponse::latency) and
+/// [`Changes::latency`](crate::Changes::latency). Comparing the client's durations with
+/// the execution time in [`QueryStats`](crate::QueryStats) shows how much of the wait
+/// was spent on the network and in queues rather than running the statement.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct Latency {
+    /// When Snowflake created the statement, from the response's `createdOn`
+    pub created_on: Option<SystemTime>,
+    /// The time from first submitting the statement until the headers of the final response
+    /// arrived, including any polling and warehouse retries
+    pub time_to_first_byte: Duration,
+    /// The time from first submitting the statement until its response was read and parsed
+    pub total: Duration,
+}
+
+impl Latency {
+    /// Add Snowflake's `createdOn`, in milliseconds since the Unix epoch
+    pub(crate) fn with_created_on(mut self, created_on: Option<u64>) -> Latency {
+        self.created_on =
+            created_on.map(|millis| SystemTime::UNIX_EPOCH + Duration::from_millis(millis));
+        self
+    }
+}
+
+/// One partition that was fetched, passed to a [`MetricsHook`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionFetch {
+    /// Snowflake's query id
+    pub query_id: String,
+    /// The partition's index, where 0 is the partition returned with the statement
+    pub index: usize,
+    /// The number of response body bytes received, after decompression
+    pub bytes: usize,
+    /// The number of rows in the partition
+    pub rows: usize,
+    /// How long the partition took to fetch (for the first, this includes running the statement)
+    pub duration: Duration,
+}
+
+/// A callback invoked with a [`PartitionFetch`] for every partition a client fetches,
+/// e.g. to feed a metrics library
+///
+/// Attach one to a client with
+/// [`SnowflakeClient::with_metrics_hook`](crate::SnowflakeClient::with_metrics_hook).
+#[derive(Clone)]
+pub struct MetricsHook(Arc<dyn Fn(&PartitionFetch) + Send + Sync>);
+
+impl MetricsHook {
+    /// Wrap a function that receives each partition fetch
+    pub fn new(hook: impl Fn(&PartitionFetch) + Send + Sync + 'static) -> Self {
+        MetricsHook(Arc::new(hook))
+    }
+
+    pub(crate) fn record(&self, fetch: &PartitionFetch) {
+        (self.0)(fetch)
+    }
+}
+
+impl fmt::Debug for MetricsHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsHook")
+    }
+}
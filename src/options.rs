@@ -0,0 +1,65 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::audit::AuditHook;
+use crate::cells::{NumberMode, TimestampZone};
+use crate::failover::Failover;
+use crate::metrics::MetricsHook;
+use crate::policy::StatementPolicy;
+use crate::poll::PollStrategy;
+use crate::pool::ConnectionPool;
+use crate::rate_limit::RateLimiter;
+use crate::retry::WarehouseRetry;
+use crate::trace::TraceContextProvider;
+
+/// Optional settings for a [`SnowflakeClient`](crate::SnowflakeClient)
+///
+/// Everything here has a sensible default, so most users can use `ClientOptions::default()`
+/// and then adjust individual settings with the `with_*` methods on the client.
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    /// Tag statements with the current tracing span, unless they have an explicit query tag
+    #[cfg(feature = "tracing")]
+    pub query_tag_from_span: bool,
+    /// Limit how quickly statements are sent, per warehouse
+    pub rate_limiter: Option<RateLimiter>,
+    /// Client-side guardrails for which statements may be sent
+    pub statement_policy: Option<StatementPolicy>,
+    /// Receives a record of every executed statement, for compliance logging
+    pub audit_hook: Option<AuditHook>,
+    /// How often to check on statements that Snowflake runs asynchronously
+    pub poll_strategy: Option<PollStrategy>,
+    /// Retry statements that fail while the warehouse is resuming
+    pub warehouse_retry: Option<WarehouseRetry>,
+    /// Receives statistics about every partition fetched, for metrics
+    pub metrics_hook: Option<MetricsHook>,
+    /// A secondary account to use when the primary account is unreachable
+    pub failover: Option<Failover>,
+    /// Reads the current trace context before each request, to propagate it to Snowflake
+    pub trace_context_provider: Option<TraceContextProvider>,
+    /// The timeout for each request that fetches a partition, instead of the statement's timeout
+    pub partition_timeout: Option<Duration>,
+    /// How NUMBER cells are parsed, unless a statement sets its own mode
+    pub number_mode: NumberMode,
+    /// The time zone that TIMESTAMP_LTZ cells are converted to
+    pub timestamp_zone: TimestampZone,
+    /// Hostnames to resolve to fixed addresses instead of using DNS
+    pub resolve: Vec<(String, SocketAddr)>,
+    /// How often to send TCP keepalive probes, to stop NATs from dropping quiet connections
+    pub tcp_keepalive: Option<Duration>,
+    /// How often to send HTTP/2 keepalive pings, and how long to wait for the reply
+    #[cfg(feature = "http2")]
+    pub http2_keep_alive: Option<(Duration, Duration)>,
+    /// Grow HTTP/2 flow control windows to match the measured bandwidth-delay product
+    #[cfg(feature = "http2")]
+    pub http2_adaptive_window: bool,
+    /// The largest HTTP/2 frame to accept, in bytes
+    #[cfg(feature = "http2")]
+    pub http2_max_frame_size: Option<u32>,
+    /// Only use HTTP/1.1, even if the `http2` feature is enabled
+    pub http1_only: bool,
+    /// Gzip statement bodies of at least this many bytes
+    pub gzip_requests: Option<usize>,
+    /// Connections and tokens shared with other clients, instead of this client's own
+    pub connection_pool: Option<ConnectionPool>,
+}
@@ -1,8 +1,20 @@
+use std::ops::{Bound, Range, RangeBounds};
 use std::sync::Arc;
 
-use crate::{cells::Cell, statement::WireStatementMetaData};
+use crate::cell_ref::CellRef;
+use crate::cells::{Cell, NumberMode, TimestampZone};
+use crate::errors::SnowflakeResult;
+use crate::lazy_row::LazyRow;
+use crate::row::Row;
+use crate::statement::{ColumnType, WirePartitionInfo, WireStatementMetaData};
+use crate::table::CellTable;
 pub type StringTable = Vec<Vec<Option<String>>>;
 
+/// With the `rayon` feature, partitions with at least this many rows are parsed in parallel.
+/// Smaller ones aren't worth the cost of dividing up the work.
+#[cfg(feature = "rayon")]
+const PARALLEL_ROWS: usize = 4096;
+
 /// A single in-memory chunk of a query response
 ///
 /// Most partitions are in the single digits of megabytes, so clones are not
@@ -10,13 +22,74 @@ pub type StringTable = Vec<Vec<Option<String>>>;
 ///
 /// The data returned from Snowflake is a list of lists of strings, so there
 /// are many type conversions involved, see [`Cell`](`crate::Cell`) for more
+///
+/// A partition may also be a view over a range of rows of another partition,
+/// see [`Partition::slice`].
 pub struct Partition {
     pub(crate) meta_data: WireStatementMetaData,
     pub(crate) data: Arc<StringTable>,
     pub(crate) index: usize,
+    /// The rows of `data` that belong to this partition
+    pub(crate) rows: Range<usize>,
+    pub(crate) number_mode: NumberMode,
+    pub(crate) timestamp_zone: TimestampZone,
+    /// Whether `NULL`s were sent as JSON `null`s rather than `"null"`
+    pub(crate) nullable: bool,
 }
 
 impl Partition {
+    pub(crate) fn new(
+        index: usize,
+        meta_data: WireStatementMetaData,
+        data: Arc<StringTable>,
+    ) -> Partition {
+        Partition {
+            rows: 0..data.len(),
+            meta_data,
+            data,
+            index,
+            number_mode: NumberMode::default(),
+            timestamp_zone: TimestampZone::default(),
+            nullable: true,
+        }
+    }
+
+    /// Parse NUMBER cells with this mode, rather than the statement's (or [`NumberMode::Lossy`]
+    /// for partitions built with [`Partition::from_raw`])
+    pub fn with_number_mode(mut self, number_mode: NumberMode) -> Partition {
+        self.number_mode = number_mode;
+        self
+    }
+
+    /// Convert TIMESTAMP_LTZ cells to this zone, rather than the client's (or
+    /// [`TimestampZone::Local`] for partitions built with [`Partition::from_raw`])
+    pub fn with_timestamp_zone(mut self, timestamp_zone: TimestampZone) -> Partition {
+        self.timestamp_zone = timestamp_zone;
+        self
+    }
+
+    /// Read `"null"` as `NULL` in semi-structured columns, like a partition of a statement with
+    /// [`Statement::with_non_nullable_results`](crate::Statement::with_non_nullable_results)
+    pub fn with_non_nullable_results(mut self) -> Partition {
+        self.nullable = false;
+        self
+    }
+
+    /// Build a partition from strings in Snowflake's wire format, like `"1.5"` for a NUMBER
+    ///
+    /// This is useful for testing and benchmarking code that consumes partitions, without
+    /// a connection to Snowflake. Each row should have a value for each column.
+    pub fn from_raw(columns: impl Into<Arc<[ColumnType]>>, data: StringTable) -> Partition {
+        let meta_data = WireStatementMetaData {
+            num_rows: data.len(),
+            row_type: columns.into(),
+            partition_info: vec![WirePartitionInfo {
+                row_count: Some(data.len()),
+            }],
+        };
+        Partition::new(0, meta_data, Arc::new(data))
+    }
+
     /// Get the index of this partition
     pub fn index(&self) -> usize {
         self.index
@@ -26,7 +99,7 @@ impl Partition {
     /// This is obtained from data.len() rather than the metadata
     /// because this partition may have been constructed by concatenating
     pub fn num_rows(&self) -> usize {
-        self.data.len()
+        self.rows.len()
     }
 
     /// Get the cells in this partition as strings just as they were returned from Snowflake
@@ -34,44 +107,493 @@ impl Partition {
     /// This could be more efficient for some use cases than converting to `Cell`s
     /// but without the type information it could be difficult to work with
     pub fn raw_cells(&self) -> &[Vec<Option<String>>] {
-        self.data.as_ref()
+        &self.data[self.rows.clone()]
+    }
+
+    /// Get a view over a range of rows in this partition
+    ///
+    /// This is cheap because the view shares the underlying strings with this partition,
+    /// so it's a convenient way to divide work among tasks. The view keeps the same index.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, just like slicing a `Vec`.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Partition {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.num_rows(),
+        };
+        assert!(
+            start <= end && end <= self.num_rows(),
+            "range {start}..{end} out of bounds for partition with {} rows",
+            self.num_rows()
+        );
+        Partition {
+            meta_data: self.meta_data.clone(),
+            data: self.data.clone(),
+            index: self.index,
+            rows: self.rows.start + start..self.rows.start + end,
+            number_mode: self.number_mode,
+            timestamp_zone: self.timestamp_zone,
+            nullable: self.nullable,
+        }
+    }
+
+    /// Split this partition into two views at a row index
+    ///
+    /// The first view contains rows `[0, mid)` and the second contains `[mid, num_rows)`.
+    ///
+    /// # Panics
+    /// Panics if `mid > num_rows`.
+    pub fn split_at(&self, mid: usize) -> (Partition, Partition) {
+        (self.slice(..mid), self.slice(mid..))
     }
 
     /// Convert the response into `Cell`s in a list of lists format
     ///
-    /// This most closely matches the format of the response from Snowflake
-    pub fn cells(&self) -> Vec<Vec<Cell>> {
-        self.data
+    /// This most closely matches the format of the response from Snowflake.
+    /// With the `rayon` feature, large partitions are parsed on rayon's thread pool.
+    ///
+    /// This fails with [`SnowflakeError::InvalidCell`](crate::SnowflakeError::InvalidCell) if
+    /// any value can't be parsed as its column's type, and so do the other conversions.
+    ///
+    /// Every cell is parsed up front. To read only a few cells of a wide result, use
+    /// [`Partition::row`] or [`Partition::lazy_rows`] instead.
+    pub fn cells(&self) -> SnowflakeResult<Vec<Vec<Cell>>> {
+        self.map_rows(|row| self.parse_row(row))
+    }
+
+    /// Convert the response into [`CellRef`]s, which borrow text and binary from the partition
+    /// rather than copying them
+    pub fn cells_ref(&self) -> SnowflakeResult<Vec<Vec<CellRef<'_>>>> {
+        let columns = &self.meta_data.row_type;
+        self.raw_cells()
             .iter()
             .map(|row| {
                 row.iter()
-                    .zip(&self.meta_data.row_type)
-                    .map(|(value, row_type)| row_type.data_type.to_cell(value))
+                    .zip(columns.iter())
+                    .map(|(value, column)| {
+                        column.data_type.to_cell_ref_with(
+                            value,
+                            column.scale,
+                            self.number_mode,
+                            self.timestamp_zone,
+                            self.nullable,
+                        )
+                    })
                     .collect()
             })
             .collect()
     }
 
-    /// Convert the response into `serde_json::Value`s in a list of lists format
-    pub fn json_table(&self) -> Vec<Vec<serde_json::Value>> {
-        self.cells()
+    /// Convert the response into [`Row`]s, which carry the column metadata with them
+    pub fn rows(&self) -> SnowflakeResult<Vec<Row>> {
+        self.map_rows(|row| {
+            Ok(Row::new(
+                self.meta_data.row_type.clone(),
+                self.parse_row(row)?,
+            ))
+        })
+    }
+
+    /// Iterate over [`LazyRow`]s, which only parse a cell when it's read
+    ///
+    /// This is cheaper than [`Partition::rows`] when only a few of many columns are read.
+    pub fn lazy_rows(&self) -> impl ExactSizeIterator<Item = LazyRow> + '_ {
+        self.rows.clone().map(|row| self.lazy_row(row))
+    }
+
+    /// Get one row as a [`LazyRow`], or `None` if it's out of bounds
+    ///
+    /// Nothing is parsed until a cell is read with [`LazyRow::cell`] or [`LazyRow::get`], so
+    /// this is a cheap way to pick a few cells out of a wide partition.
+    pub fn row(&self, index: usize) -> Option<LazyRow> {
+        (index < self.num_rows()).then(|| self.lazy_row(self.rows.start + index))
+    }
+
+    /// A lazy view of a row, by its index in `data`
+    fn lazy_row(&self, row: usize) -> LazyRow {
+        LazyRow::new(
+            self.meta_data.row_type.clone(),
+            self.data.clone(),
+            row,
+            self.number_mode,
+            self.timestamp_zone,
+            self.nullable,
+        )
+    }
+
+    /// Deserialize each row into a `T`, such as a struct with a field for each column
+    ///
+    /// This uses [`Row`]'s `serde::Deserializer` implementation, so it doesn't allocate
+    /// intermediate `serde_json::Value`s. Column names match field names case-insensitively.
+    pub fn rows_as<T: serde::de::DeserializeOwned>(&self) -> SnowflakeResult<Vec<T>> {
+        self.rows()?
             .into_iter()
-            .map(|row| row.into_iter().map(|cell| cell.into()).collect())
+            .map(|row| Ok(T::deserialize(row)?))
             .collect()
     }
 
+    /// Convert the response into a [`CellTable`], which stores every cell in one allocation
+    ///
+    /// This uses less memory than [`Partition::cells`] or [`Partition::rows`], so it suits
+    /// results that are kept around, like a cache behind a dashboard.
+    pub fn cell_table(&self) -> SnowflakeResult<CellTable> {
+        let columns = &self.meta_data.row_type;
+        let mut cells = Vec::with_capacity(self.num_rows() * columns.len());
+        for row in self.raw_cells() {
+            for (value, column) in row.iter().zip(columns.iter()) {
+                cells.push(column.data_type.to_cell_with(
+                    value,
+                    column.scale,
+                    self.number_mode,
+                    self.timestamp_zone,
+                    self.nullable,
+                )?);
+            }
+        }
+        Ok(CellTable::new(columns.clone(), self.num_rows(), cells))
+    }
+
+    /// Convert the response into `serde_json::Value`s in a list of lists format
+    pub fn json_table(&self) -> SnowflakeResult<Vec<Vec<serde_json::Value>>> {
+        self.map_rows(|row| {
+            Ok(self
+                .parse_row(row)?
+                .into_iter()
+                .map(|cell| cell.into())
+                .collect())
+        })
+    }
+
     /// Convert the response into `serde_json::Value`s in a list of objects format
-    pub fn json_objects(&self) -> Vec<serde_json::Value> {
-        self.json_table()
-            .into_iter()
-            .map(|row| {
-                serde_json::Value::Object(
-                    row.into_iter()
-                        .enumerate()
-                        .map(|(i, cell)| (self.meta_data.row_type[i].name.clone(), cell))
-                        .collect(),
-                )
+    ///
+    /// Each object has its own copy of every column name. For wide results, or when the
+    /// objects are only going to be serialized, [`Partition::json_objects_shared`] avoids that.
+    pub fn json_objects(&self) -> SnowflakeResult<Vec<serde_json::Value>> {
+        let columns = &self.meta_data.row_type;
+        self.map_rows(|row| {
+            let mut object = serde_json::Map::with_capacity(columns.len());
+            for (column, cell) in columns.iter().zip(self.parse_row(row)?) {
+                object.insert(column.name.clone(), cell.into());
+            }
+            Ok(serde_json::Value::Object(object))
+        })
+    }
+
+    /// Convert the response into [`JsonObject`]s, which all share one copy of the column names
+    ///
+    /// These serialize to the same objects as [`Partition::json_objects`].
+    pub fn json_objects_shared(&self) -> SnowflakeResult<Vec<JsonObject>> {
+        let keys: Arc<[String]> = self
+            .meta_data
+            .row_type
+            .iter()
+            .map(|column| column.name.clone())
+            .collect();
+        self.map_rows(|row| {
+            Ok(JsonObject {
+                keys: keys.clone(),
+                values: self
+                    .parse_row(row)?
+                    .into_iter()
+                    .map(|cell| cell.into())
+                    .collect(),
             })
-            .collect()
+        })
+    }
+
+    /// Convert the partition into a single `serde_json::Value`, like
+    /// `{"columns": ["ID", "NAME"], "rows": [[1, "Henry"]]}`
+    ///
+    /// The rows are in the same format as [`Partition::json_table`].
+    pub fn to_value(&self) -> SnowflakeResult<serde_json::Value> {
+        let columns: Vec<_> = self.meta_data.row_type.iter().map(|c| &c.name).collect();
+        Ok(serde_json::json!({
+            "columns": columns,
+            "rows": self.json_table()?,
+        }))
+    }
+
+    fn parse_row(&self, row: &[Option<String>]) -> SnowflakeResult<Vec<Cell>> {
+        let columns = &self.meta_data.row_type;
+        let mut cells = Vec::with_capacity(columns.len());
+        for (value, column) in row.iter().zip(columns.iter()) {
+            cells.push(column.data_type.to_cell_with(
+                value,
+                column.scale,
+                self.number_mode,
+                self.timestamp_zone,
+                self.nullable,
+            )?);
+        }
+        Ok(cells)
+    }
+
+    /// Convert each raw row, in parallel if the `rayon` feature is enabled and there are
+    /// enough rows. The order of the rows is kept either way, and the first error stops it.
+    fn map_rows<T: Send>(
+        &self,
+        convert: impl Fn(&[Option<String>]) -> SnowflakeResult<T> + Sync,
+    ) -> SnowflakeResult<Vec<T>> {
+        #[cfg(feature = "rayon")]
+        if self.num_rows() >= PARALLEL_ROWS {
+            use rayon::prelude::*;
+            return self
+                .raw_cells()
+                .par_iter()
+                .map(|row| convert(row))
+                .collect();
+        }
+        self.raw_cells().iter().map(|row| convert(row)).collect()
+    }
+}
+
+/// A row as a JSON object, whose keys are shared with the other rows of its partition
+///
+/// This is created by [`Partition::json_objects_shared`], and serializes as an object
+/// like `{"ID": 1, "NAME": "Henry"}`. If there are duplicate column names, the last one wins
+/// in [`JsonObject::into_value`] but all of them are serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonObject {
+    keys: Arc<[String]>,
+    values: Vec<serde_json::Value>,
+}
+
+impl JsonObject {
+    /// The column names, in order
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// The values, in the same order as the keys
+    pub fn values(&self) -> &[serde_json::Value] {
+        &self.values
+    }
+
+    /// Get the value of a column by name (case-sensitive)
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.keys
+            .iter()
+            .position(|name| name == key)
+            .map(|ix| &self.values[ix])
+    }
+
+    /// Iterate over the column names and values, in order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.keys.iter().map(String::as_str).zip(self.values.iter())
+    }
+
+    /// Convert into a `serde_json::Value`, copying the column names
+    pub fn into_value(self) -> serde_json::Value {
+        serde_json::Value::Object(self.keys.iter().cloned().zip(self.values).collect())
+    }
+}
+
+impl serde::Serialize for JsonObject {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A partition of a single NUMBER column named `IX` with the values `0..rows`
+    fn numbers(rows: usize) -> Partition {
+        let meta_data = serde_json::from_value(serde_json::json!({
+            "numRows": rows,
+            "rowType": [{
+                "name": "IX",
+                "database": "DB",
+                "schema": "SCHEMA",
+                "table": "TABLE",
+                "precision": 38,
+                "byteLength": null,
+                "type": "fixed",
+                "scale": 0,
+                "nullable": false
+            }],
+            "partitionInfo": [{}]
+        }))
+        .unwrap();
+        let data = (0..rows).map(|ix| vec![Some(ix.to_string())]).collect();
+        Partition::new(0, meta_data, Arc::new(data))
+    }
+
+    #[test]
+    fn to_value_has_columns_and_rows() -> SnowflakeResult<()> {
+        assert_eq!(
+            numbers(2).slice(1..).to_value()?,
+            serde_json::json!({"columns": ["IX"], "rows": [[1]]})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn slices_share_rows() -> SnowflakeResult<()> {
+        let partition = numbers(10);
+        let slice = partition.slice(2..5);
+        assert_eq!(slice.num_rows(), 3);
+        assert!(matches!(slice.cells()?[0][0], Cell::Int(2)));
+        assert!(Arc::ptr_eq(&slice.data, &partition.data));
+
+        let (left, right) = slice.split_at(1);
+        assert_eq!(left.num_rows(), 1);
+        assert_eq!(right.num_rows(), 2);
+        assert!(matches!(right.cells()?[0][0], Cell::Int(3)));
+        assert_eq!(right.slice(..=1).num_rows(), 2);
+        assert_eq!(partition.slice(..).num_rows(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn cells_ref_match_cells() -> SnowflakeResult<()> {
+        let partition = numbers(3);
+        let cells = partition.cells_ref()?;
+        assert_eq!(cells.len(), 3);
+        assert!(matches!(cells[2][..], [CellRef::Int(2)]));
+        assert!(matches!(cells[1][0].to_cell()?, Cell::Int(1)));
+        Ok(())
+    }
+
+    #[test]
+    fn rows_share_columns() -> SnowflakeResult<()> {
+        let rows = numbers(2).rows()?;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].columns()[0].name, "IX");
+        assert!(matches!(rows[1].cells(), [Cell::Int(1)]));
+        Ok(())
+    }
+
+    #[test]
+    fn large_partitions_keep_their_order() -> SnowflakeResult<()> {
+        let partition = numbers(10_000);
+        let cells = partition.cells()?;
+        assert_eq!(cells.len(), 10_000);
+        assert!(cells
+            .iter()
+            .enumerate()
+            .all(|(ix, row)| matches!(row[..], [Cell::Int(value)] if value == ix as i128)));
+        assert_eq!(
+            partition.json_objects()?[9_999],
+            serde_json::json!({"IX": 9_999})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn shared_json_objects() -> SnowflakeResult<()> {
+        let partition = numbers(3);
+        let objects = partition.json_objects_shared()?;
+        assert!(Arc::ptr_eq(&objects[0].keys, &objects[2].keys));
+        assert_eq!(objects[1].get("IX"), Some(&serde_json::json!(1)));
+        assert_eq!(objects[1].get("ix"), None);
+        assert_eq!(
+            serde_json::to_value(&objects).unwrap(),
+            serde_json::Value::Array(partition.json_objects()?)
+        );
+        assert_eq!(
+            objects[2].clone().into_value(),
+            serde_json::json!({"IX": 2})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_cells_fail_the_conversion() {
+        let mut data: StringTable = (0..3).map(|ix| vec![Some(ix.to_string())]).collect();
+        data[1][0] = Some("one".to_owned());
+        let partition = Partition::from_raw(numbers(0).meta_data.row_type, data);
+        assert!(matches!(
+            partition.cells(),
+            Err(crate::SnowflakeError::InvalidCell { ref value, .. }) if value == "one"
+        ));
+        assert!(partition.rows().is_err());
+        assert!(partition.json_objects().is_err());
+        assert!(partition.cell_table().is_err());
+        // The bad row can still be skipped
+        assert!(partition.slice(2..).rows().is_ok());
+    }
+
+    #[test]
+    fn number_mode_applies_to_every_conversion() -> SnowflakeResult<()> {
+        let price: ColumnType = serde_json::from_value(serde_json::json!({
+            "name": "PRICE", "type": "fixed", "precision": 10, "scale": 2
+        }))?;
+        let data = vec![vec![Some("1.10".to_owned())], vec![Some("2.00".to_owned())]];
+        let partition = Partition::from_raw(vec![price], data);
+        // The column has a scale, so whole numbers aren't Ints
+        assert_eq!(partition.cells()?[0][0], Cell::Float(1.1));
+        assert_eq!(partition.cells()?[1][0], Cell::Float(2.0));
+        let exact = Cell::Number {
+            mantissa: 110,
+            scale: 2,
+        };
+        let partition = partition.with_number_mode(NumberMode::Exact);
+        assert_eq!(partition.cells()?[0][0], exact);
+        assert_eq!(
+            partition.slice(1..).cells()?[0][0],
+            Cell::Number {
+                mantissa: 200,
+                scale: 2
+            }
+        );
+        assert_eq!(partition.json_table()?[0][0].to_string(), "1.10");
+        assert_eq!(
+            partition.lazy_rows().next().unwrap().get(0)?,
+            Some(exact.clone())
+        );
+        assert!(matches!(
+            partition.cells_ref()?[0][0],
+            CellRef::Number {
+                mantissa: 110,
+                scale: 2
+            }
+        ));
+        assert_eq!(partition.cell_table()?.get(0, 0), Some(&exact));
+        Ok(())
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn timestamp_zone_applies_to_every_conversion() -> SnowflakeResult<()> {
+        let created: ColumnType = serde_json::from_value(serde_json::json!({
+            "name": "CREATED", "type": "timestamp_ltz", "scale": 9
+        }))?;
+        let data = vec![vec![Some("1672534861.000000000".to_owned())]];
+        let partition =
+            Partition::from_raw(vec![created], data).with_timestamp_zone(TimestampZone::Utc);
+        let utc = "2023-01-01 01:01:01 +00:00";
+        assert_eq!(partition.cells()?[0][0].to_string(), utc);
+        assert_eq!(partition.slice(..).rows()?[0][0].to_string(), utc);
+        assert_eq!(
+            partition
+                .lazy_rows()
+                .next()
+                .unwrap()
+                .get(0)?
+                .unwrap()
+                .to_string(),
+            utc
+        );
+        assert_eq!(partition.cells_ref()?[0][0].to_cell()?.to_string(), utc);
+        assert_eq!(
+            partition.cell_table()?.rows().next().unwrap()[0].to_string(),
+            utc
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn slices_out_of_bounds_panic() {
+        numbers(3).slice(2..4);
     }
 }
@@ -0,0 +1,265 @@
+use crate::errors::PolicyViolation;
+
+/// The statements a read-only policy allows
+const READ_ONLY_KEYWORDS: &[&str] = &[
+    "SELECT", "WITH", "SHOW", "DESCRIBE", "DESC", "EXPLAIN", "LIST",
+];
+
+/// Client-side guardrails for which statements a client may send
+///
+/// Attach one with [`SnowflakeClient::with_statement_policy`](crate::SnowflakeClient::with_statement_policy).
+/// Statements are matched by their leading keyword (after comments and parentheses), and
+/// every statement in a multi-statement string is checked. A statement is rejected with
+/// [`SnowflakeError::PolicyViolation`](crate::SnowflakeError::PolicyViolation) if its keyword
+/// is denied, or if there is an allow list and its keyword isn't on it.
+///
+/// This is defense in depth, not a substitute for Snowflake's own privileges: grant the
+/// client's role only what it needs, too.
+#[derive(Debug, Clone, Default)]
+pub struct StatementPolicy {
+    allow: Option<Vec<String>>,
+    deny: Vec<String>,
+}
+
+impl StatementPolicy {
+    /// A policy that allows everything, which you can then restrict with
+    /// [`StatementPolicy::allow`] and [`StatementPolicy::deny`]
+    pub fn new() -> StatementPolicy {
+        StatementPolicy::default()
+    }
+
+    /// Only allow queries: `SELECT`, `WITH`, `SHOW`, `DESCRIBE`, `EXPLAIN`, and `LIST`
+    pub fn read_only() -> StatementPolicy {
+        READ_ONLY_KEYWORDS
+            .iter()
+            .fold(StatementPolicy::new(), |policy, keyword| {
+                policy.allow(keyword)
+            })
+    }
+
+    /// Allow statements starting with this keyword, e.g. `"SELECT"`
+    ///
+    /// Once anything is allowed, every other keyword is rejected.
+    pub fn allow(mut self, keyword: &str) -> StatementPolicy {
+        self.allow
+            .get_or_insert_with(Vec::new)
+            .push(keyword.to_ascii_uppercase());
+        self
+    }
+
+    /// Reject statements starting with this keyword, e.g. `"DROP"` or `"TRUNCATE"`
+    pub fn deny(mut self, keyword: &str) -> StatementPolicy {
+        self.deny.push(keyword.to_ascii_uppercase());
+        self
+    }
+
+    /// Check every statement in the SQL against the policy
+    pub(crate) fn check(&self, sql: &str) -> Result<(), PolicyViolation> {
+        for statement in split_statements(sql) {
+            let keyword = leading_keyword(statement);
+            let allowed = match &self.allow {
+                Some(allow) => allow.contains(&keyword),
+                None => true,
+            };
+            if !allowed || self.deny.contains(&keyword) {
+                return Err(PolicyViolation {
+                    keyword,
+                    statement: statement.trim().to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a request to one of Snowflake's other REST endpoints against the policy
+    ///
+    /// The request is treated like the statement it stands in for: reads like `SHOW`, `POST`
+    /// like `CREATE`, `PUT` and `PATCH` like `ALTER`, and `DELETE` like `DROP`. So a read-only
+    /// policy rejects every request that would change an object.
+    pub(crate) fn check_request(
+        &self,
+        method: &reqwest::Method,
+        path: &str,
+    ) -> Result<(), PolicyViolation> {
+        use reqwest::Method;
+        let keyword = match *method {
+            Method::GET | Method::HEAD => "SHOW",
+            Method::POST => "CREATE",
+            Method::PUT | Method::PATCH => "ALTER",
+            Method::DELETE => "DROP",
+            _ => method.as_str(),
+        };
+        let allowed = match &self.allow {
+            Some(allow) => allow.iter().any(|allowed| allowed == keyword),
+            None => true,
+        };
+        if !allowed || self.deny.iter().any(|denied| denied == keyword) {
+            return Err(PolicyViolation {
+                keyword: keyword.to_owned(),
+                statement: format!("{method} {path}"),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Split SQL on semicolons, except inside quotes, `$$` blocks, and comments
+fn split_statements(sql: &str) -> Vec<&str> {
+    let mut statements = vec![];
+    let mut start = 0;
+    scan_code(sql, |ix| {
+        if sql.as_bytes()[ix] == b';' {
+            statements.push(&sql[start..ix]);
+            start = ix + 1;
+        }
+    });
+    statements.push(&sql[start.min(sql.len())..]);
+    // Trailing semicolons and blank lines are not statements
+    statements.retain(|statement| !leading_keyword(statement).is_empty());
+    if statements.is_empty() {
+        statements.push(sql);
+    }
+    statements
+}
+
+/// Call `visit` with the position of each byte of SQL that is code, skipping quotes,
+/// `$$` blocks, and comments
+pub(crate) fn scan_code(sql: &str, mut visit: impl FnMut(usize)) {
+    let bytes = sql.as_bytes();
+    let mut ix = 0;
+    while ix < bytes.len() {
+        // Compare bytes, since `ix` may be in the middle of a multibyte character
+        let rest = &bytes[ix..];
+        let skip_to = |end: &str, from: usize| {
+            sql[ix + from..]
+                .find(end)
+                .map_or(sql.len(), |at| ix + from + at + end.len())
+        };
+        ix = match bytes[ix] {
+            // Strings escape with `\` or `''`, but identifiers only with `""`
+            b'\'' | b'"' => {
+                let quote = bytes[ix];
+                let mut end = ix + 1;
+                while end < bytes.len() {
+                    if bytes[end] == b'\\' && quote == b'\'' {
+                        end += 1;
+                    } else if bytes[end] == quote {
+                        if bytes.get(end + 1) != Some(&quote) {
+                            break;
+                        }
+                        end += 1;
+                    }
+                    end += 1;
+                }
+                end + 1
+            }
+            _ if rest.starts_with(b"$$") => skip_to("$$", 2),
+            _ if rest.starts_with(b"--") || rest.starts_with(b"//") => skip_to("\n", 2),
+            _ if rest.starts_with(b"/*") => skip_to("*/", 2),
+            _ => {
+                visit(ix);
+                ix + 1
+            }
+        };
+    }
+}
+
+/// The first keyword of a statement, uppercased, skipping comments and parentheses
+fn leading_keyword(statement: &str) -> String {
+    let mut rest = statement;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+        if rest.starts_with("--") || rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |at| &rest[at..]);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.find("*/").map_or("", |at| &comment[at + 2..]);
+        } else {
+            break;
+        }
+    }
+    rest.chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_allows_queries() {
+        let policy = StatementPolicy::read_only();
+        assert!(policy.check("select 1").is_ok());
+        assert!(policy.check("-- the users\n(SELECT * FROM users);").is_ok());
+        assert!(policy.check("SHOW TABLES").is_ok());
+        let violation = policy.check("DELETE FROM users").unwrap_err();
+        assert_eq!(violation.keyword, "DELETE");
+    }
+
+    #[test]
+    fn every_statement_is_checked() {
+        let policy = StatementPolicy::new().deny("drop");
+        assert!(policy.check("SELECT ';'; SELECT $$;$$ /* ; */").is_ok());
+        let violation = policy
+            .check("SELECT 1; /* sneaky */ DROP TABLE users")
+            .unwrap_err();
+        assert_eq!(violation.keyword, "DROP");
+        assert_eq!(violation.statement, "/* sneaky */ DROP TABLE users");
+    }
+
+    #[test]
+    fn backslashes_only_escape_strings() {
+        let policy = StatementPolicy::read_only();
+        let violation = policy
+            .check(r#"SELECT "a\"; DROP TABLE users; --""#)
+            .unwrap_err();
+        assert_eq!(violation.keyword, "DROP");
+        assert_eq!(violation.statement, "DROP TABLE users");
+        assert!(policy
+            .check(r#"SELECT 'a\'; DROP TABLE users; --'"#)
+            .is_ok());
+        assert!(policy
+            .check(r#"SELECT "a""; DROP TABLE users; --" FROM t"#)
+            .is_ok());
+        assert!(policy.check("SELECT 'it''s; DROP TABLE users'").is_ok());
+    }
+
+    #[test]
+    fn non_ascii_sql_is_split() {
+        let policy = StatementPolicy::new().deny("drop");
+        assert!(policy.check("SELECT 'café' AS café; -- é").is_ok());
+        assert!(policy
+            .check("SELECT 'naïve\\é'; SELECT $$ünïcödé;$$")
+            .is_ok());
+        assert!(policy
+            .check("SELECT 1 /* 日本; */ -- ✓;\nSELECT 'é'")
+            .is_ok());
+        let violation = policy
+            .check("SELECT \"é\"; /* ✓ */ DROP TABLE ünïcödé")
+            .unwrap_err();
+        assert_eq!(violation.keyword, "DROP");
+        assert_eq!(violation.statement, "/* ✓ */ DROP TABLE ünïcödé");
+    }
+
+    #[test]
+    fn read_only_rejects_rest_mutations() {
+        use reqwest::Method;
+        let policy = StatementPolicy::read_only();
+        assert!(policy
+            .check_request(&Method::GET, "/api/v2/warehouses")
+            .is_ok());
+        let violation = policy
+            .check_request(&Method::POST, "/api/v2/warehouses")
+            .unwrap_err();
+        assert_eq!(violation.keyword, "CREATE");
+        assert_eq!(violation.statement, "POST /api/v2/warehouses");
+        let policy = StatementPolicy::new().deny("drop");
+        assert!(policy
+            .check_request(&Method::POST, "/api/v2/databases")
+            .is_ok());
+        assert!(policy
+            .check_request(&Method::DELETE, "/api/v2/databases/scratch")
+            .is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+/// How often to check on a statement that Snowflake is running asynchronously
+///
+/// Snowflake answers a statement within about 45 seconds, and if it's still running,
+/// the client polls until it finishes. The default polls every 5 seconds with no deadline,
+/// which suits most interactive queries; use a backoff for long jobs so they don't poll
+/// thousands of times, or a short interval for statements that usually finish quickly.
+///
+/// Set one for a client with [`SnowflakeClient::with_poll_strategy`](crate::SnowflakeClient::with_poll_strategy)
+/// or for a single statement with [`Statement::with_poll_strategy`](crate::Statement::with_poll_strategy).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollStrategy {
+    initial_interval: Duration,
+    max_interval: Duration,
+    multiplier: f64,
+    deadline: Option<Duration>,
+}
+
+impl PollStrategy {
+    /// Poll at a fixed interval, with no deadline
+    pub fn fixed(interval: Duration) -> PollStrategy {
+        PollStrategy {
+            initial_interval: interval,
+            max_interval: interval,
+            multiplier: 1.0,
+            deadline: None,
+        }
+    }
+
+    /// Start polling at `initial_interval`, multiplying the wait by `multiplier` after each poll
+    /// until it reaches `max_interval`
+    pub fn exponential(
+        initial_interval: Duration,
+        max_interval: Duration,
+        multiplier: f64,
+    ) -> Self {
+        PollStrategy {
+            initial_interval,
+            max_interval: max_interval.max(initial_interval),
+            multiplier: multiplier.max(1.0),
+            deadline: None,
+        }
+    }
+
+    /// Give up on the statement this long after it was submitted
+    ///
+    /// When the deadline passes, the statement is cancelled and
+    /// [`SnowflakeError::PollDeadlineExceeded`](crate::SnowflakeError::PollDeadlineExceeded)
+    /// is returned. This is independent of the statement's server-side timeout.
+    pub fn with_deadline(mut self, deadline: Duration) -> PollStrategy {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The overall deadline, if there is one
+    pub fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    /// How long to wait before the poll after one that waited `previous`
+    /// (or the first poll, if `previous` is `None`)
+    pub(crate) fn next_interval(&self, previous: Option<Duration>) -> Duration {
+        match previous {
+            None => self.initial_interval,
+            Some(previous) => previous.mul_f64(self.multiplier).min(self.max_interval),
+        }
+    }
+}
+
+impl Default for PollStrategy {
+    fn default() -> Self {
+        PollStrategy::fixed(Duration::from_secs(5))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponential_backoff_is_capped() {
+        let poll = PollStrategy::exponential(Duration::from_secs(1), Duration::from_secs(60), 2.0);
+        let mut interval = None;
+        let intervals: Vec<_> = (0..8)
+            .map(|_| {
+                interval = Some(poll.next_interval(interval));
+                interval.unwrap().as_secs()
+            })
+            .collect();
+        assert_eq!(intervals, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        let fixed = PollStrategy::default();
+        assert_eq!(
+            fixed.next_interval(fixed.next_interval(None).into()),
+            Duration::from_secs(5)
+        );
+    }
+}
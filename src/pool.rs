@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use jwt_simple::algorithms::RS256KeyPair;
+
+use crate::errors::SnowflakeResult;
+use crate::jwt;
+use crate::options::ClientOptions;
+
+/// How long a cached token is reused; tokens are valid for 59 minutes
+const TOKEN_REUSE: Duration = Duration::from_secs(50 * 60);
+
+/// An HTTP connection pool and JWT cache that many clients can share
+///
+/// Without one, each client keeps its own connections, shared with its clones and statements,
+/// and every request signs a new token. Attach the same pool to several clients, even for
+/// different accounts, users, and keys, with
+/// [`SnowflakeClient::with_connection_pool`](crate::SnowflakeClient::with_connection_pool) (or
+/// let a [`SnowflakeRegistry`](crate::SnowflakeRegistry) do it), and they reuse connections to
+/// each account, and each token until shortly before it expires. Clones share the same pool.
+///
+/// The connection settings (like [`ClientOptions::resolve`] and [`ClientOptions::tcp_keepalive`])
+/// come from the options the pool is created with, not from the clients that use it.
+#[derive(Clone)]
+pub struct ConnectionPool {
+    http: reqwest::Client,
+    tokens: Arc<Mutex<HashMap<TokenKey, (String, Instant)>>>,
+}
+
+/// The account, the user, and the key's thumbprint
+type TokenKey = (String, String, String);
+
+impl ConnectionPool {
+    /// Create a pool with the connection settings of these options
+    pub fn new(options: &ClientOptions) -> SnowflakeResult<ConnectionPool> {
+        Ok(ConnectionPool {
+            http: http_client(options)?,
+            tokens: Arc::default(),
+        })
+    }
+
+    pub(crate) fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// A cached token for this account, user, and key, or a new one
+    ///
+    /// Set `fresh` after Snowflake rejected a token, to replace it.
+    pub(crate) fn token(
+        &self,
+        key_pair: &RS256KeyPair,
+        account: &str,
+        user: &str,
+        fresh: bool,
+    ) -> SnowflakeResult<String> {
+        let key = (
+            account.to_owned(),
+            user.to_owned(),
+            key_pair.public_key().sha256_thumbprint(),
+        );
+        if !fresh {
+            if let Some((token, issued)) = self.tokens.lock().unwrap().get(&key) {
+                if issued.elapsed() < TOKEN_REUSE {
+                    return Ok(token.clone());
+                }
+            }
+        }
+        let token = jwt::create_token(key_pair, account, user)?;
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|_, (_, issued)| issued.elapsed() < TOKEN_REUSE);
+        tokens.insert(key, (token.clone(), Instant::now()));
+        Ok(token)
+    }
+}
+
+impl std::fmt::Debug for ConnectionPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionPool")
+            .field("tokens", &self.tokens.lock().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Build an HTTP client with the connection settings of these options
+///
+/// Timeouts are set on each request instead, since they depend on the statement.
+pub(crate) fn http_client(options: &ClientOptions) -> SnowflakeResult<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    for (domain, address) in &options.resolve {
+        builder = builder.resolve(domain, *address);
+    }
+    if let Some(interval) = options.tcp_keepalive {
+        builder = builder.tcp_keepalive(interval);
+    }
+    #[cfg(feature = "http2")]
+    if let Some((interval, timeout)) = options.http2_keep_alive {
+        builder = builder
+            .http2_keep_alive_interval(interval)
+            .http2_keep_alive_timeout(timeout)
+            .http2_keep_alive_while_idle(true);
+    }
+    #[cfg(feature = "http2")]
+    if options.http2_adaptive_window {
+        builder = builder.http2_adaptive_window(true);
+    }
+    #[cfg(feature = "http2")]
+    if let Some(size) = options.http2_max_frame_size {
+        builder = builder.http2_max_frame_size(size);
+    }
+    if options.http1_only {
+        builder = builder.http1_only();
+    }
+    Ok(builder.build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_cached_per_key() -> SnowflakeResult<()> {
+        let pool = ConnectionPool::new(&ClientOptions::default())?;
+        let key = RS256KeyPair::generate(2048)?;
+        let other = RS256KeyPair::generate(2048)?;
+        let token = pool.token(&key, "ACCOUNT", "USER", false)?;
+        assert_eq!(pool.token(&key, "ACCOUNT", "USER", false)?, token);
+        assert_ne!(pool.token(&other, "ACCOUNT", "USER", false)?, token);
+        assert_ne!(pool.token(&key, "ACCOUNT", "OTHER", false)?, token);
+        // A rejected token is replaced, and the replacement is cached
+        std::thread::sleep(Duration::from_millis(1100));
+        let fresh = pool.token(&key, "ACCOUNT", "USER", true)?;
+        assert_ne!(fresh, token);
+        assert_eq!(pool.token(&key, "ACCOUNT", "USER", false)?, fresh);
+        Ok(())
+    }
+
+    #[test]
+    fn clients_reuse_their_http_client() -> SnowflakeResult<()> {
+        let client = crate::test_client();
+        let clone = client.clone();
+        assert!(std::ptr::eq(client.http()?, clone.http()?));
+        // Changing a clone's options builds it a new one, without touching the original's
+        let changed = clone.with_tcp_keepalive(Duration::from_secs(30));
+        assert!(!std::ptr::eq(client.http()?, changed.http()?));
+        assert!(std::ptr::eq(changed.http()?, changed.clone().http()?));
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// A rate limiter for statements, keyed by warehouse name
+///
+/// Attach one to a [`SnowflakeClient`](crate::SnowflakeClient) with
+/// [`SnowflakeClient::with_rate_limiter`](crate::SnowflakeClient::with_rate_limiter).
+/// Clones share the same budget, so you can attach the same limiter to several clients
+/// (e.g. for different users or roles) and they will be limited together whenever
+/// they use the same warehouse.
+///
+/// The limits only apply to submitting statements, not to fetching additional partitions.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    per_second: Option<u32>,
+    max_concurrent: Option<usize>,
+    warehouses: Arc<Mutex<HashMap<String, Arc<WarehouseLimiter>>>>,
+}
+
+#[derive(Debug)]
+struct WarehouseLimiter {
+    next_slot: tokio::sync::Mutex<Instant>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+/// Proof that a statement was admitted by a [`RateLimiter`]
+///
+/// The concurrency slot is released when this is dropped.
+#[derive(Debug)]
+pub(crate) struct RatePermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    /// Create a limiter with no limits, which you can then configure with
+    /// [`RateLimiter::per_second`] and [`RateLimiter::max_concurrent`]
+    pub fn new() -> RateLimiter {
+        RateLimiter {
+            per_second: None,
+            max_concurrent: None,
+            warehouses: Arc::default(),
+        }
+    }
+
+    /// Allow at most this many statements to start per second, per warehouse
+    ///
+    /// Statements are spaced evenly rather than admitted in bursts.
+    pub fn per_second(mut self, statements: u32) -> RateLimiter {
+        self.per_second = Some(statements.max(1));
+        self
+    }
+
+    /// Allow at most this many statements to be in flight at once, per warehouse
+    pub fn max_concurrent(mut self, statements: usize) -> RateLimiter {
+        self.max_concurrent = Some(statements.max(1));
+        self
+    }
+
+    /// Wait until a statement may be sent to the given warehouse
+    pub(crate) async fn acquire(&self, warehouse: &str) -> RatePermit {
+        let limiter = self
+            .warehouses
+            .lock()
+            .unwrap()
+            .entry(warehouse.to_owned())
+            .or_insert_with(|| {
+                Arc::new(WarehouseLimiter {
+                    next_slot: tokio::sync::Mutex::new(Instant::now()),
+                    concurrency: self.max_concurrent.map(|n| Arc::new(Semaphore::new(n))),
+                })
+            })
+            .clone();
+
+        let permit = match &limiter.concurrency {
+            // The semaphore is never closed, so this can't fail
+            Some(semaphore) => Some(semaphore.clone().acquire_owned().await.unwrap()),
+            None => None,
+        };
+
+        if let Some(per_second) = self.per_second {
+            let interval = Duration::from_secs(1) / per_second;
+            let mut next_slot = limiter.next_slot.lock().await;
+            let now = Instant::now();
+            if *next_slot > now {
+                tokio::time::sleep_until(*next_slot).await;
+            }
+            *next_slot = (*next_slot).max(now) + interval;
+        }
+
+        RatePermit { _permit: permit }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn limits_concurrency_per_warehouse() {
+        let limiter = RateLimiter::new().max_concurrent(1);
+        let first = limiter.acquire("WH").await;
+        // A different warehouse has its own budget
+        let _other = limiter.acquire("OTHER_WH").await;
+        let second = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("WH")).await;
+        assert!(second.is_err());
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("WH")).await;
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    async fn spaces_statements_per_second() {
+        let limiter = RateLimiter::new().per_second(20);
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire("WH").await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
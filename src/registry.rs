@@ -0,0 +1,204 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+
+use crate::metrics::{MetricsHook, PartitionFetch};
+use crate::pool::ConnectionPool;
+use crate::SnowflakeClient;
+
+/// Clients for many tenants, looked up by a tenant key, that share one [`ConnectionPool`]
+///
+/// Each tenant can have its own account, user, key, and options, but every client added
+/// to the registry sends its requests through the registry's pool, so connections to the same
+/// account and tokens for the same user are reused instead of duplicated per tenant. A metrics
+/// hook set on the registry is given to every client that doesn't have its own.
+///
+/// Clones share the same clients, so a registry can be kept in application state.
+///
+/// ```rust,no_run
+/// # fn example(acme: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// use light_snowflake_connector::{ClientOptions, ConnectionPool, SnowflakeRegistry};
+///
+/// let registry = SnowflakeRegistry::new(ConnectionPool::new(&ClientOptions::default())?)
+///     .with_metrics_hook(|fetch| println!("{} bytes", fetch.bytes));
+/// registry.insert("acme".to_owned(), acme);
+/// if let Some(client) = registry.get("acme") {
+///     let statement = client.prepare("SELECT 1");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SnowflakeRegistry<K = String> {
+    pool: ConnectionPool,
+    metrics_hook: Option<MetricsHook>,
+    clients: Arc<RwLock<HashMap<K, SnowflakeClient>>>,
+}
+
+impl<K: Eq + Hash> SnowflakeRegistry<K> {
+    /// Create an empty registry whose clients share this pool
+    pub fn new(pool: ConnectionPool) -> SnowflakeRegistry<K> {
+        SnowflakeRegistry {
+            pool,
+            metrics_hook: None,
+            clients: Arc::default(),
+        }
+    }
+
+    /// Call this hook for every partition fetched by clients that don't have their own
+    ///
+    /// This only applies to clients inserted afterwards.
+    pub fn with_metrics_hook(
+        mut self,
+        hook: impl Fn(&PartitionFetch) + Send + Sync + 'static,
+    ) -> SnowflakeRegistry<K> {
+        self.metrics_hook = Some(MetricsHook::new(hook));
+        self
+    }
+
+    /// The pool shared by every client in the registry
+    pub fn pool(&self) -> &ConnectionPool {
+        &self.pool
+    }
+
+    /// Add or replace the client of a tenant, returning the client it replaced
+    ///
+    /// The client is switched to the registry's pool and given its metrics hook (unless it
+    /// has one), so use the copy from [`SnowflakeRegistry::get`] rather than the original.
+    pub fn insert(&self, tenant: K, mut client: SnowflakeClient) -> Option<SnowflakeClient> {
+        let options = client.options_mut();
+        options.connection_pool = Some(self.pool.clone());
+        if options.metrics_hook.is_none() {
+            options.metrics_hook = self.metrics_hook.clone();
+        }
+        self.clients.write().unwrap().insert(tenant, client)
+    }
+
+    /// The client of a tenant, if it was added
+    pub fn get<Q>(&self, tenant: &Q) -> Option<SnowflakeClient>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.clients.read().unwrap().get(tenant).cloned()
+    }
+
+    /// Remove the client of a tenant, returning it
+    pub fn remove<Q>(&self, tenant: &Q) -> Option<SnowflakeClient>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.clients.write().unwrap().remove(tenant)
+    }
+
+    /// Whether a tenant has a client
+    pub fn contains<Q>(&self, tenant: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.clients.read().unwrap().contains_key(tenant)
+    }
+
+    /// Get the number of tenants
+    pub fn len(&self) -> usize {
+        self.clients.read().unwrap().len()
+    }
+
+    /// Whether there are no tenants
+    pub fn is_empty(&self) -> bool {
+        self.clients.read().unwrap().is_empty()
+    }
+
+    /// The keys of every tenant, in no particular order
+    pub fn tenants(&self) -> Vec<K>
+    where
+        K: Clone,
+    {
+        self.clients.read().unwrap().keys().cloned().collect()
+    }
+}
+
+impl<K> Clone for SnowflakeRegistry<K> {
+    fn clone(&self) -> Self {
+        SnowflakeRegistry {
+            pool: self.pool.clone(),
+            metrics_hook: self.metrics_hook.clone(),
+            clients: self.clients.clone(),
+        }
+    }
+}
+
+impl<K> std::fmt::Debug for SnowflakeRegistry<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnowflakeRegistry")
+            .field("pool", &self.pool)
+            .field("metrics_hook", &self.metrics_hook)
+            .field("tenants", &self.clients.read().unwrap().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::SnowflakeResult;
+    use crate::options::ClientOptions;
+
+    use super::*;
+
+    fn client(account: &str) -> SnowflakeClient {
+        SnowflakeClient::new(crate::test_client().key_pair().clone(), account, "USER")
+    }
+
+    #[test]
+    fn clients_share_the_pool() -> SnowflakeResult<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static SHARED: AtomicUsize = AtomicUsize::new(0);
+        static OWN: AtomicUsize = AtomicUsize::new(0);
+        let registry = SnowflakeRegistry::new(ConnectionPool::new(&ClientOptions::default())?)
+            .with_metrics_hook(|_| {
+                SHARED.fetch_add(1, Ordering::SeqCst);
+            });
+        assert!(registry.is_empty());
+        registry.insert("acme".to_owned(), client("ACME"));
+        let globex = client("GLOBEX").with_metrics_hook(|_| {
+            OWN.fetch_add(1, Ordering::SeqCst);
+        });
+        assert!(registry
+            .clone()
+            .insert("globex".to_owned(), globex)
+            .is_none());
+
+        let fetch = PartitionFetch {
+            query_id: "01b2c3d4".into(),
+            index: 0,
+            bytes: 0,
+            rows: 0,
+            duration: Default::default(),
+        };
+        let record = |tenant: &str| {
+            let client = registry.get(tenant).unwrap();
+            assert!(client.options().connection_pool.is_some());
+            client
+                .options()
+                .metrics_hook
+                .clone()
+                .unwrap()
+                .record(&fetch);
+        };
+        record("acme");
+        record("globex");
+        assert_eq!(SHARED.load(Ordering::SeqCst), 1);
+        assert_eq!(OWN.load(Ordering::SeqCst), 1);
+        assert!(registry.get("initech").is_none());
+
+        let mut tenants = registry.tenants();
+        tenants.sort();
+        assert_eq!(tenants, ["acme", "globex"]);
+        assert_eq!(registry.remove("acme").unwrap().account(), "ACME");
+        assert!(!registry.contains("acme"));
+        assert_eq!(registry.len(), 1);
+        Ok(())
+    }
+}
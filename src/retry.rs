@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use crate::errors::SnowflakeError;
+
+/// Phrases in Snowflake's error messages while a warehouse is resuming or provisioning
+const TRANSIENT_WAREHOUSE_PHRASES: &[&str] =
+    &["resuming", "being resumed", "provisioning", "starting"];
+
+/// Retry statements that fail because their warehouse is still starting up
+///
+/// Attach one with [`SnowflakeClient::with_warehouse_retry`](crate::SnowflakeClient::with_warehouse_retry).
+/// An error is considered transient if its code was added with [`WarehouseRetry::code`],
+/// or if its message says the warehouse is resuming, provisioning, or starting.
+/// Retries wait `backoff`, then twice that, and so on, and give up after `max_attempts`
+/// attempts in total, returning the last error.
+#[derive(Debug, Clone)]
+pub struct WarehouseRetry {
+    max_attempts: u32,
+    backoff: Duration,
+    codes: Vec<String>,
+}
+
+impl WarehouseRetry {
+    /// Make up to 4 attempts, waiting 1, 2, then 4 seconds between them
+    pub fn new() -> WarehouseRetry {
+        WarehouseRetry {
+            max_attempts: 4,
+            backoff: Duration::from_secs(1),
+            codes: vec![],
+        }
+    }
+
+    /// Make at most this many attempts in total, including the first
+    pub fn max_attempts(mut self, attempts: u32) -> WarehouseRetry {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Wait this long before the first retry, doubling for each one after
+    pub fn backoff(mut self, backoff: Duration) -> WarehouseRetry {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Also treat errors with this Snowflake error code as transient, e.g. `"000625"`
+    pub fn code(mut self, code: &str) -> WarehouseRetry {
+        self.codes.push(code.to_owned());
+        self
+    }
+
+    /// How long to wait before the next attempt, or `None` to give up
+    ///
+    /// `attempt` counts from 1 for the attempt that just failed.
+    pub(crate) fn delay(&self, attempt: u32, error: &SnowflakeError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_transient(error) {
+            return None;
+        }
+        Some(self.backoff * 2u32.saturating_pow(attempt - 1))
+    }
+
+    fn is_transient(&self, error: &SnowflakeError) -> bool {
+        let SnowflakeError::ServerError { code, message } = error else {
+            return false;
+        };
+        let message = message.to_ascii_lowercase();
+        self.codes.contains(code)
+            || message.contains("warehouse")
+                && TRANSIENT_WAREHOUSE_PHRASES
+                    .iter()
+                    .any(|phrase| message.contains(phrase))
+    }
+}
+
+impl Default for WarehouseRetry {
+    fn default() -> Self {
+        WarehouseRetry::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error(code: &str, message: &str) -> SnowflakeError {
+        SnowflakeError::ServerError {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn retries_resuming_warehouses_with_backoff() {
+        let retry = WarehouseRetry::new().max_attempts(3).code("000625");
+        let resuming = server_error("000000", "Warehouse 'WH' is being resumed.");
+        assert_eq!(retry.delay(1, &resuming), Some(Duration::from_secs(1)));
+        assert_eq!(retry.delay(2, &resuming), Some(Duration::from_secs(2)));
+        assert_eq!(retry.delay(3, &resuming), None);
+        assert!(retry.delay(1, &server_error("000625", "")).is_some());
+        assert!(retry
+            .delay(1, &server_error("002003", "Table 'T' does not exist."))
+            .is_none());
+    }
+}
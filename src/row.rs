@@ -0,0 +1,251 @@
+use std::ops::Index;
+use std::sync::Arc;
+
+use crate::cells::{Cell, FromCell};
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::statement::ColumnType;
+
+/// A single row of a result set, along with the columns it belongs to
+///
+/// The column metadata is shared between all the rows of a response,
+/// so rows are self-describing without copying it for every row.
+/// That makes them convenient to pass across tasks and channels.
+///
+/// Small rows can be destructured into tuples of up to 12 [`FromCell`] types, by position,
+/// without defining a struct. This fails if the row doesn't have exactly as many cells as
+/// the tuple, or if a cell can't be converted:
+///
+/// ```rust
+/// # fn example(row: light_snowflake_connector::Row) -> light_snowflake_connector::SnowflakeResult<()> {
+/// let (id, name, score): (i64, String, Option<f64>) = row.try_into()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Row {
+    columns: Arc<[ColumnType]>,
+    cells: Vec<Cell>,
+}
+
+impl Row {
+    pub(crate) fn new(columns: Arc<[ColumnType]>, cells: Vec<Cell>) -> Row {
+        Row { columns, cells }
+    }
+
+    /// The columns of this row, in order
+    pub fn columns(&self) -> &[ColumnType] {
+        &self.columns
+    }
+
+    /// The cells of this row, in the same order as the columns
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Discard the column metadata and take the cells
+    pub fn into_cells(self) -> Vec<Cell> {
+        self.cells
+    }
+
+    /// Get the number of cells in this row
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether this row has no cells (which only happens for results without columns)
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get a cell by position or by column name, or `None` if there is no such column
+    ///
+    /// Column names are matched exactly if possible, and otherwise case-insensitively,
+    /// since Snowflake uppercases unquoted names.
+    pub fn get<I: ColumnIndex>(&self, index: I) -> Option<&Cell> {
+        index
+            .position(&self.columns)
+            .and_then(|ix| self.cells.get(ix))
+    }
+
+    /// Get a cell by position or by column name, converted to a [`FromCell`] type
+    ///
+    /// For example, `row.get_as::<Option<i64>, _>("AGE")`. This fails if there is no such
+    /// column, or if the cell can't be converted (use an `Option` to allow `NULL`).
+    pub fn get_as<T: FromCell, I: ColumnIndex + std::fmt::Display + Copy>(
+        &self,
+        index: I,
+    ) -> SnowflakeResult<T> {
+        let cell = self
+            .get(index)
+            .ok_or_else(|| SnowflakeError::UnexpectedResult(format!("no column {index} in row")))?;
+        Ok(T::from_cell(cell)?)
+    }
+
+    /// Iterate over pairs of columns and cells
+    pub fn iter(&self) -> impl Iterator<Item = (&ColumnType, &Cell)> {
+        self.columns.iter().zip(&self.cells)
+    }
+}
+
+/// A way to look up a column in a [`Row`]: either a position (`usize`) or a name (`&str`)
+///
+/// This is sealed, so it can't be implemented outside this crate.
+pub trait ColumnIndex: private::Sealed {
+    #[doc(hidden)]
+    fn position(&self, columns: &[ColumnType]) -> Option<usize>;
+}
+
+impl ColumnIndex for usize {
+    fn position(&self, columns: &[ColumnType]) -> Option<usize> {
+        (*self < columns.len()).then_some(*self)
+    }
+}
+
+impl ColumnIndex for &str {
+    fn position(&self, columns: &[ColumnType]) -> Option<usize> {
+        let names = || columns.iter().map(|column| column.name.as_str());
+        names()
+            .position(|name| name == *self)
+            .or_else(|| names().position(|name| name.eq_ignore_ascii_case(self)))
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for usize {}
+    impl Sealed for &str {}
+}
+
+impl<I: ColumnIndex + std::fmt::Display + Copy> Index<I> for Row {
+    type Output = Cell;
+
+    /// Get a cell by position or column name
+    ///
+    /// # Panics
+    /// Panics if there is no such column. Use [`Row::get`] to avoid this.
+    fn index(&self, index: I) -> &Cell {
+        self.get(index)
+            .unwrap_or_else(|| panic!("no column {index} in row"))
+    }
+}
+
+// Tuples of up to 12 `FromCell` types, converted by position
+macro_rules! impl_try_from_row {
+    ($len: literal; $($ty: ident $ix: tt),+) => {
+        impl<$($ty: FromCell),+> TryFrom<&Row> for ($($ty,)+) {
+            type Error = SnowflakeError;
+
+            fn try_from(row: &Row) -> SnowflakeResult<Self> {
+                if row.len() != $len {
+                    return Err(SnowflakeError::UnexpectedResult(format!(
+                        "expected a row of {} cells, but it has {}",
+                        $len,
+                        row.len()
+                    )));
+                }
+                Ok(($($ty::from_cell(&row.cells[$ix])?,)+))
+            }
+        }
+
+        impl<$($ty: FromCell),+> TryFrom<Row> for ($($ty,)+) {
+            type Error = SnowflakeError;
+
+            fn try_from(row: Row) -> SnowflakeResult<Self> {
+                Self::try_from(&row)
+            }
+        }
+    };
+}
+impl_try_from_row!(1; A 0);
+impl_try_from_row!(2; A 0, B 1);
+impl_try_from_row!(3; A 0, B 1, C 2);
+impl_try_from_row!(4; A 0, B 1, C 2, D 3);
+impl_try_from_row!(5; A 0, B 1, C 2, D 3, E 4);
+impl_try_from_row!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_try_from_row!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_try_from_row!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_try_from_row!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_try_from_row!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_try_from_row!(11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_try_from_row!(12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> Row {
+        let column = |name: &str| -> ColumnType {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "database": "DB",
+                "schema": "SCHEMA",
+                "table": "TABLE",
+                "type": "text",
+                "nullable": true
+            }))
+            .unwrap()
+        };
+        Row::new(
+            vec![column("ID"), column("NAME")].into(),
+            vec![Cell::Int(1), Cell::Varchar("Henry".into())],
+        )
+    }
+
+    #[test]
+    fn index_by_name_or_position() {
+        let row = row();
+        assert!(matches!(row["ID"], Cell::Int(1)));
+        assert!(matches!(row["name"], Cell::Varchar(ref x) if &**x == "Henry"));
+        assert!(matches!(row[1], Cell::Varchar(_)));
+        assert!(row.get("MISSING").is_none());
+        assert!(row.get(2).is_none());
+    }
+
+    #[test]
+    fn clones_share_text() -> SnowflakeResult<()> {
+        let row = row();
+        let clone = row.clone();
+        let text = |row: &Row| row.get_as::<Arc<str>, _>("NAME");
+        assert!(Arc::ptr_eq(&text(&row)?, &text(&clone)?));
+        Ok(())
+    }
+
+    #[test]
+    fn get_typed_cells() -> SnowflakeResult<()> {
+        let row = row();
+        assert_eq!(row.get_as::<u32, _>("ID")?, 1);
+        assert_eq!(row.get_as::<String, _>(1)?, "Henry");
+        assert!(matches!(
+            row.get_as::<i64, _>("NAME"),
+            Err(SnowflakeError::CellConversion(_))
+        ));
+        assert!(matches!(
+            row.get_as::<i64, _>("MISSING"),
+            Err(SnowflakeError::UnexpectedResult(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn rows_into_tuples() -> SnowflakeResult<()> {
+        let (id, name): (i64, Option<String>) = row().try_into()?;
+        assert_eq!((id, name.as_deref()), (1, Some("Henry")));
+        let (id, _) = <(u8, Cell)>::try_from(&row())?;
+        assert_eq!(id, 1);
+        assert!(matches!(
+            <(i64, i64)>::try_from(row()),
+            Err(SnowflakeError::CellConversion(_))
+        ));
+        assert!(matches!(
+            <(i64,)>::try_from(row()),
+            Err(SnowflakeError::UnexpectedResult(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "no column MISSING")]
+    fn index_missing_column_panics() {
+        let _ = &row()["MISSING"];
+    }
+}
@@ -0,0 +1,126 @@
+use crate::cells::Cell;
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::row::Row;
+
+/// A file in a stage, as listed by `LIST @stage`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageFile {
+    /// The path of the file, including the stage's location (e.g. `my_stage/data/0_0_0.csv.gz`)
+    pub name: String,
+    /// The size of the file in bytes
+    pub size: u64,
+    /// The MD5 hash of the file, in hex (for some encrypted stages, this is not a real MD5)
+    pub md5: String,
+    /// When the file was last modified, as a TIMESTAMP_TZ [`Cell`] in UTC
+    ///
+    /// This is a cell so that its type doesn't depend on the enabled features, so convert it
+    /// with [`FromCell`](crate::FromCell), like `DateTime::<Utc>::try_from(&file.last_modified)`.
+    /// Without `chrono` or `time`, it's Snowflake's text, like `Tue, 10 Jan 2023 12:00:00 GMT`.
+    pub last_modified: Cell,
+}
+
+/// Build the `LIST` statement, quoting the pattern as a string literal
+pub(crate) fn list_sql(stage: &str, pattern: Option<&str>) -> String {
+    match pattern {
+        Some(pattern) => format!(
+            "LIST {stage} PATTERN = '{}'",
+            pattern.replace('\\', "\\\\").replace('\'', "\\'")
+        ),
+        None => format!("LIST {stage}"),
+    }
+}
+
+impl TryFrom<Row> for StageFile {
+    type Error = SnowflakeError;
+
+    fn try_from(row: Row) -> SnowflakeResult<StageFile> {
+        let text = |column: &str| match row.get(column) {
+            Some(Cell::Varchar(value)) => Ok(value.to_string()),
+            _ => Err(SnowflakeError::UnexpectedResult(format!(
+                "LIST did not return a {column} column"
+            ))),
+        };
+        let size = match row.get("size") {
+            Some(Cell::Int(size)) => u64::try_from(*size).ok(),
+            _ => None,
+        };
+        let last_modified = text("last_modified")?;
+        #[cfg(feature = "chrono")]
+        let last_modified = chrono::DateTime::parse_from_rfc2822(&last_modified)
+            .ok()
+            .map(Cell::TimestampTz);
+        #[cfg(all(feature = "time", not(feature = "chrono")))]
+        let last_modified = {
+            use time::format_description::well_known::Rfc2822;
+            time::OffsetDateTime::parse(&last_modified, &Rfc2822)
+                .ok()
+                .map(Cell::TimeTimestampTz)
+        };
+        #[cfg(not(any(feature = "chrono", feature = "time")))]
+        let last_modified = Some(Cell::Varchar(last_modified.into()));
+        Ok(StageFile {
+            name: text("name")?,
+            size: size.ok_or_else(|| {
+                SnowflakeError::UnexpectedResult("LIST returned an invalid size".into())
+            })?,
+            md5: text("md5")?,
+            last_modified: last_modified.ok_or_else(|| {
+                SnowflakeError::UnexpectedResult("LIST returned an invalid last_modified".into())
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_sql_quotes_the_pattern() {
+        assert_eq!(list_sql("@my_stage", None), "LIST @my_stage");
+        assert_eq!(
+            list_sql("@my_stage/data", Some(r".*\.csv'")),
+            r"LIST @my_stage/data PATTERN = '.*\\.csv\''"
+        );
+    }
+
+    #[test]
+    fn parse_stage_files() -> SnowflakeResult<()> {
+        let column = |name: &str, data_type: &str| -> crate::ColumnType {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "type": data_type,
+            }))
+            .unwrap()
+        };
+        let row = Row::new(
+            vec![
+                column("name", "text"),
+                column("size", "fixed"),
+                column("md5", "text"),
+                column("last_modified", "text"),
+            ]
+            .into(),
+            vec![
+                Cell::Varchar("my_stage/data.csv.gz".into()),
+                Cell::Int(1024),
+                Cell::Varchar("0cc175b9c0f1b6a831c399e269772661".into()),
+                Cell::Varchar("Tue, 10 Jan 2023 12:00:00 GMT".into()),
+            ],
+        );
+        let file = StageFile::try_from(row)?;
+        assert_eq!(file.name, "my_stage/data.csv.gz");
+        assert_eq!(file.size, 1024);
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            file.last_modified.as_timestamp().map(|at| at.timestamp()),
+            Some(1673352000)
+        );
+        #[cfg(feature = "time")]
+        assert_eq!(
+            time::OffsetDateTime::try_from(&file.last_modified).map(|at| at.unix_timestamp()),
+            Ok(1673352000)
+        );
+        Ok(())
+    }
+}
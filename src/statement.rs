@@ -1,25 +1,200 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 
-use futures::{StreamExt, TryStream, TryStreamExt};
+use futures::{Sink, SinkExt, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
 
-use crate::bindings::Binding;
-use crate::cells::{Cell, RawCell};
-use crate::errors::{SnowflakeError, SnowflakeResult, SnowflakeWireResult};
+use crate::audit::{self, AuditOutcome, AuditRecord};
+use crate::bindings::{Binding, ToBinding};
+use crate::cells::{Cell, FromCell, NumberMode, RawCell};
+use crate::errors::{
+    parse_data_response, parse_response, read_body, SchemaMismatch, SnowflakeError, SnowflakeResult,
+};
+use crate::metrics::{Latency, PartitionFetch, TransferStats};
 use crate::partition::{Partition, StringTable};
-use crate::{jwt, SnowflakeClient};
+use crate::poll::PollStrategy;
+use crate::row::Row;
+use crate::stream::{PartitionStream, ResultStream, RowStream};
+use crate::trace::TraceContext;
+use crate::transport::Transport;
+use crate::SnowflakeClient;
+
+/// The timeout for each HTTP request when the statement itself has no timeout
+const UNBOUNDED_CLIENT_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Whether fetching a partition failed because Snowflake no longer has the result
+fn is_expired_result(error: &SnowflakeError) -> bool {
+    match error {
+        SnowflakeError::ServerError { message, .. } => {
+            let message = message.to_ascii_lowercase();
+            message.contains("expired") || message.contains("result not found")
+        }
+        _ => false,
+    }
+}
+
+/// Uppercase an identifier the way Snowflake would, unless it is quoted
+///
+/// Quoted identifiers like `"my_db"` are case sensitive in Snowflake,
+/// so they are passed through verbatim, quotes included.
+pub(crate) fn normalize_identifier(name: &str) -> String {
+    if name.len() >= 2 && name.starts_with('"') && name.ends_with('"') {
+        name.to_owned()
+    } else {
+        name.to_ascii_uppercase()
+    }
+}
+
+/// Quote each part of a dotted object name like `db.schema."My Table"` as needed
+///
+/// Parts that are already quoted (with any quotes inside them doubled), or are valid unquoted
+/// identifiers, are left alone (so Snowflake still uppercases the latter). Anything else, like
+/// a name with spaces or dots, is wrapped in double quotes with embedded quotes doubled.
+pub(crate) fn quote_object_name(name: &str) -> String {
+    let mut parts = vec![];
+    let mut part = String::new();
+    let mut in_quotes = false;
+    for c in name.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '.' if !in_quotes => {
+                parts.push(std::mem::take(&mut part));
+                continue;
+            }
+            _ => {}
+        }
+        part.push(c);
+    }
+    parts.push(part);
+    parts
+        .iter()
+        .map(|part| quote_identifier(part))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Quote a single identifier (like a column name) if it needs to be
+///
+/// Unlike [`quote_object_name`], dots are treated as part of the identifier. An identifier is
+/// only left as it is if it's quoted with every quote inside doubled, like `"say ""hi"""`.
+pub(crate) fn quote_identifier(part: &str) -> String {
+    let is_quoted = part.len() >= 2
+        && part.starts_with('"')
+        && part.ends_with('"')
+        && !part[1..part.len() - 1].replace("\"\"", "").contains('"');
+    let mut chars = part.chars();
+    let is_plain = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_quoted || is_plain {
+        part.to_owned()
+    } else {
+        format!("\"{}\"", part.replace('"', "\"\""))
+    }
+}
+
+/// Wrap a Snowflake Scripting block in `EXECUTE IMMEDIATE`, so the SQL API runs it as one statement
+///
+/// Blocks that are already wrapped are left alone. The block is quoted with `$$`,
+/// unless it contains `$$` itself, in which case it becomes an escaped string literal.
+pub(crate) fn script_sql(block: &str) -> String {
+    let block = block.trim();
+    let is_wrapped = block
+        .get(..17)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("EXECUTE IMMEDIATE"));
+    if is_wrapped {
+        block.to_owned()
+    } else if block.contains("$$") {
+        format!(
+            "EXECUTE IMMEDIATE '{}'",
+            block.replace('\\', "\\\\").replace('\'', "\\'")
+        )
+    } else {
+        format!("EXECUTE IMMEDIATE $$\n{block}\n$$")
+    }
+}
+
+/// The name of the column that holds a scalar return value, for `CALL` and scripting blocks
+///
+/// Anonymous blocks return a column named `anonymous block`, and stored procedures return a
+/// column named after the procedure (uppercased, unless it is quoted).
+fn return_column(sql: &str) -> Option<String> {
+    let sql = sql.trim_start();
+    let keyword = |word: &str| {
+        sql.get(..word.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(word))
+    };
+    if keyword("EXECUTE IMMEDIATE") {
+        return Some("anonymous block".into());
+    }
+    if !keyword("CALL") || !sql[4..].starts_with(char::is_whitespace) {
+        return None;
+    }
+    let name = sql[4..].split('(').next()?.trim();
+    let name = quote_object_name(name);
+    let last = name.rsplit('.').next()?;
+    Some(
+        match last.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+            Some(quoted) => quoted.replace("\"\"", "\""),
+            None => last.to_ascii_uppercase(),
+        },
+    )
+}
 
 /// A builder for a prepared statement (created by SnowflakeClient)
 ///
-#[derive(Debug, Clone)]
+/// The `Debug` output redacts binding values and custom header values, since they may be sensitive.
+#[derive(Clone)]
 pub struct Statement {
-    host: String,
     wire: WireStatement,
     uuid: uuid::Uuid,
+    client_timeout: Option<Duration>,
+    partition_timeout: Option<Duration>,
+    number_mode: Option<NumberMode>,
+    expected_schema: Option<Vec<(String, RawCell)>>,
+    headers: reqwest::header::HeaderMap,
+    trace_context: Option<TraceContext>,
+    poll_strategy: Option<PollStrategy>,
+    requery_on_expiry: bool,
+    nullable: bool,
+    deadline: Option<Instant>,
+    /// The account that follow-up requests go to, once a request has failed over to it
+    serving_account: OnceLock<String>,
     config: SnowflakeClient,
 }
 
+impl std::fmt::Debug for Statement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Statement")
+            .field("sql", &self.wire.statement)
+            .field("request_id", &self.uuid)
+            .field("timeout", &self.wire.timeout)
+            .field("database", &self.wire.database)
+            .field("warehouse", &self.wire.warehouse)
+            .field("role", &self.wire.role)
+            .field(
+                "bindings",
+                &format_args!("<{} redacted>", self.wire.bindings.len()),
+            )
+            .field("client_timeout", &self.client_timeout)
+            .field("partition_timeout", &self.partition_timeout)
+            .field("number_mode", &self.number_mode)
+            .field("expected_schema", &self.expected_schema)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("trace_context", &self.trace_context)
+            .field("poll_strategy", &self.poll_strategy)
+            .field("requery_on_expiry", &self.requery_on_expiry)
+            .field("nullable", &self.nullable)
+            .field("deadline", &self.deadline)
+            .field("serving_account", &self.serving_account.get())
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
 impl Statement {
     /// Create a new statement from a SQL string and a SnowflakeClient
     ///
@@ -27,66 +202,343 @@ impl Statement {
     /// but the difference is merely ergonomic.
     pub fn new(sql: &str, config: &crate::SnowflakeClient) -> Statement {
         Statement {
-            host: format!(
-                "https://{}.snowflakecomputing.com",
-                config.account.to_ascii_lowercase(),
-            ),
             wire: WireStatement {
                 statement: sql.to_owned(),
                 timeout: Some(30),
-                database: config.database.to_ascii_uppercase(),
-                warehouse: config.warehouse.to_ascii_uppercase(),
-                role: config.role.as_ref().map(|x| x.to_ascii_uppercase()),
-                bindings: HashMap::new(),
+                database: config.database().map(normalize_identifier),
+                warehouse: config.warehouse().map(normalize_identifier),
+                role: config.role().map(normalize_identifier),
+                bindings: vec![],
+                parameters: HashMap::new(),
             },
             uuid: uuid::Uuid::new_v4(),
-            config: config.to_owned(),
+            client_timeout: None,
+            partition_timeout: None,
+            number_mode: None,
+            expected_schema: None,
+            headers: reqwest::header::HeaderMap::new(),
+            trace_context: None,
+            poll_strategy: None,
+            requery_on_expiry: false,
+            nullable: true,
+            deadline: None,
+            serving_account: OnceLock::new(),
+            config: config.clone(),
         }
     }
 
-    pub(crate) fn client(&self) -> SnowflakeResult<reqwest::Client> {
-        use reqwest::header::*;
-        let token = jwt::create_token(
-            &self.config.key_pair,
-            &self.config.account.to_ascii_uppercase(),
-            &self.config.user.to_ascii_uppercase(),
-        )?;
+    /// The statement to send, with a query tag from the current span if the client wants one,
+    /// and a timeout no later than the deadline, if there is one
+    fn wire(&self) -> std::borrow::Cow<'_, WireStatement> {
+        #[allow(unused_mut)]
+        let mut wire = std::borrow::Cow::Borrowed(&self.wire);
+        #[cfg(feature = "tracing")]
+        if self.config.options().query_tag_from_span
+            && !self.wire.parameters.contains_key("query_tag")
+        {
+            let span = tracing::Span::current();
+            if let Some(metadata) = span.metadata() {
+                let trace_context = self.transport().trace_context();
+                let trace_id = trace_context.as_ref().and_then(TraceContext::trace_id);
+                let tag = serde_json::json!({"trace_id": trace_id, "span": metadata.name()});
+                wire.to_mut()
+                    .parameters
+                    .insert("query_tag".into(), tag.to_string());
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            // Round up, since a timeout of 0 means no timeout at all
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let remaining = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+            let remaining = remaining.max(1);
+            if wire
+                .timeout
+                .is_none_or(|timeout| timeout == 0 || timeout > remaining)
+            {
+                wire.to_mut().timeout = Some(remaining);
+            }
+        }
+        wire
+    }
 
-        let mut headers = HeaderMap::with_capacity(5);
-        headers.append(CONTENT_TYPE, "application/json".parse()?);
-        headers.append(AUTHORIZATION, format!("Bearer {}", token).parse()?);
-        headers.append(
-            "X-Snowflake-Authorization-Token-Type",
-            "KEYPAIR_JWT".parse()?,
-        );
-        headers.append(ACCEPT, "application/json".parse()?);
-        headers.append(
-            USER_AGENT,
-            concat!(env!("CARGO_PKG_NAME"), '/', env!("CARGO_PKG_VERSION")).parse()?,
-        );
+    /// Run `work` until the statement's deadline, if it has one, dropping it when the
+    /// deadline passes
+    ///
+    /// If Snowflake has a handle for the `submission` by then, the statement is cancelled, so
+    /// it doesn't keep running (and using the warehouse) after the caller has given up on it.
+    /// `work` can set the handle once it learns it, like when Snowflake accepts a statement.
+    async fn until_deadline<T>(
+        &self,
+        submission: &Submission,
+        work: impl std::future::Future<Output = SnowflakeResult<T>>,
+    ) -> SnowflakeResult<T> {
+        let Some(deadline) = self.deadline else {
+            return work.await;
+        };
+        if Instant::now() >= deadline {
+            return Err(SnowflakeError::DeadlineExceeded {
+                query_id: submission.handle.get().cloned(),
+            });
+        }
+        match tokio::time::timeout_at(tokio::time::Instant::from_std(deadline), work).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.cancel(submission).await;
+                Err(SnowflakeError::DeadlineExceeded {
+                    query_id: submission.handle.get().cloned(),
+                })
+            }
+        }
+    }
 
-        Ok(reqwest::Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(
-                self.wire.timeout.unwrap_or(30) + 15,
-            ))
-            .build()?)
+    /// Send requests with this statement's headers, trace context, and timeout, to the account
+    /// serving it
+    pub(crate) fn transport(&self) -> Transport<'_> {
+        Transport {
+            client: &self.config,
+            headers: Some(&self.headers),
+            trace_context: self.trace_context.as_ref(),
+            timeout: self.client_timeout(),
+            serving_account: Some(&self.serving_account),
+        }
     }
 
-    async fn send(&self) -> Result<reqwest::Response, SnowflakeError> {
-        log::debug!(
-            "Sending statement: {}",
-            serde_json::to_string_pretty(&self.wire).unwrap()
-        );
-        Ok(self
-            .client()?
-            .post(format!(
-                "{}/api/v2/statements?nullable=true&requestId={}",
-                self.host, self.uuid
-            ))
-            .json(&self.wire)
-            .send()
-            .await?)
+    /// Like [`Statement::transport`], to the account serving the `submission`
+    fn submission_transport<'a>(&'a self, submission: &'a Submission) -> Transport<'a> {
+        Transport {
+            serving_account: Some(&submission.serving_account),
+            ..self.transport()
+        }
+    }
+
+    /// Start tracking a submission of this statement, to the account currently serving it
+    ///
+    /// Pass the `handle` when Snowflake already has one, like for fetching partitions.
+    fn submission(&self, handle: Option<&str>) -> Submission {
+        Submission {
+            handle: handle
+                .map(str::to_owned)
+                .map(OnceLock::from)
+                .unwrap_or_default(),
+            serving_account: self.serving_account.clone(),
+        }
+    }
+
+    /// A copy of this statement that sends its follow-up requests to the account that served
+    /// the `submission`
+    fn served_by(&self, submission: Submission) -> Statement {
+        let mut statement = self.clone();
+        statement.serving_account = submission.serving_account;
+        statement
+    }
+
+    /// The timeout for each HTTP request
+    ///
+    /// Snowflake answers within about 45 seconds either way, with a 202 if the statement
+    /// is still running, so unbounded statements don't need unbounded requests.
+    fn client_timeout(&self) -> Duration {
+        if let Some(timeout) = self.client_timeout {
+            return timeout;
+        }
+        match self.wire.timeout {
+            Some(0) | None => UNBOUNDED_CLIENT_TIMEOUT,
+            Some(timeout) => Duration::from_secs(timeout + 15),
+        }
+    }
+
+    /// The timeout for each request that fetches a partition after the first
+    ///
+    /// This is the statement's partition timeout, or else the client's, or else the same
+    /// timeout as the statement's other requests.
+    fn partition_timeout(&self) -> Duration {
+        self.partition_timeout
+            .or(self.config.options().partition_timeout)
+            .unwrap_or_else(|| self.client_timeout())
+    }
+
+    /// How NUMBER cells of the result are parsed: the statement's mode, or else the client's
+    fn number_mode(&self) -> NumberMode {
+        self.number_mode
+            .unwrap_or(self.config.options().number_mode)
+    }
+
+    /// Send the statement and parse Snowflake's response, retrying transient warehouse errors
+    /// if the client has a [`WarehouseRetry`](crate::WarehouseRetry)
+    ///
+    /// Also returns the size of the response, how many times the statement was resubmitted,
+    /// and how long it took (without `createdOn`, which is part of `T`).
+    async fn execute<T: serde::de::DeserializeOwned>(&self) -> SnowflakeResult<Executed<T>> {
+        let submission = self.submission(None);
+        let executed = self
+            .until_deadline(&submission, self.execute_with_retries(&submission))
+            .await?;
+        Ok(Executed {
+            submission,
+            ..executed
+        })
+    }
+
+    async fn execute_with_retries<T: serde::de::DeserializeOwned>(
+        &self,
+        submission: &Submission,
+    ) -> SnowflakeResult<Executed<T>> {
+        let submitted = Instant::now();
+        let mut attempt = 1;
+        loop {
+            let mut time_to_first_byte = Duration::ZERO;
+            let result = async {
+                let response = self.send(attempt > 1, submission).await?;
+                time_to_first_byte = submitted.elapsed();
+                parse_response::<T>(response).await
+            }
+            .await;
+            let delay = match (&self.config.options().warehouse_retry, &result) {
+                (Some(retry), Err(error)) => retry.delay(attempt, error),
+                _ => None,
+            };
+            let Some(delay) = delay else {
+                return result.map(|(value, bytes)| Executed {
+                    value,
+                    bytes,
+                    retries: attempt - 1,
+                    submission: Submission::default(),
+                    latency: Latency {
+                        created_on: None,
+                        time_to_first_byte,
+                        total: submitted.elapsed(),
+                    },
+                });
+            };
+            log::warn!(
+                "Warehouse {} is not ready, retrying in {:?}",
+                self.wire.warehouse.as_deref().unwrap_or("(default)"),
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Submit the statement and wait for it to finish
+    ///
+    /// Set `retry` when resubmitting the same request id, so Snowflake accepts it again.
+    /// Once Snowflake hands the statement off to async execution, the submission's handle is
+    /// set, and if a request fails over, so is the account serving it.
+    async fn send(
+        &self,
+        retry: bool,
+        submission: &Submission,
+    ) -> Result<reqwest::Response, SnowflakeError> {
+        if let Some(policy) = &self.config.options().statement_policy {
+            policy.check(&self.wire.statement)?;
+        }
+        // Hold the permit until Snowflake responds, so it counts against concurrency
+        let _permit = match &self.config.options().rate_limiter {
+            // Statements without a warehouse share the limit of the user's default warehouse
+            Some(limiter) => {
+                let warehouse = self.wire.warehouse.as_deref().unwrap_or_default();
+                Some(limiter.acquire(warehouse).await)
+            }
+            None => None,
+        };
+        // Serialize once, straight into the buffer that is sent. Bytes are reference
+        // counted, so failing over or refreshing the token doesn't copy the body again,
+        // which matters for statements with many megabytes of bindings.
+        let body = bytes::Bytes::from(self.to_wire_json(false)?);
+        log::debug!("Sending statement: {}", String::from_utf8_lossy(&body));
+        let (body, encoding) = match self.config.options().gzip_requests {
+            Some(min_size) if body.len() >= min_size => (gzip(&body)?, Some("gzip")),
+            _ => (body, None),
+        };
+        let submitted = Instant::now();
+        let mut response = self
+            .submission_transport(submission)
+            .request(|client, host| {
+                let request = client
+                    .post(format!(
+                        "{}/api/v2/statements?nullable={}&requestId={}{}",
+                        host,
+                        self.nullable,
+                        self.uuid,
+                        if retry { "&retry=true" } else { "" }
+                    ))
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body.clone());
+                match encoding {
+                    Some(encoding) => request.header(reqwest::header::CONTENT_ENCODING, encoding),
+                    None => request,
+                }
+            })
+            .await?;
+
+        // Long running statements are handed off to Snowflake's async execution,
+        // so wait for them to finish, minting a new token each time in case it expires.
+        let poll = self
+            .poll_strategy
+            .as_ref()
+            .or(self.config.options().poll_strategy.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        let mut interval = None;
+        while response.status() == reqwest::StatusCode::ACCEPTED {
+            let pending = response.json::<WireStatementStatus>().await?;
+            let Some(status_url) = pending.statement_status_url else {
+                return Err(SnowflakeError::ServerError {
+                    code: pending.code,
+                    message: pending.message,
+                });
+            };
+            let _ = submission.handle.set(pending.statement_handle.clone());
+            let next = poll.next_interval(interval);
+            interval = Some(next);
+            // Check once more at the polling deadline, and only give up after it has passed.
+            // The overall deadline is enforced by `until_deadline`, which cancels the statement.
+            let mut wait = next;
+            if let Some(deadline) = poll.deadline() {
+                let Some(remaining) = deadline.checked_sub(submitted.elapsed()) else {
+                    self.cancel(submission).await;
+                    return Err(SnowflakeError::PollDeadlineExceeded {
+                        query_id: pending.statement_handle,
+                        deadline,
+                    });
+                };
+                wait = wait.min(remaining);
+            }
+            log::debug!("Statement still running, polling: {}", status_url);
+            tokio::time::sleep(wait).await;
+            response = self
+                .submission_transport(submission)
+                .request(|client, host| client.get(format!("{}{}", host, status_url)))
+                .await?;
+        }
+        Ok(response)
+    }
+
+    /// Ask Snowflake to cancel a submitted statement, logging rather than returning failures
+    ///
+    /// This does nothing until Snowflake has given the submission a handle.
+    async fn cancel(&self, submission: &Submission) {
+        let Some(statement_handle) = submission.handle.get() else {
+            return;
+        };
+        let result = self
+            .submission_transport(submission)
+            .request(|client, host| {
+                client.post(format!(
+                    "{}/api/v2/statements/{}/cancel",
+                    host, statement_handle
+                ))
+            })
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => log::warn!(
+                "Failed to cancel statement {}: {}",
+                statement_handle,
+                response.status()
+            ),
+            Err(error) => log::warn!("Failed to cancel statement {}: {}", statement_handle, error),
+        }
     }
 
     /// Execute SQL that returns a result set
@@ -95,33 +547,212 @@ impl Statement {
     /// but the first partition is buffered immediately.
     ///
     /// For a single partition, consider using [`QueryResponse::only_partition`].
+    ///
+    /// This works for any SQL, so a generic runner can use it for everything: DDL returns its
+    /// status text as a row, and a statement without a result set returns no columns and no rows.
     pub async fn query(&self) -> Result<QueryResponse, SnowflakeError> {
-        Ok(self
-            .send()
-            .await?
-            .json::<SnowflakeWireResult<WireQueryResponse>>()
-            .await?
-            .into_result()?
-            .hydrate(self.clone()))
+        let started = (SystemTime::now(), Instant::now());
+        let result = async {
+            let executed = self.execute::<WireQueryResponse>().await?;
+            let rows = executed.value.data.len();
+            let latency = executed.latency.with_created_on(executed.value.created_on);
+            let statement = self.served_by(executed.submission);
+            let mut response = executed.value.hydrate(statement, started.1);
+            response.latency = latency;
+            response.record_fetch(0, executed.bytes, rows, started.1.elapsed());
+            response.transfer.lock().unwrap().retries = executed.retries;
+            if let Some(expected) = &self.expected_schema {
+                let expected: Vec<_> = expected.iter().map(|(n, t)| (n.as_str(), *t)).collect();
+                response.expect_schema(&expected)?;
+            }
+            Ok(response)
+        }
+        .await;
+        let query_id = result.as_ref().ok().map(|response| response.query_id());
+        self.audit(started, query_id, &result);
+        result
+    }
+
+    /// Execute SQL that returns a result set, with a deadline for the whole query, including
+    /// fetching every partition
+    ///
+    /// This is a shorthand for [`Statement::with_deadline`] followed by [`Statement::query`].
+    pub async fn query_with_deadline(&self, deadline: Instant) -> SnowflakeResult<QueryResponse> {
+        self.clone().with_deadline(deadline).query().await
+    }
+
+    /// Execute SQL that returns a single value, like `SELECT COUNT(*) FROM users`,
+    /// and convert it to a [`FromCell`] type
+    ///
+    /// This takes the first column of the first row. If there are no rows, the value is `NULL`,
+    /// so use an `Option` to allow for that.
+    pub async fn fetch_scalar<T: FromCell>(&self) -> SnowflakeResult<T> {
+        let response = self.query().await?;
+        let partition = response.partition(0).await?;
+        let cell = match partition {
+            Some(partition) => partition
+                .slice(..partition.num_rows().min(1))
+                .cells()?
+                .pop(),
+            None => None,
+        }
+        .and_then(|row| row.into_iter().next())
+        .unwrap_or(Cell::Null);
+        Ok(T::from_cell(&cell)?)
+    }
+
+    /// Execute a `CALL` to a stored procedure or a scripting block (see
+    /// [`SnowflakeClient::prepare_block`](crate::SnowflakeClient::prepare_block)),
+    /// telling apart a scalar return value from a returned table
+    ///
+    /// Snowflake returns a scalar as a single row with a single column, named after the
+    /// procedure or `anonymous block`, so that shape is a [`ScriptOutput::Value`] and anything
+    /// else is a [`ScriptOutput::Table`]. (A procedure that returns a table with one column
+    /// named after itself looks like a value, so avoid that.)
+    pub async fn call(&self) -> SnowflakeResult<ScriptOutput> {
+        let response = self.query().await?;
+        let is_value = match (return_column(&self.wire.statement), response.column_types()) {
+            (Some(expected), [column]) => {
+                column.name.eq_ignore_ascii_case(&expected) && response.num_rows() <= 1
+            }
+            _ => false,
+        };
+        if !is_value {
+            return Ok(ScriptOutput::Table(Box::new(response)));
+        }
+        let cell = response
+            .only_partition()?
+            .cells()?
+            .pop()
+            .and_then(|row| row.into_iter().next())
+            .unwrap_or(Cell::Null);
+        Ok(ScriptOutput::Value(cell))
     }
 
     /// Execute SQL that does not return a result set
     ///
-    /// This is useful for DML statements like `INSERT`, `UPDATE`, and `DELETE`
+    /// This is useful for DML statements like `INSERT`, `UPDATE`, and `DELETE`.
+    /// DDL works too, and reports no changed rows.
     pub async fn manipulate(&self) -> Result<Changes, SnowflakeError> {
-        let dml_reslt = self
-            .send()
-            .await?
-            .json::<SnowflakeWireResult<WireDMLResult>>()
-            .await?
-            .into_result()?;
-        Ok(Changes {
-            message: dml_reslt.message,
-            rows_inserted: dml_reslt.stats.rows_inserted,
-            rows_deleted: dml_reslt.stats.rows_deleted,
-            rows_updated: dml_reslt.stats.rows_updated,
-            duplicates: dml_reslt.stats.duplicates,
+        let started = (SystemTime::now(), Instant::now());
+        let result = self
+            .execute::<WireDMLResult>()
+            .await
+            .map(|executed| (executed.value, executed.latency));
+        let query_id = result
+            .as_ref()
+            .ok()
+            .and_then(|(dml_result, _)| dml_result.statement_handle.as_deref());
+        self.audit(started, query_id, &result);
+        let (dml_result, latency) = result?;
+        let mut changes = Changes::new(dml_result.message, dml_result.stats);
+        changes.latency = latency.with_created_on(dml_result.created_on);
+        Ok(changes)
+    }
+
+    /// Execute a multi-statement request, returning the handles of its statements in order,
+    /// and the submission, for the account serving them
+    ///
+    /// Set `MULTI_STATEMENT_COUNT` with [`Statement::with_parameter`] first.
+    async fn execute_multi(&self) -> SnowflakeResult<(Vec<String>, Submission)> {
+        let started = (SystemTime::now(), Instant::now());
+        let result = self
+            .execute::<WireMultiStatementResult>()
+            .await
+            .map(|executed| (executed.value, executed.submission));
+        let query_id = result
+            .as_ref()
+            .ok()
+            .and_then(|(multi_result, _)| multi_result.statement_handle.as_deref());
+        self.audit(started, query_id, &result);
+        let (multi_result, submission) = result?;
+        Ok((multi_result.statement_handles, submission))
+    }
+
+    /// Fetch the result of one statement of a multi-statement request, from the account
+    /// serving the `submission`
+    async fn fetch_child<T: serde::de::DeserializeOwned>(
+        &self,
+        submission: &Submission,
+        handle: &str,
+    ) -> SnowflakeResult<(T, usize)> {
+        let child = Submission {
+            handle: OnceLock::from(handle.to_owned()),
+            serving_account: submission.serving_account.clone(),
+        };
+        self.until_deadline(&child, async {
+            let response = self
+                .submission_transport(&child)
+                .request(|client, host| {
+                    client.get(format!("{}/api/v2/statements/{}", host, handle))
+                })
+                .await?;
+            parse_response(response).await
         })
+        .await
+    }
+
+    /// Execute a multi-statement request, returning the changes of each DML statement in it
+    ///
+    /// Snowflake only reports the handles of the statements, so each one's result is fetched
+    /// afterwards, and statements without DML stats (like `BEGIN` and `COMMIT`) are skipped.
+    pub(crate) async fn manipulate_multi(&self) -> SnowflakeResult<Vec<Changes>> {
+        let mut changes = vec![];
+        let (handles, submission) = self.execute_multi().await?;
+        for handle in handles {
+            let (child, _) = self
+                .fetch_child::<WireChildResult>(&submission, &handle)
+                .await?;
+            if let Some(stats) = child.stats {
+                changes.push(Changes::new(child.message, stats));
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Execute a multi-statement request, returning the result set of the statement at `index`
+    pub(crate) async fn query_multi(&self, index: usize) -> SnowflakeResult<QueryResponse> {
+        let started = Instant::now();
+        let (handles, submission) = self.execute_multi().await?;
+        let handle = handles.get(index).ok_or_else(|| {
+            SnowflakeError::UnexpectedResult(format!(
+                "expected at least {} statements, but Snowflake ran {}",
+                index + 1,
+                handles.len()
+            ))
+        })?;
+        let (wire, bytes) = self
+            .fetch_child::<WireQueryResponse>(&submission, handle)
+            .await?;
+        let rows = wire.data.len();
+        let response = wire.hydrate(self.served_by(submission), started);
+        response.record_fetch(0, bytes, rows, started.elapsed());
+        Ok(response)
+    }
+
+    /// Send an audit record for this statement, if the client has an audit hook
+    fn audit<T>(
+        &self,
+        (timestamp, started): (SystemTime, Instant),
+        query_id: Option<&str>,
+        result: &SnowflakeResult<T>,
+    ) {
+        let Some(hook) = &self.config.options().audit_hook else {
+            return;
+        };
+        hook.record(&AuditRecord {
+            timestamp,
+            elapsed: started.elapsed(),
+            user: self.config.user().to_owned(),
+            role: self.wire.role.clone(),
+            fingerprint: audit::fingerprint(&self.wire.statement),
+            request_id: self.uuid,
+            query_id: query_id.map(str::to_owned),
+            outcome: match result {
+                Ok(_) => AuditOutcome::Succeeded,
+                Err(error) => AuditOutcome::Failed(error.to_string()),
+            },
+        });
     }
 
     /// Set the Snowflake-side timeout for the statement
@@ -132,10 +763,195 @@ impl Statement {
     /// which is far too long for the use cases this library is targeting,
     /// so this library defaults to 30 seconds on the server side if not specified,
     /// implying a client-side timeout of 45 seconds.
+    ///
+    /// A timeout of 0 is the same as [`Statement::with_max_timeout`].
     pub fn with_timeout(mut self, timeout_seconds: u64) -> Statement {
         self.wire.timeout = Some(timeout_seconds);
         self
     }
+
+    /// Let the statement run for as long as Snowflake allows (currently 7 days)
+    ///
+    /// This is meant for long batch statements. Each HTTP request still has a finite
+    /// timeout, but statements that run longer than about 45 seconds are polled
+    /// until they finish instead of holding a request open.
+    pub fn with_max_timeout(mut self) -> Statement {
+        self.wire.timeout = Some(0);
+        self
+    }
+    /// The SQL text of this statement
+    pub fn sql(&self) -> &str {
+        &self.wire.statement
+    }
+
+    /// The JSON body that would be sent to Snowflake for this statement
+    ///
+    /// This is the exact body, including the database, warehouse, role, bindings, and
+    /// parameters, which is useful for reviewing generated statements before running them
+    /// or comparing requests in tests. With `redact_bindings`, the type of each binding is
+    /// kept but its value is replaced with `"<redacted>"`, so the JSON can be logged.
+    ///
+    /// Query tags derived from the current tracing span are included, since they are
+    /// part of the body, but the body is not compressed even if the client would.
+    pub fn to_wire_json(&self, redact_bindings: bool) -> SnowflakeResult<String> {
+        if !redact_bindings {
+            return Ok(serde_json::to_string(&self.wire())?);
+        }
+        let mut body = serde_json::to_value(self.wire())?;
+        if let Some(bindings) = body["bindings"].as_object_mut() {
+            for binding in bindings.values_mut() {
+                if !binding["value"].is_null() {
+                    binding["value"] = "<redacted>".into();
+                }
+            }
+        }
+        Ok(body.to_string())
+    }
+
+    /// The request ID sent to Snowflake with this statement
+    ///
+    /// This is generated randomly when the statement is prepared, and it's useful for
+    /// logging or correlating a request with Snowflake support.
+    pub fn request_id(&self) -> uuid::Uuid {
+        self.uuid
+    }
+
+    /// Use a specific request ID instead of a random one
+    ///
+    /// Snowflake uses the request ID to recognize duplicate submissions, so
+    /// you can use this to implement your own deduplication or retry schemes.
+    pub fn with_request_id(mut self, request_id: uuid::Uuid) -> Statement {
+        self.uuid = request_id;
+        self
+    }
+
+    /// Check the columns of the response before returning it from [`Statement::query`]
+    ///
+    /// See [`QueryResponse::expect_schema`] for details.
+    pub fn with_expected_schema(mut self, columns: &[(&str, RawCell)]) -> Statement {
+        self.expected_schema = Some(
+            columns
+                .iter()
+                .map(|(name, data_type)| (name.to_string(), *data_type))
+                .collect(),
+        );
+        self
+    }
+
+    /// Send an extra HTTP header with every request for this statement
+    ///
+    /// This applies to submitting the statement, polling it, and fetching its partitions,
+    /// which is useful for routing headers or idempotency keys required by a proxy.
+    /// Adding the same header more than once sends all of the values.
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Statement {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Send W3C `traceparent` and `tracestate` headers with every request for this statement
+    ///
+    /// This overrides the client's [`TraceContextProvider`](crate::TraceContextProvider), if any.
+    pub fn with_trace_context(mut self, trace_context: TraceContext) -> Statement {
+        self.trace_context = Some(trace_context);
+        self
+    }
+
+    /// Set the `QUERY_TAG` session parameter for this statement, which appears in `QUERY_HISTORY`
+    pub fn with_query_tag(mut self, tag: &str) -> Statement {
+        self.wire
+            .parameters
+            .insert("query_tag".into(), tag.to_owned());
+        self
+    }
+
+    /// Set a session parameter for this statement, like `MULTI_STATEMENT_COUNT`
+    pub(crate) fn with_parameter(mut self, name: &str, value: String) -> Statement {
+        self.wire.parameters.insert(name.to_owned(), value);
+        self
+    }
+
+    /// Choose how often to check on this statement while Snowflake runs it asynchronously,
+    /// overriding the client's [`PollStrategy`]
+    pub fn with_poll_strategy(mut self, poll_strategy: PollStrategy) -> Statement {
+        self.poll_strategy = Some(poll_strategy);
+        self
+    }
+
+    /// Re-execute the statement if its result expires while partitions are still being fetched
+    ///
+    /// Snowflake only keeps results for a limited time, so a slow consumer of a large result
+    /// can find that later partitions are gone. With this set, the statement is run again
+    /// and fetching resumes from the same partition index of the new result, as long as it
+    /// has the same columns and number of partitions (otherwise the original error is returned).
+    ///
+    /// Only use this for deterministic queries, since the rows come from a separate execution.
+    /// The re-execution gets its own request id, and its own record for the client's audit hook.
+    pub fn with_requery_on_expiry(mut self) -> Statement {
+        self.requery_on_expiry = true;
+        self
+    }
+
+    /// Ask Snowflake for the compact non-nullable result format, where `NULL`s are sent
+    /// as the string `"null"` instead of JSON `null`
+    ///
+    /// Use this when you know the result has no `NULL`s, or none in text columns: for most
+    /// other types, `"null"` can't be a real value, so it is still read as [`Cell::Null`]. But in
+    /// a text column, a `NULL` and the string `'null'` look the same, and both are read as text.
+    /// In a semi-structured column, a `NULL` and a JSON `null` look the same, and both are read
+    /// as [`Cell::Null`].
+    pub fn with_non_nullable_results(mut self) -> Statement {
+        self.nullable = false;
+        self
+    }
+
+    /// Bound the whole lifecycle of the statement by a deadline: submitting it, waiting for it
+    /// to finish, and fetching every partition of its result
+    ///
+    /// Per-request timeouts don't add up to an end-to-end limit, since a statement can make
+    /// many requests. With a deadline, the statement's Snowflake-side timeout is lowered to the
+    /// time that's left when it's submitted, and any request still in flight at the deadline
+    /// is abandoned. If Snowflake is still running the statement then, it's cancelled. All of
+    /// these fail with [`SnowflakeError::DeadlineExceeded`].
+    ///
+    /// The deadline sticks to the [`QueryResponse`], so partitions fetched after it passes
+    /// fail the same way.
+    pub fn with_deadline(mut self, deadline: Instant) -> Statement {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the client-side timeout for each HTTP request, regardless of the server-side timeout
+    ///
+    /// This is useful on slow links, where transferring a large first partition can take
+    /// longer than the usual 15 seconds of slack, without letting the statement itself
+    /// run any longer on Snowflake. It also applies to fetching additional partitions,
+    /// unless there is a separate partition timeout.
+    pub fn with_client_timeout(mut self, timeout: Duration) -> Statement {
+        self.client_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for each request that fetches a partition after the first,
+    /// overriding the client's [`ClientOptions::partition_timeout`](crate::ClientOptions::partition_timeout)
+    ///
+    /// Partitions are already computed, so fetching one is only a download: this can be much
+    /// shorter than the statement's timeout on fast links, or longer for huge partitions on
+    /// slow ones.
+    pub fn with_partition_timeout(mut self, timeout: Duration) -> Statement {
+        self.partition_timeout = Some(timeout);
+        self
+    }
+
+    /// Parse the NUMBER cells of the result with this mode, overriding the client's
+    /// [`ClientOptions::number_mode`](crate::ClientOptions::number_mode)
+    ///
+    /// Use [`NumberMode::Exact`] for amounts of money and other decimals that must not be
+    /// rounded to the nearest `f64`.
+    pub fn with_number_mode(mut self, number_mode: NumberMode) -> Statement {
+        self.number_mode = Some(number_mode);
+        self
+    }
+
     /// Add a binding to the statement
     ///
     /// Several types are supported:
@@ -143,14 +959,19 @@ impl Statement {
     /// * All integers are converted to `i128` and bound as `NUMBER`
     /// * `f64` and `f32` are bound as `REAL`
     /// * `bool`, `&str`, `String`, `chrono::NaiveDate`, `chrono::NaiveDateTime`, and `chrono::NaiveTime` are bound as `TEXT`
+    ///   (the chrono types require the `chrono` feature, which is enabled by default)
+    /// * With the `time` feature, `time::Date`, `time::Time`, `time::PrimitiveDateTime`,
+    ///   and `time::OffsetDateTime` are also bound as `TEXT`
+    /// * `&[u8]` and `Vec<u8>` (and `bytes::Bytes`, with the `bytes` feature) are bound as `BINARY`
+    /// * `Option`s of any of these are bound as `NULL` when they are `None`
     ///
     /// More types may be supported in the future.
     ///
     /// Text is the most flexible type, and for additional types you can usually workaround by
-    /// converting to text before binding. Or, you could contribute to this library and add support
-    pub fn add_binding<T: Into<Binding>>(mut self, value: T) -> Statement {
-        let bindings = &mut self.wire.bindings;
-        bindings.insert((bindings.len() + 1).to_string(), value.into());
+    /// converting to text before binding. For your own types, implement [`ToBinding`] once
+    /// and pass them directly. Or, you could contribute to this library and add support
+    pub fn add_binding<T: ToBinding>(mut self, value: T) -> Statement {
+        self.wire.bindings.push(value.to_binding());
         self
     }
 }
@@ -165,24 +986,297 @@ impl Statement {
 pub struct QueryResponse {
     result_set_meta_data: WireStatementMetaData,
     data: Arc<StringTable>,
+    statement_handle: String,
     statement_status_url: String,
+    /// Client-side statistics, updated as partitions are fetched
+    transfer: std::sync::Mutex<TransferStats>,
+    /// When the statement was submitted, for the wall time in the transfer stats
+    submitted: Instant,
+    latency: Latency,
+    /// The status URL of a re-execution, if the result expired (see [`Statement::with_requery_on_expiry`])
+    requeried_status_url: tokio::sync::Mutex<Option<String>>,
     statement: Statement,
 }
 
+/// What a stored procedure or scripting block returned
+///
+/// These are returned by [`Statement::call`].
+#[derive(Debug)]
+pub enum ScriptOutput {
+    /// A single value, from `RETURN <expression>` (`NULL` if nothing was returned)
+    Value(Cell),
+    /// A result set, from `RETURN TABLE(...)`
+    Table(Box<QueryResponse>),
+}
+
+impl ScriptOutput {
+    /// Convert a returned value to a [`FromCell`] type, failing if a table was returned instead
+    pub fn value<T: FromCell>(&self) -> SnowflakeResult<T> {
+        match self {
+            ScriptOutput::Value(cell) => Ok(T::from_cell(cell)?),
+            ScriptOutput::Table(_) => Err(SnowflakeError::UnexpectedResult(
+                "expected a value, but a table was returned".into(),
+            )),
+        }
+    }
+
+    /// Take the returned table, or `None` if a single value was returned instead
+    pub fn into_table(self) -> Option<QueryResponse> {
+        match self {
+            ScriptOutput::Value(_) => None,
+            ScriptOutput::Table(table) => Some(*table),
+        }
+    }
+}
+
 /// The result of a DML statement
 ///
 /// These are returned by [`Statement::manipulate`] and are almost exactly
-/// the same as the response from Snowflake.
-#[derive(Debug)]
+/// the same as the response from Snowflake. The most common counts are copied
+/// to the top level, and [`Changes::stats`] has everything Snowflake reported.
+#[derive(Debug, Clone, Serialize)]
 pub struct Changes {
     pub message: String,
     pub rows_inserted: usize,
     pub rows_deleted: usize,
     pub rows_updated: usize,
     pub duplicates: usize,
+    /// The complete stats object from Snowflake
+    pub stats: DmlStats,
+    /// When Snowflake created the statement, and how long the client waited for it
+    pub latency: Latency,
+}
+
+impl Changes {
+    fn new(message: String, stats: DmlStats) -> Changes {
+        Changes {
+            message,
+            rows_inserted: stats.rows_inserted,
+            rows_deleted: stats.rows_deleted,
+            rows_updated: stats.rows_updated,
+            duplicates: stats.duplicates,
+            stats,
+            latency: Latency::default(),
+        }
+    }
+}
+
+/// The stats Snowflake reports for a DML statement, as in the `stats` field of its response
+///
+/// Counts that are missing from the response are zero. Snowflake reports some counts only
+/// for certain statements (like rows unloaded by `COPY INTO <location>`), and may add more
+/// over time, so any other numbers are kept in [`DmlStats::other`] under their original names.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct DmlStats {
+    #[serde(rename = "numRowsInserted", default)]
+    pub rows_inserted: usize,
+    #[serde(rename = "numRowsDeleted", default)]
+    pub rows_deleted: usize,
+    #[serde(rename = "numRowsUpdated", default)]
+    pub rows_updated: usize,
+    #[serde(rename = "numDmlDuplicates", default)]
+    pub duplicates: usize,
+    /// Rows written to files by `COPY INTO <location>`
+    #[serde(rename = "numRowsUnloaded", default)]
+    pub rows_unloaded: usize,
+    /// Any other stats, like `numRowsLoaded`, by their names in the response
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl DmlStats {
+    /// Get any count by its name in the response, like `numRowsInserted`
+    pub fn get(&self, name: &str) -> Option<u64> {
+        let known = match name {
+            "numRowsInserted" => self.rows_inserted,
+            "numRowsDeleted" => self.rows_deleted,
+            "numRowsUpdated" => self.rows_updated,
+            "numDmlDuplicates" => self.duplicates,
+            "numRowsUnloaded" => self.rows_unloaded,
+            _ => return self.other.get(name).and_then(serde_json::Value::as_u64),
+        };
+        Some(known as u64)
+    }
+}
+
+/// The combined result of several DML statements
+///
+/// This sums the row counts of each statement, while keeping each statement's
+/// own [`Changes`] for a per-statement breakdown. You can build one by collecting
+/// an iterator of `Changes`, or by calling [`ChangesSummary::push`] as you go.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ChangesSummary {
+    pub rows_inserted: usize,
+    pub rows_deleted: usize,
+    pub rows_updated: usize,
+    pub duplicates: usize,
+    /// The changes of each statement, in the order they were added
+    pub statements: Vec<Changes>,
+}
+
+impl ChangesSummary {
+    /// Add the changes from one more statement
+    pub fn push(&mut self, changes: Changes) {
+        self.rows_inserted += changes.rows_inserted;
+        self.rows_deleted += changes.rows_deleted;
+        self.rows_updated += changes.rows_updated;
+        self.duplicates += changes.duplicates;
+        self.statements.push(changes);
+    }
+
+    /// Combine two summaries, keeping the statements of `other` after those of `self`
+    pub fn merge(mut self, other: ChangesSummary) -> ChangesSummary {
+        self.extend(other.statements);
+        self
+    }
+
+    /// The total number of rows inserted, deleted, or updated
+    pub fn rows_affected(&self) -> usize {
+        self.rows_inserted + self.rows_deleted + self.rows_updated
+    }
+}
+
+impl Extend<Changes> for ChangesSummary {
+    fn extend<T: IntoIterator<Item = Changes>>(&mut self, iter: T) {
+        for changes in iter {
+            self.push(changes);
+        }
+    }
+}
+
+impl FromIterator<Changes> for ChangesSummary {
+    fn from_iter<T: IntoIterator<Item = Changes>>(iter: T) -> Self {
+        let mut summary = ChangesSummary::default();
+        summary.extend(iter);
+        summary
+    }
+}
+
+/// The execution state of a statement, as reported by Snowflake
+///
+/// These are returned by [`QueryResponse::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementStatus {
+    /// The statement is still executing
+    Running,
+    /// The statement completed successfully
+    Succeeded,
+    /// The statement failed, or its result is no longer available
+    Failed { code: String, message: String },
+}
+
+/// Execution statistics for a completed query, from `QUERY_HISTORY`
+///
+/// These are returned by [`QueryResponse::stats`]. Any of them may be missing,
+/// depending on the kind of statement and your account's privileges.
+#[derive(Debug, Clone)]
+pub struct QueryStats {
+    /// The number of bytes scanned by the query
+    pub bytes_scanned: Option<u64>,
+    /// The number of rows the query produced
+    pub rows_produced: Option<u64>,
+    /// Time spent compiling the query
+    pub compilation_time: Option<Duration>,
+    /// Time spent executing the query
+    pub execution_time: Option<Duration>,
+    /// Time spent waiting in the warehouse queue because it was overloaded
+    pub queued_overload_time: Option<Duration>,
+    /// End-to-end time reported by Snowflake
+    pub total_elapsed_time: Option<Duration>,
+    /// Cloud services credits used by the query
+    ///
+    /// Warehouse credits are billed per warehouse rather than per query,
+    /// so they can't be reported here.
+    pub credits_used_cloud_services: Option<f64>,
+}
+
+impl QueryStats {
+    /// Read the stats from a row of `QUERY_HISTORY`, whichever [`NumberMode`] it was parsed with
+    fn from_history(row: &[Cell]) -> QueryStats {
+        let count = |cell: &Cell| match cell {
+            Cell::Int(x) => u64::try_from(*x).ok(),
+            Cell::Float(x) => Some(*x as u64),
+            Cell::Number { mantissa, scale } => {
+                let factor = 10_i128.checked_pow((*scale).try_into().ok()?)?;
+                u64::try_from(mantissa / factor).ok()
+            }
+            _ => None,
+        };
+        let millis = |cell: &Cell| count(cell).map(Duration::from_millis);
+        QueryStats {
+            bytes_scanned: count(&row[0]),
+            rows_produced: count(&row[1]),
+            compilation_time: millis(&row[2]),
+            execution_time: millis(&row[3]),
+            queued_overload_time: millis(&row[4]),
+            total_elapsed_time: millis(&row[5]),
+            credits_used_cloud_services: f64::from_cell(&row[6]).ok(),
+        }
+    }
 }
 
 impl QueryResponse {
+    /// The Snowflake query ID (also called the statement handle)
+    ///
+    /// This is the ID shown in `QUERY_HISTORY` and the Snowflake UI
+    pub fn query_id(&self) -> &str {
+        &self.statement_handle
+    }
+
+    /// Fetch execution statistics for this query from `QUERY_HISTORY`
+    ///
+    /// This runs an additional query using the same client, so it incurs IO
+    /// and uses a little of the warehouse. Returns `None` if Snowflake has no
+    /// history for this query (e.g. it isn't visible to your role).
+    pub async fn stats(&self) -> SnowflakeResult<Option<QueryStats>> {
+        let history = self
+            .statement
+            .config
+            .prepare(
+                "SELECT BYTES_SCANNED, ROWS_PRODUCED, COMPILATION_TIME, EXECUTION_TIME,
+                    QUEUED_OVERLOAD_TIME, TOTAL_ELAPSED_TIME, CREDITS_USED_CLOUD_SERVICES
+                FROM TABLE(INFORMATION_SCHEMA.QUERY_HISTORY_BY_USER(RESULT_LIMIT => 10000))
+                WHERE QUERY_ID = ?",
+            )
+            .add_binding(self.query_id())
+            .query()
+            .await?
+            .only_partition()?
+            .cells()?;
+        Ok(history.first().map(|row| QueryStats::from_history(row)))
+    }
+
+    /// Check the execution status of this statement with Snowflake
+    ///
+    /// This incurs IO. A `QueryResponse` is only created once the statement has succeeded,
+    /// so this is mostly useful to confirm the result is still available on the server.
+    pub async fn status(&self) -> SnowflakeResult<StatementStatus> {
+        let response = self
+            .statement
+            .transport()
+            .request(|client, host| client.get(format!("{}{}", host, self.statement_status_url)))
+            .await?;
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(StatementStatus::Succeeded),
+            reqwest::StatusCode::ACCEPTED => Ok(StatementStatus::Running),
+            _ => {
+                let status = response.json::<WireStatementStatus>().await?;
+                Ok(StatementStatus::Failed {
+                    code: status.code,
+                    message: status.message,
+                })
+            }
+        }
+    }
+
+    /// When Snowflake created the statement, and how long the client waited for its response
+    ///
+    /// This only covers the statement itself; see [`QueryResponse::transfer_stats`] for
+    /// fetching the rest of the partitions.
+    pub fn latency(&self) -> &Latency {
+        &self.latency
+    }
+
     /// Get the number of rows across all partitions
     pub fn num_rows(&self) -> usize {
         self.result_set_meta_data.num_rows
@@ -207,6 +1301,45 @@ impl QueryResponse {
         &self.result_set_meta_data.row_type
     }
 
+    /// Check that the response has exactly these column names and types, in order
+    ///
+    /// This catches schema drift before any rows are consumed. Names are compared exactly,
+    /// so remember that Snowflake uppercases unquoted names. This never causes IO.
+    pub fn expect_schema(&self, columns: &[(&str, RawCell)]) -> SnowflakeResult<()> {
+        let actual = self.column_types();
+        if actual.len() != columns.len() {
+            return Err(SchemaMismatch::ColumnCount {
+                expected: columns.len(),
+                actual: actual.len(),
+            }
+            .into());
+        }
+        for (index, ((name, data_type), column)) in columns.iter().zip(actual).enumerate() {
+            if *name != column.name || *data_type != column.data_type {
+                return Err(SchemaMismatch::Column {
+                    index,
+                    expected_name: name.to_string(),
+                    expected_type: *data_type,
+                    actual_name: column.name.clone(),
+                    actual_type: column.data_type,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// A partition of this response, which parses NUMBER cells with the statement's mode,
+    /// converts TIMESTAMP_LTZ cells to the client's zone, and reads `"null"` in the statement's
+    /// result format
+    fn new_partition(&self, index: usize, data: Arc<StringTable>) -> Partition {
+        let mut partition = Partition::new(index, self.result_set_meta_data.clone(), data)
+            .with_number_mode(self.statement.number_mode())
+            .with_timestamp_zone(self.statement.config.options().timestamp_zone);
+        partition.nullable = self.statement.nullable;
+        partition
+    }
+
     /// A convenience method to assert that there is only one partition and return it
     ///
     /// This never causes IO, is not async, and can only error with [`SnowflakeError::MultiplePartitions`]
@@ -214,50 +1347,180 @@ impl QueryResponse {
         if self.num_partitions() != 1 {
             Err(SnowflakeError::MultiplePartitions)
         } else {
-            Ok(Partition {
-                index: 0,
-                meta_data: self.result_set_meta_data.clone(),
-                data: self.data.clone(),
+            Ok(self.new_partition(0, self.data.clone()))
+        }
+    }
+
+    /// Get a single partition from the response
+    ///
+    /// If this is the first partition, you get it immediately,
+    /// otherwise it will incur an additional request to get the partition
+    ///
+    /// Returns an error if the requested partition does not exist.
+    pub async fn partition(&self, index: usize) -> SnowflakeResult<Option<Partition>> {
+        if index == 0 {
+            Ok(Some(self.new_partition(index, self.data.clone())))
+        } else if index >= self.num_partitions() {
+            Ok(None)
+        } else {
+            let started = Instant::now();
+            let status_url = self.requeried_status_url.lock().await.clone();
+            let status_url = status_url.unwrap_or_else(|| self.statement_status_url.clone());
+            let fetch = async {
+                match self.fetch_partition(&status_url, index).await {
+                    Err(error) if self.statement.requery_on_expiry && is_expired_result(&error) => {
+                        let status_url = self.requery(&status_url, error).await?;
+                        self.fetch_partition(&status_url, index).await
+                    }
+                    result => result,
+                }
+            };
+            let (data, bytes) = self
+                .statement
+                .until_deadline(
+                    &self.statement.submission(Some(&self.statement_handle)),
+                    fetch,
+                )
+                .await?;
+            self.record_fetch(index, bytes, data.len(), started.elapsed());
+            Ok(Some(self.new_partition(index, data)))
+        }
+    }
+
+    /// Fetch a partition's JSON just as Snowflake sent it, without parsing it
+    ///
+    /// This is for services that relay results, like to a browser, and would otherwise parse
+    /// every string only to encode it again. The body looks like `{"data": [["1", "Henry"]]}`
+    /// (with strings in the same wire format as [`Partition::raw_cells`]), and for the first
+    /// partition, it also has `resultSetMetaData`, which describes the columns.
+    ///
+    /// Unlike [`QueryResponse::partition`], this always makes a request, even for the first
+    /// partition, which Snowflake sends again. Returns `None` if the partition does not exist.
+    pub async fn raw_partition(&self, index: usize) -> SnowflakeResult<Option<bytes::Bytes>> {
+        if index >= self.num_partitions() {
+            return Ok(None);
+        }
+        let status_url = self.requeried_status_url.lock().await.clone();
+        let status_url = status_url.unwrap_or_else(|| self.statement_status_url.clone());
+        let fetch = async {
+            match read_body(self.partition_request(&status_url, index).await?).await {
+                Err(error) if self.statement.requery_on_expiry && is_expired_result(&error) => {
+                    let status_url = self.requery(&status_url, error).await?;
+                    read_body(self.partition_request(&status_url, index).await?).await
+                }
+                result => result,
+            }
+        };
+        self.statement
+            .until_deadline(
+                &self.statement.submission(Some(&self.statement_handle)),
+                fetch,
+            )
+            .await
+            .map(Some)
+    }
+
+    /// Stream over the JSON of every partition, just as Snowflake sent it
+    ///
+    /// See [`QueryResponse::raw_partition`]. Like [`QueryResponse::partitions`], this buffers
+    /// one partition.
+    pub fn raw_partitions(&self) -> ResultStream<'_, bytes::Bytes> {
+        let partition_futures = (0..self.num_partitions()).map(|index| self.raw_partition(index));
+        ResultStream::new(
+            futures::stream::iter(partition_futures)
+                .buffered(1)
+                // We can't be out of bounds, so remove the Option
+                .map_ok(Option::unwrap),
+        )
+    }
+
+    async fn fetch_partition(
+        &self,
+        status_url: &str,
+        index: usize,
+    ) -> SnowflakeResult<(Arc<StringTable>, usize)> {
+        let response = self.partition_request(status_url, index).await?;
+        let (response, bytes) = parse_data_response::<WirePartitionResponse>(response).await?;
+        Ok((response.data, bytes))
+    }
+
+    async fn partition_request(
+        &self,
+        status_url: &str,
+        index: usize,
+    ) -> SnowflakeResult<reqwest::Response> {
+        self.statement
+            .transport()
+            .request(|client, host| {
+                client
+                    .get(format!("{}{}", host, status_url))
+                    .query(&[("partition", index)])
+                    .header("Accept", "application/json")
+                    .timeout(self.statement.partition_timeout())
             })
+            .await
+    }
+
+    /// Update the transfer stats and notify the client's metrics hook, if any
+    fn record_fetch(&self, index: usize, bytes: usize, rows: usize, duration: Duration) {
+        let fetch = PartitionFetch {
+            query_id: self.statement_handle.clone(),
+            index,
+            bytes,
+            rows,
+            duration,
+        };
+        {
+            let mut transfer = self.transfer.lock().unwrap();
+            transfer.record(&fetch);
+            transfer.wall_time = self.submitted.elapsed();
+        }
+        if let Some(hook) = &self.statement.config.options().metrics_hook {
+            hook.record(&fetch);
         }
     }
 
-    /// Get a single partition from the response
-    ///
-    /// If this is the first partition, you get it immediately,
-    /// otherwise it will incur an additional request to get the partition
+    /// Client-side statistics about fetching this result so far, like bytes downloaded
+    pub fn transfer_stats(&self) -> TransferStats {
+        self.transfer.lock().unwrap().clone()
+    }
+
+    /// Run the statement again after its result expired, returning the new status URL
     ///
-    /// Returns an error if the requested partition does not exist.
-    pub async fn partition(&self, index: usize) -> SnowflakeResult<Option<Partition>> {
-        if index == 0 {
-            Ok(Some(Partition {
-                index,
-                meta_data: self.result_set_meta_data.clone(),
-                data: self.data.clone(),
-            }))
-        } else if index >= self.num_partitions() {
-            Ok(None)
-        } else {
-            let url =
-                self.statement.host.trim_end_matches('/').to_owned() + &self.statement_status_url;
-            let response = self
-                .statement
-                .client()?
-                .get(&url)
-                .query(&[("partition", index)])
-                .header("Accept", "application/json")
-                .send()
-                .await?
-                .json::<SnowflakeWireResult<WirePartitionResponse>>()
-                .await?
-                .into_result()?;
-
-            Ok(Some(Partition {
-                index,
-                meta_data: self.result_set_meta_data.clone(),
-                data: response.data,
-            }))
+    /// Concurrent fetches that hit the same expired result share one re-execution.
+    async fn requery(&self, expired_url: &str, error: SnowflakeError) -> SnowflakeResult<String> {
+        let mut requeried = self.requeried_status_url.lock().await;
+        if let Some(url) = requeried.as_deref().filter(|url| *url != expired_url) {
+            return Ok(url.to_owned());
         }
+        log::warn!("Result of {} expired, running it again", self.query_id());
+        // This is audited like any other execution, under its own request id
+        let started = (SystemTime::now(), Instant::now());
+        let statement = self.statement.clone().with_request_id(uuid::Uuid::new_v4());
+        let result = statement.execute::<WireQueryResponse>().await;
+        let query_id = result
+            .as_ref()
+            .ok()
+            .map(|executed| executed.value.statement_handle.as_str());
+        statement.audit(started, query_id, &result);
+        let executed = result?;
+        // Keep fetching from the account that ran it again, if that one failed over
+        if let Some(account) = executed.submission.serving_account.into_inner() {
+            let _ = self.statement.serving_account.set(account);
+        }
+        let rerun = executed.value;
+        self.transfer.lock().unwrap().retries += executed.retries + 1;
+        let columns = |meta: &WireStatementMetaData| -> Vec<(String, RawCell)> {
+            let types = meta.row_type.iter();
+            types.map(|c| (c.name.clone(), c.data_type)).collect()
+        };
+        let same_shape = rerun.result_set_meta_data.partition_info.len() == self.num_partitions()
+            && columns(&rerun.result_set_meta_data) == columns(&self.result_set_meta_data);
+        if !same_shape {
+            return Err(error);
+        }
+        *requeried = Some(rerun.statement_status_url.clone());
+        Ok(rerun.statement_status_url)
     }
 
     /// Stream over all partitions in the response
@@ -266,14 +1529,38 @@ impl QueryResponse {
     ///
     /// In order to improve concurrency, this will buffer one partition,
     /// so you can have one partition in flight while processing another.
-    pub fn partitions(&self) -> impl TryStream<Ok = Partition, Error = SnowflakeError> + '_ {
+    /// Dropping the stream aborts that request, so stopping early doesn't cost any more IO.
+    pub fn partitions(&self) -> PartitionStream<'_> {
         let partition_futures = (0..self.num_partitions()).map(|index| self.partition(index));
-        futures::stream::iter(partition_futures)
-            .buffered(1)
-            .then(move |partition| async move {
+        ResultStream::new(futures::stream::iter(partition_futures).buffered(1).then(
+            move |partition| async move {
                 // We can't be out of bounds, so remove the Option
                 partition.map(|opt| opt.unwrap())
-            })
+            },
+        ))
+    }
+
+    /// Stream over all partitions in the response, like [`QueryResponse::partitions`],
+    /// but taking ownership of the response so the stream can outlive it
+    pub fn into_partition_stream(self) -> PartitionStream<'static> {
+        let num_partitions = self.num_partitions();
+        let response = Arc::new(self);
+        let partition_futures = (0..num_partitions).map(move |index| {
+            let response = response.clone();
+            async move {
+                // We can't be out of bounds, so remove the Option
+                response.partition(index).await.map(|opt| opt.unwrap())
+            }
+        });
+        ResultStream::new(futures::stream::iter(partition_futures).buffered(1))
+    }
+
+    /// Fetch every partition and convert them into a single `serde_json::Value`, like
+    /// `{"columns": ["ID", "NAME"], "rows": [[1, "Henry"]]}`
+    ///
+    /// See [`Partition::to_value`]. This incurs IO, and holds the whole result in memory.
+    pub async fn to_value(&self) -> SnowflakeResult<serde_json::Value> {
+        self.concat_partitions().await?.to_value()
     }
 
     /// Concatenate all partitions into a single partition
@@ -286,13 +1573,50 @@ impl QueryResponse {
         let mut cells = Vec::with_capacity(self.num_rows());
         for partition in self.partitions().try_collect::<Vec<_>>().await? {
             // TODO: This could save a clone when Arc::unwrap_or_clone is stable
-            cells.extend(partition.data.iter().cloned());
+            cells.extend(partition.raw_cells().iter().cloned());
         }
-        Ok(Partition {
-            index: 0,
-            meta_data: self.result_set_meta_data.clone(),
-            data: Arc::new(cells),
-        })
+        Ok(self.new_partition(0, Arc::new(cells)))
+    }
+
+    /// Download every partition up front, with up to `concurrency` requests in flight,
+    /// and concatenate them into a single partition
+    ///
+    /// This incurs IO, so try to only use this once.
+    ///
+    /// Unlike [`QueryResponse::concat_partitions`], this fetches partitions concurrently and
+    /// can stop early: if `max_bytes` is set and the text of the cells exceeds it, this returns
+    /// [`SnowflakeError::ResultTooLarge`] and cancels any outstanding requests.
+    pub async fn prefetch_all(
+        &self,
+        concurrency: usize,
+        max_bytes: Option<usize>,
+    ) -> SnowflakeResult<Partition> {
+        let mut cells = Vec::with_capacity(self.num_rows());
+        let mut bytes = 0;
+        let mut partitions = futures::stream::iter(0..self.num_partitions())
+            .map(|index| self.partition(index))
+            .buffered(concurrency.max(1));
+        while let Some(partition) = partitions.try_next().await? {
+            // We can't be out of bounds, so remove the Option
+            let partition = partition.unwrap();
+            if let Some(max_bytes) = max_bytes {
+                bytes += partition
+                    .raw_cells()
+                    .iter()
+                    .flatten()
+                    .map(|cell| cell.as_ref().map_or(0, String::len))
+                    .sum::<usize>();
+                if bytes > max_bytes {
+                    return Err(SnowflakeError::ResultTooLarge { max_bytes });
+                }
+            }
+            // Only the first partition is shared with the response, the rest can be moved
+            match Arc::try_unwrap(partition.data) {
+                Ok(data) => cells.extend(data),
+                Err(data) => cells.extend(data[partition.rows].iter().cloned()),
+            }
+        }
+        Ok(self.new_partition(0, Arc::new(cells)))
     }
 
     /// Stream over all rows in the response
@@ -303,11 +1627,56 @@ impl QueryResponse {
     /// so you can have one partition in flight while processing another.
     ///
     /// If you only need one partition, it may be simpler to use `partition`
-    /// and then stream over the rows in that partition.
-    pub fn rows(&self) -> impl TryStream<Ok = Vec<Cell>, Error = SnowflakeError> + '_ {
-        self.partitions()
-            .map_ok(|partition| futures::stream::iter(partition.cells()).map(Ok))
-            .try_flatten()
+    /// and then stream over the rows in that partition. To stop after a number of rows,
+    /// use [`QueryResponse::take_rows`], which avoids fetching partitions it won't need.
+    pub fn rows(&self) -> RowStream<'_> {
+        ResultStream::new(
+            self.partitions()
+                .map(|partition| partition?.rows())
+                .map_ok(|rows| futures::stream::iter(rows).map(Ok))
+                .try_flatten(),
+        )
+    }
+
+    /// Stream over all rows in the response, like [`QueryResponse::rows`], but taking
+    /// ownership of the response so the stream can outlive it
+    ///
+    /// For example, this can be returned from an axum handler as the body of a response.
+    pub fn into_row_stream(self) -> RowStream<'static> {
+        ResultStream::new(
+            self.into_partition_stream()
+                .map(|partition| partition?.rows())
+                .map_ok(|rows| futures::stream::iter(rows).map(Ok))
+                .try_flatten(),
+        )
+    }
+
+    /// Stream over the first `limit` rows in the response, like [`QueryResponse::rows`]
+    /// with a client-side `LIMIT`
+    ///
+    /// Snowflake reports how many rows each partition has, so only the partitions holding
+    /// those rows are fetched, and none are prefetched past them.
+    pub fn take_rows(&self, limit: usize) -> RowStream<'_> {
+        let mut needed = 0;
+        let mut rows = 0;
+        for partition in &self.result_set_meta_data.partition_info {
+            if rows >= limit {
+                break;
+            }
+            needed += 1;
+            // Without a row count, the stream still stops fetching once the rows are taken
+            rows = rows.saturating_add(partition.row_count.unwrap_or(usize::MAX));
+        }
+        let partition_futures = (0..needed).map(|index| self.partition(index));
+        ResultStream::new(
+            futures::stream::iter(partition_futures)
+                .buffered(1)
+                // We can't be out of bounds, so remove the Option
+                .map(|partition| partition?.unwrap().rows())
+                .map_ok(|rows| futures::stream::iter(rows).map(Ok))
+                .try_flatten()
+                .take(limit),
+        )
     }
 
     /// Stream over all rows in the response as JSON tables
@@ -316,12 +1685,13 @@ impl QueryResponse {
     ///
     /// In order to improve concurrency, this will buffer one partition,
     /// so you can have one partition in flight while processing another.
-    pub fn json_tables(
-        &self,
-    ) -> impl TryStream<Ok = Vec<serde_json::Value>, Error = SnowflakeError> + '_ {
-        self.partitions()
-            .map_ok(|partition| futures::stream::iter(partition.json_table()).map(Ok))
-            .try_flatten()
+    pub fn json_tables(&self) -> ResultStream<'_, Vec<serde_json::Value>> {
+        ResultStream::new(
+            self.partitions()
+                .map(|partition| partition?.json_table())
+                .map_ok(|rows| futures::stream::iter(rows).map(Ok))
+                .try_flatten(),
+        )
     }
 
     /// Stream over all rows in the response as JSON objects
@@ -330,61 +1700,863 @@ impl QueryResponse {
     ///
     /// In order to improve concurrency, this will buffer one partition,
     /// so you can have one partition in flight while processing another.
-    pub fn json_objects(
-        &self,
-    ) -> impl TryStream<Ok = serde_json::Value, Error = SnowflakeError> + '_ {
-        self.partitions()
-            .map_ok(|partition| futures::stream::iter(partition.json_objects()).map(Ok))
-            .try_flatten()
+    pub fn json_objects(&self) -> ResultStream<'_, serde_json::Value> {
+        ResultStream::new(
+            self.partitions()
+                .map(|partition| partition?.json_objects())
+                .map_ok(|rows| futures::stream::iter(rows).map(Ok))
+                .try_flatten(),
+        )
+    }
+
+    /// Send all rows in the response into a [`Sink`](futures::Sink), like a channel or websocket
+    ///
+    /// This incurs IO, so try to only use this once.
+    ///
+    /// Rows are fed one at a time and the sink is flushed after every partition, so a slow
+    /// sink applies backpressure to fetching partitions. The sink is not closed afterward.
+    pub async fn forward_to<S>(&self, sink: S) -> SnowflakeResult<ForwardSummary>
+    where
+        S: Sink<Row>,
+        S::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let sink_error = |error: S::Error| SnowflakeError::Sink(Box::new(error));
+        let partitions = self.partitions().into_stream();
+        futures::pin_mut!(partitions);
+        futures::pin_mut!(sink);
+        let mut summary = ForwardSummary::default();
+        while let Some(partition) = partitions.try_next().await? {
+            for row in partition.rows()? {
+                sink.feed(row).await.map_err(sink_error)?;
+                summary.rows += 1;
+            }
+            sink.flush().await.map_err(sink_error)?;
+            summary.partitions += 1;
+        }
+        Ok(summary)
+    }
+
+    /// Fetch the rows on a background task, and receive them from a channel
+    ///
+    /// This must be called within a Tokio runtime. The task fetches partitions while you
+    /// process rows, and waits whenever `capacity` rows are waiting to be received. It stops
+    /// after sending the first error, or as soon as the receiver is dropped.
+    ///
+    /// Unlike [`QueryResponse::rows`], the receiver doesn't borrow the response, so it can be
+    /// handed to another task or stored in a struct.
+    pub fn spawn_into_channel(
+        self,
+        capacity: usize,
+    ) -> tokio::sync::mpsc::Receiver<SnowflakeResult<Row>> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+        tokio::spawn(async move {
+            let mut rows = self.into_row_stream();
+            let closed = sender.closed();
+            futures::pin_mut!(closed);
+            // Stop as soon as the receiver is dropped, aborting a partition being fetched
+            while let futures::future::Either::Left((Some(row), _)) =
+                futures::future::select(rows.next(), closed.as_mut()).await
+            {
+                let failed = row.is_err();
+                if sender.send(row).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        receiver
     }
 }
 
+/// A summary of the rows sent by [`QueryResponse::forward_to`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForwardSummary {
+    /// How many rows were sent into the sink
+    pub rows: usize,
+    /// How many partitions those rows came from
+    pub partitions: usize,
+}
+
 #[cfg(test)]
 mod tests {
-    use jwt_simple::algorithms::RS256KeyPair;
-
     use crate::errors::SnowflakeResult;
+    use crate::test_client;
 
     use super::*;
 
+    /// A response with one partition of a single NUMBER column with the values `0..rows`
+    fn test_response(rows: usize) -> SnowflakeResult<QueryResponse> {
+        submitted_response(rows, Instant::now())
+    }
+
+    /// Like [`test_response`], for a statement submitted at `submitted`
+    fn submitted_response(rows: usize, submitted: Instant) -> SnowflakeResult<QueryResponse> {
+        let wire: WireQueryResponse = serde_json::from_value(serde_json::json!({
+            "resultSetMetaData": {
+                "numRows": rows,
+                "rowType": [{
+                    "name": "IX",
+                    "database": "DB",
+                    "schema": "SCHEMA",
+                    "table": "TABLE",
+                    "precision": 38,
+                    "type": "fixed",
+                    "scale": 0,
+                    "nullable": false
+                }],
+                "partitionInfo": [{}]
+            },
+            "data": (0..rows).map(|ix| vec![ix.to_string()]).collect::<Vec<_>>(),
+            "statementHandle": "01b2c3d4-0000-0000-0000-000000000000",
+            "statementStatusUrl": "/api/v2/statements/01b2c3d4-0000-0000-0000-000000000000"
+        }))?;
+        Ok(wire.hydrate(test_client().prepare("SELECT seq4() AS IX"), submitted))
+    }
+
+    #[tokio::test]
+    async fn show_tables_metadata() -> SnowflakeResult<()> {
+        // Abbreviated from a real `SHOW TABLES` response: lowercase names, no source table
+        let wire: WireQueryResponse = serde_json::from_value(serde_json::json!({
+            "resultSetMetaData": {
+                "numRows": 1,
+                "rowType": [
+                    {"name": "created_on", "database": "", "schema": "", "table": "",
+                     "type": "timestamp_ltz", "scale": 3, "precision": 0, "nullable": true},
+                    {"name": "name", "database": null, "schema": null, "table": null,
+                     "type": "text", "byteLength": 16777216, "nullable": true},
+                    {"name": "rows", "type": "fixed", "scale": 0, "precision": 38}
+                ],
+                "partitionInfo": [{}]
+            },
+            "data": [["1672534861.123000000", "MY_TABLE", "42"]],
+            "statementHandle": "01b2c3d4-0000-0000-0000-000000000000",
+            "statementStatusUrl": "/api/v2/statements/01b2c3d4-0000-0000-0000-000000000000"
+        }))?;
+        let response = wire.hydrate(test_client().prepare("SHOW TABLES"), Instant::now());
+        assert_eq!(response.column_types()[2].table, "");
+        assert!(response.column_types()[2].nullable);
+        let rows: Vec<Row> = response.rows().try_collect().await?;
+        assert!(matches!(&rows[0]["name"], Cell::Varchar(name) if &**name == "MY_TABLE"));
+        assert!(matches!(rows[0]["ROWS"], Cell::Int(42)));
+        #[cfg(feature = "chrono")]
+        assert!(
+            matches!(rows[0]["created_on"], Cell::TimestampLtz(on) if on.timestamp() == 1672534861)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn responses_without_results() -> SnowflakeResult<()> {
+        let handle = serde_json::json!({
+            "statementHandle": "01b2c3d4-0000-0000-0000-000000000000",
+            "statementStatusUrl": "/api/v2/statements/01b2c3d4-0000-0000-0000-000000000000"
+        });
+        let with = |fields: serde_json::Value| -> SnowflakeResult<QueryResponse> {
+            let mut wire = handle.clone();
+            wire.as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            let wire: WireQueryResponse = serde_json::from_value(wire)?;
+            Ok(wire.hydrate(
+                test_client().prepare("CREATE TABLE t (id INT)"),
+                Instant::now(),
+            ))
+        };
+
+        // DDL reports its status as a row
+        let ddl = with(serde_json::json!({
+            "resultSetMetaData": {
+                "numRows": 1,
+                "rowType": [{"name": "status", "type": "text", "nullable": true}]
+            },
+            "data": [["Table T successfully created."]]
+        }))?;
+        assert_eq!(ddl.num_partitions(), 1);
+        let rows: Vec<Row> = ddl.rows().try_collect().await?;
+        assert_eq!(
+            rows[0].get_as::<String, _>("status")?,
+            "Table T successfully created."
+        );
+
+        // No metadata or data at all, or null data
+        for fields in [
+            serde_json::json!({}),
+            serde_json::json!({"resultSetMetaData": null, "data": null}),
+            serde_json::json!({"resultSetMetaData": {"rowType": [], "partitionInfo": []}}),
+        ] {
+            let empty = with(fields)?;
+            assert_eq!((empty.num_rows(), empty.num_columns()), (0, 0));
+            assert!(empty.rows().try_collect::<Vec<_>>().await?.is_empty());
+            assert_eq!(empty.only_partition()?.num_rows(), 0);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn forward_rows_to_sink() -> SnowflakeResult<()> {
+        let response = test_response(3)?;
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let summary = response.forward_to(tx).await?;
+        assert_eq!(
+            summary,
+            ForwardSummary {
+                rows: 3,
+                partitions: 1
+            }
+        );
+        let rows: Vec<Row> = rx.collect().await;
+        assert!(matches!(rows[2]["IX"], Cell::Int(2)));
+        Ok(())
+    }
+
+    #[test]
+    fn expect_schema() -> SnowflakeResult<()> {
+        let response = test_response(1)?;
+        response.expect_schema(&[("IX", RawCell::Fixed)])?;
+        assert!(matches!(
+            response.expect_schema(&[("IX", RawCell::Text)]),
+            Err(SnowflakeError::SchemaMismatch(SchemaMismatch::Column {
+                index: 0,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            response.expect_schema(&[]),
+            Err(SnowflakeError::SchemaMismatch(
+                SchemaMismatch::ColumnCount {
+                    expected: 0,
+                    actual: 1
+                }
+            ))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn query_tags_are_parameters() -> SnowflakeResult<()> {
+        let statement = test_client().prepare("SELECT 1");
+        let wire = serde_json::to_value(&*statement.wire())?;
+        assert!(wire.get("parameters").is_none());
+        let wire = serde_json::to_value(&*statement.with_query_tag("nightly").wire())?;
+        assert_eq!(wire["parameters"]["query_tag"], "nightly");
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn query_tags_from_span() -> SnowflakeResult<()> {
+        let statement = test_client()
+            .with_query_tag_from_span()
+            .prepare("SELECT 1")
+            .with_trace_context(TraceContext::new(
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ));
+        let tag = tracing::subscriber::with_default(tracing_subscriber::registry(), || {
+            let _span = tracing::info_span!("load_orders").entered();
+            statement.wire().parameters["query_tag"].clone()
+        });
+        assert_eq!(
+            tag,
+            r#"{"trace_id":"4bf92f3577b34da6a3ce929d0e0e4736","span":"load_orders"}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detect_expired_results() {
+        let error = |message: &str| SnowflakeError::ServerError {
+            code: "000709".into(),
+            message: message.into(),
+        };
+        assert!(is_expired_result(&error(
+            "Result for query 01b2 has expired."
+        )));
+        assert!(!is_expired_result(&error("SQL compilation error")));
+    }
+
+    #[test]
+    fn transfer_stats_accumulate() -> SnowflakeResult<()> {
+        let fetches = Arc::new(std::sync::Mutex::new(vec![]));
+        let client = {
+            let fetches = fetches.clone();
+            test_client()
+                .with_metrics_hook(move |fetch| fetches.lock().unwrap().push(fetch.clone()))
+        };
+        let response = test_response(3)?;
+        let response = QueryResponse {
+            statement: client.prepare("SELECT 1"),
+            ..response
+        };
+        response.record_fetch(0, 100, 3, Duration::from_millis(20));
+        response.record_fetch(1, 50, 2, Duration::from_millis(10));
+        let stats = response.transfer_stats();
+        assert_eq!(stats.bytes_downloaded, 150);
+        assert_eq!(stats.partitions_fetched, 2);
+        assert_eq!(stats.rows_parsed, 5);
+        assert_eq!(stats.partition_durations[1], (1, Duration::from_millis(10)));
+        assert_eq!(fetches.lock().unwrap().len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn owned_row_streams() -> SnowflakeResult<()> {
+        fn assert_send<T: Send + 'static>(value: T) -> T {
+            value
+        }
+        let rows = assert_send(test_response(3)?.into_row_stream());
+        let seen: Vec<Row> = rows.try_collect().await?;
+        assert_eq!(seen.len(), 3);
+        assert!(matches!(seen[2]["IX"], Cell::Int(2)));
+        let response = test_response(2)?;
+        let partitions: Vec<Partition> = response.partitions().try_collect().await?;
+        assert_eq!(partitions[0].num_rows(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn take_rows_skips_unneeded_partitions() -> SnowflakeResult<()> {
+        let wire: WireQueryResponse = serde_json::from_value(serde_json::json!({
+            "resultSetMetaData": {
+                "numRows": 6,
+                "rowType": [{"name": "IX", "type": "fixed", "scale": 0, "precision": 38}],
+                "partitionInfo": [{"rowCount": 3}, {"rowCount": 3}]
+            },
+            "data": [["0"], ["1"], ["2"]],
+            "statementHandle": "01b2c3d4-0000-0000-0000-000000000000",
+            "statementStatusUrl": "/api/v2/statements/01b2c3d4-0000-0000-0000-000000000000"
+        }))?;
+        // Fetching the second partition would fail, since there is no server
+        let response = wire.hydrate(test_client().prepare("SELECT seq4() AS IX"), Instant::now());
+        let rows: Vec<Row> = response.take_rows(2).try_collect().await?;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            response.take_rows(3).try_collect::<Vec<_>>().await?.len(),
+            3
+        );
+        assert!(response
+            .take_rows(0)
+            .try_collect::<Vec<_>>()
+            .await?
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn raw_partitions_are_bounded() -> SnowflakeResult<()> {
+        let response = test_response(1)?;
+        assert!(response.raw_partition(1).await?.is_none());
+        // The first partition is requested again, so it's subject to the deadline
+        let mut response = response;
+        response.statement = response.statement.with_deadline(Instant::now());
+        assert!(matches!(
+            response.raw_partition(0).await,
+            Err(SnowflakeError::DeadlineExceeded { query_id: Some(_) })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rows_can_be_received_from_a_channel() -> SnowflakeResult<()> {
+        let mut receiver = test_response(3)?.spawn_into_channel(1);
+        let mut seen = vec![];
+        while let Some(row) = receiver.recv().await {
+            seen.push(row?.get_as::<i64, _>("IX")?);
+        }
+        assert_eq!(seen, [0, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn statements_share_the_client() -> SnowflakeResult<()> {
+        let client = test_client();
+        let statement = client.clone().prepare("SELECT 1");
+        let other = client.prepare("SELECT 2");
+        assert!(Arc::ptr_eq(&statement.config.inner, &other.config.inner));
+        assert!(Arc::ptr_eq(&statement.config.inner, &client.inner));
+        assert!(Arc::ptr_eq(
+            &statement.config.inner,
+            &statement.clone().config.inner
+        ));
+        // Changing a clone's settings leaves the others alone
+        let failover = client
+            .clone()
+            .with_failover(crate::Failover::new("SECONDARY"));
+        assert!(!Arc::ptr_eq(&failover.inner, &client.inner));
+        assert!(client.options().failover.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn debug_redacts_secrets() -> SnowflakeResult<()> {
+        let client = test_client();
+        let private_key = client.key_pair().to_pem()?;
+        let statement = client
+            .prepare("SELECT * FROM users WHERE password = ?")
+            .add_binding("hunter2")
+            .with_header(
+                HeaderName::from_static("x-api-key"),
+                HeaderValue::from_static("s3cret"),
+            );
+        let debug = format!("{statement:?}");
+        assert!(debug.contains("SELECT * FROM users"));
+        assert!(debug.contains("x-api-key"));
+        for secret in ["hunter2", "s3cret", private_key.lines().nth(1).unwrap()] {
+            assert!(!debug.contains(secret), "{secret} leaked");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn wall_time_counts_from_submission() -> SnowflakeResult<()> {
+        let submitted = Instant::now();
+        std::thread::sleep(Duration::from_millis(20));
+        let time_to_first_byte = submitted.elapsed();
+        let mut response = submitted_response(1, submitted)?;
+        response.latency.time_to_first_byte = time_to_first_byte;
+        response.record_fetch(0, 1, 1, Duration::ZERO);
+        assert!(response.transfer_stats().wall_time >= response.latency().time_to_first_byte);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn audits_failed_statements() -> SnowflakeResult<()> {
+        let records = Arc::new(std::sync::Mutex::new(vec![]));
+        let client = {
+            let records = records.clone();
+            test_client()
+                .with_statement_policy(crate::StatementPolicy::read_only())
+                .with_audit_hook(move |record| records.lock().unwrap().push(record.clone()))
+        };
+        let statement = client.prepare("DELETE FROM users WHERE name = 'Henry'");
+        assert!(statement.manipulate().await.is_err());
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].user, "USER");
+        assert_eq!(records[0].fingerprint, "DELETE FROM users WHERE name = ?");
+        assert_eq!(records[0].request_id, statement.request_id());
+        assert_eq!(records[0].query_id, None);
+        assert!(matches!(records[0].outcome, AuditOutcome::Failed(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn query_stats_from_exact_numbers() -> SnowflakeResult<()> {
+        use crate::cells::TimestampZone;
+        let cell = |value: &str, scale: Option<i32>| {
+            let value = Some(value.to_owned());
+            RawCell::Fixed.to_cell_with(
+                &value,
+                scale,
+                NumberMode::Exact,
+                TimestampZone::Local,
+                true,
+            )
+        };
+        let row = [
+            cell("1048576.000", Some(3))?,
+            cell("42.0", None)?,
+            cell("12.5", None)?,
+            cell("250", None)?,
+            cell("0", None)?,
+            cell("262.500", Some(3))?,
+            cell("0.000125", Some(6))?,
+        ];
+        assert!(matches!(row[0], Cell::Number { .. }));
+        let stats = QueryStats::from_history(&row);
+        assert_eq!(stats.bytes_scanned, Some(1_048_576));
+        assert_eq!(stats.rows_produced, Some(42));
+        assert_eq!(stats.compilation_time, Some(Duration::from_millis(12)));
+        assert_eq!(stats.execution_time, Some(Duration::from_millis(250)));
+        assert_eq!(stats.queued_overload_time, Some(Duration::ZERO));
+        assert_eq!(stats.total_elapsed_time, Some(Duration::from_millis(262)));
+        assert_eq!(stats.credits_used_cloud_services, Some(0.000125));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn audits_requeries() -> SnowflakeResult<()> {
+        // Nothing listens at the account, so both executions fail to connect
+        let closed = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let records = Arc::new(std::sync::Mutex::new(vec![]));
+        let client = {
+            let records = records.clone();
+            test_client()
+                .with_resolve("account.snowflakecomputing.com", closed)
+                .with_audit_hook(move |record| records.lock().unwrap().push(record.clone()))
+        };
+        let statement = client.prepare("SELECT 1").with_requery_on_expiry();
+        assert!(statement.query().await.is_err());
+        let wire: WireQueryResponse = serde_json::from_value(serde_json::json!({
+            "data": [["1"]],
+            "statementHandle": "01b2c3d4",
+            "statementStatusUrl": "/api/v2/statements/01b2c3d4"
+        }))?;
+        let response = wire.hydrate(statement.clone(), Instant::now());
+        let expired = SnowflakeError::ServerError {
+            code: "000709".into(),
+            message: "Statement not found".into(),
+        };
+        assert!(response
+            .requery(&response.statement_status_url, expired)
+            .await
+            .is_err());
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].request_id, statement.request_id());
+        assert_ne!(records[1].request_id, statement.request_id());
+        assert_eq!(records[1].fingerprint, "SELECT ?");
+        assert!(matches!(records[1].outcome, AuditOutcome::Failed(_)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn policy_rejects_before_sending() -> SnowflakeResult<()> {
+        let client = test_client().with_statement_policy(crate::StatementPolicy::read_only());
+        // The account doesn't exist, so this would fail differently if it were sent
+        let result = client.prepare("DROP TABLE users").query().await;
+        assert!(matches!(result, Err(SnowflakeError::PolicyViolation(v)) if v.keyword == "DROP"));
+        Ok(())
+    }
+
     #[test]
     fn sql() -> SnowflakeResult<()> {
-        let key_pair = RS256KeyPair::generate(2048)?;
-        let sql = SnowflakeClient {
-            key_pair,
-            account: "ACCOUNT".into(),
-            user: "USER".into(),
-            database: "DB".into(),
-            warehouse: "WH".into(),
-            role: Some("ROLE".into()),
-        }
-        .prepare("SELECT * FROM TEST_TABLE WHERE id = ? AND name = ?")
-        .add_binding(10);
+        let sql = test_client()
+            .prepare("SELECT * FROM TEST_TABLE WHERE id = ? AND name = ?")
+            .add_binding(10);
         assert_eq!(sql.wire.bindings.len(), 1);
         let sql = sql.add_binding("Henry");
         assert_eq!(sql.wire.bindings.len(), 2);
+        let body = serde_json::to_value(&sql.wire)?;
+        assert_eq!(body["bindings"]["1"]["value"], "10");
+        assert_eq!(body["bindings"]["2"]["value"], "Henry");
+        Ok(())
+    }
+
+    #[test]
+    fn wire_json() -> SnowflakeResult<()> {
+        let sql = test_client()
+            .prepare("SELECT * FROM users WHERE id = ? AND name = ?")
+            .add_binding(10)
+            .add_binding(None::<String>)
+            .with_timeout(60);
+        let body: serde_json::Value = serde_json::from_str(&sql.to_wire_json(false)?)?;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "statement": "SELECT * FROM users WHERE id = ? AND name = ?",
+                "timeout": 60,
+                "database": "DB",
+                "warehouse": "WH",
+                "role": "ROLE",
+                "bindings": {
+                    "1": {"type": "FIXED", "value": "10"},
+                    "2": {"type": "TEXT", "value": null},
+                },
+            })
+        );
+        let redacted: serde_json::Value = serde_json::from_str(&sql.to_wire_json(true)?)?;
+        assert_eq!(
+            redacted["bindings"],
+            serde_json::json!({
+                "1": {"type": "FIXED", "value": "<redacted>"},
+                "2": {"type": "TEXT", "value": null},
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wire_json_without_namespace() -> SnowflakeResult<()> {
+        let client = SnowflakeClient::new(test_client().key_pair().clone(), "ACCOUNT", "USER");
+        let body: serde_json::Value =
+            serde_json::from_str(&client.prepare("SHOW DATABASES").to_wire_json(false)?)?;
+        assert!(body.get("database").is_none());
+        assert!(body.get("warehouse").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_bodies_round_trip() -> SnowflakeResult<()> {
+        use std::io::Read;
+        let body = serde_json::to_vec(&serde_json::json!({"bindings": vec!["value"; 1000]}))?;
+        let compressed = gzip(&body)?;
+        assert!(compressed.len() < body.len() / 10);
+        let mut decompressed = vec![];
+        flate2::read::GzDecoder::new(&compressed[..]).read_to_end(&mut decompressed)?;
+        assert_eq!(decompressed, body);
+        Ok(())
+    }
+
+    #[test]
+    fn scripting_blocks_are_wrapped() {
+        let block = "DECLARE x INT DEFAULT 1;\nBEGIN\n  RETURN x;\nEND;\n";
+        assert_eq!(
+            script_sql(block),
+            "EXECUTE IMMEDIATE $$\nDECLARE x INT DEFAULT 1;\nBEGIN\n  RETURN x;\nEND;\n$$"
+        );
+        assert_eq!(
+            script_sql("BEGIN RETURN '$$'; END"),
+            r"EXECUTE IMMEDIATE 'BEGIN RETURN \'$$\'; END'"
+        );
+        let wrapped = "execute immediate $$ BEGIN RETURN 1; END $$";
+        assert_eq!(script_sql(wrapped), wrapped);
+    }
+
+    #[test]
+    fn scalar_return_columns() {
+        assert_eq!(
+            return_column("EXECUTE IMMEDIATE $$ BEGIN RETURN 1; END; $$").as_deref(),
+            Some("anonymous block")
+        );
+        assert_eq!(
+            return_column("call db.public.add_one(?)").as_deref(),
+            Some("ADD_ONE")
+        );
+        assert_eq!(
+            return_column(r#"CALL db."My Procs"."addOne"()"#).as_deref(),
+            Some("addOne")
+        );
+        assert_eq!(return_column("SELECT 1"), None);
+        assert_eq!(return_column("CALLER()"), None);
+    }
+
+    #[test]
+    fn quote_object_names() {
+        assert_eq!(quote_object_name("db.public.users"), "db.public.users");
+        assert_eq!(
+            quote_object_name("DB.\"my.schema\".my table"),
+            "DB.\"my.schema\".\"my table\""
+        );
+        assert_eq!(quote_object_name("say\"hi"), "\"say\"\"hi\"");
+        assert_eq!(
+            quote_object_name(r#"db."say ""hi""""#),
+            r#"db."say ""hi""""#
+        );
+        // Quotes that would end the identifier early are doubled, rather than trusted
+        assert_eq!(
+            quote_object_name(r#""a"; DROP TABLE t; --""#),
+            r#""""a""; DROP TABLE t; --""""#
+        );
+        assert_eq!(quote_identifier(r#"""""#), r#""""""""""#);
+    }
+
+    #[test]
+    fn quoted_identifiers_keep_their_case() {
+        assert_eq!(normalize_identifier("my_db"), "MY_DB");
+        assert_eq!(normalize_identifier("\"my_db\""), "\"my_db\"");
+        assert_eq!(normalize_identifier("\""), "\"");
+    }
+
+    #[test]
+    fn partition_timeout_overrides() -> SnowflakeResult<()> {
+        let client = test_client();
+        let statement = client.prepare("SELECT 1").with_timeout(60);
+        assert_eq!(statement.partition_timeout(), Duration::from_secs(75));
+        let client = client.with_partition_timeout(Duration::from_secs(10));
+        let statement = client.prepare("SELECT 1").with_timeout(60);
+        assert_eq!(statement.partition_timeout(), Duration::from_secs(10));
+        let statement = statement.with_partition_timeout(Duration::from_secs(300));
+        assert_eq!(statement.partition_timeout(), Duration::from_secs(300));
+        Ok(())
+    }
+
+    #[test]
+    fn number_mode_overrides() -> SnowflakeResult<()> {
+        let client = test_client();
+        assert_eq!(client.prepare("SELECT 1").number_mode(), NumberMode::Lossy);
+        let client = client.with_number_mode(NumberMode::Exact);
+        let statement = client.prepare("SELECT 1");
+        assert_eq!(statement.number_mode(), NumberMode::Exact);
+        let statement = statement.with_number_mode(NumberMode::Lossy);
+        assert_eq!(statement.number_mode(), NumberMode::Lossy);
+        Ok(())
+    }
+
+    #[test]
+    fn client_timeout_follows_server_timeout() -> SnowflakeResult<()> {
+        let sql = test_client().prepare("SELECT 1");
+        assert_eq!(sql.client_timeout(), Duration::from_secs(45));
+        let sql = sql.with_timeout(100);
+        assert_eq!(sql.client_timeout(), Duration::from_secs(115));
+        let sql = sql.with_max_timeout();
+        assert_eq!(sql.wire.timeout, Some(0));
+        assert_eq!(sql.client_timeout(), UNBOUNDED_CLIENT_TIMEOUT);
+        let sql = sql.with_client_timeout(Duration::from_secs(600));
+        assert_eq!(sql.client_timeout(), Duration::from_secs(600));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deadlines_bound_the_statement() -> SnowflakeResult<()> {
+        let sql = test_client().prepare("SELECT 1").with_max_timeout();
+        let soon = sql
+            .clone()
+            .with_deadline(Instant::now() + Duration::from_millis(2500));
+        assert_eq!(soon.wire().timeout, Some(3));
+        let later = sql
+            .with_timeout(10)
+            .with_deadline(Instant::now() + Duration::from_secs(60));
+        assert_eq!(later.wire().timeout, Some(10));
+        // Nothing is sent once the deadline has passed
+        let result = later.query_with_deadline(Instant::now()).await;
+        assert!(matches!(
+            result,
+            Err(SnowflakeError::DeadlineExceeded { query_id: None })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn follow_ups_stay_with_the_failover_account() -> SnowflakeResult<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        // Nothing listens at the primary, and the secondary accepts connections but never
+        // answers, so each request times out once it reaches the secondary
+        let primary = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let secondary = std::net::TcpListener::bind("127.0.0.1:0")?;
+        secondary.set_nonblocking(true)?;
+        let failovers = Arc::new(AtomicUsize::new(0));
+        let client = {
+            let failovers = failovers.clone();
+            let failover = crate::Failover::new("SECONDARY").on_failover(move |_| {
+                failovers.fetch_add(1, Ordering::SeqCst);
+            });
+            test_client()
+                .with_resolve("account.snowflakecomputing.com", primary)
+                .with_resolve("secondary.snowflakecomputing.com", secondary.local_addr()?)
+                .with_failover(failover)
+        };
+        let wire: WireQueryResponse = serde_json::from_value(serde_json::json!({
+            "resultSetMetaData": {
+                "numRows": 2,
+                "rowType": [{"name": "IX", "type": "fixed", "scale": 0, "nullable": false}],
+                "partitionInfo": [{}, {}]
+            },
+            "data": [["0"]],
+            "statementHandle": "01b2c3d4",
+            "statementStatusUrl": "/api/v2/statements/01b2c3d4"
+        }))?;
+        let statement = client
+            .prepare("SELECT seq4() AS IX")
+            .with_client_timeout(Duration::from_millis(100));
+        let response = wire.hydrate(statement, Instant::now());
+
+        assert!(response.status().await.is_err());
+        assert_eq!(
+            response.statement.serving_account.get().map(String::as_str),
+            Some("SECONDARY")
+        );
+        // The partition is fetched from the secondary without trying the primary again
+        assert!(response.partition(1).await.is_err());
+        assert_eq!(failovers.load(Ordering::SeqCst), 1);
+        assert!(secondary.accept().is_ok());
+        assert!(secondary.accept().is_ok());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn deadlines_cancel_running_statements() -> SnowflakeResult<()> {
+        // The account doesn't exist, so the cancellation fails quickly and is only logged
+        let sql = test_client()
+            .prepare("SELECT SYSTEM$WAIT(60)")
+            .with_client_timeout(Duration::from_millis(100))
+            .with_deadline(Instant::now() + Duration::from_millis(50));
+        let submission = sql.submission(None);
+        let running = async {
+            let _ = submission.handle.set("01b2c3d4".to_owned());
+            std::future::pending().await
+        };
+        let result: SnowflakeResult<()> = sql.until_deadline(&submission, running).await;
+        assert!(matches!(
+            result,
+            Err(SnowflakeError::DeadlineExceeded { query_id: Some(id) }) if id == "01b2c3d4"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_full_dml_stats() -> SnowflakeResult<()> {
+        let result: WireDMLResult = serde_json::from_value(serde_json::json!({
+            "message": "ok",
+            "stats": {"numRowsUnloaded": 12, "numRowsLoaded": 3},
+        }))?;
+        let changes = Changes::new(result.message, result.stats);
+        assert_eq!(changes.rows_inserted, 0);
+        assert_eq!(changes.stats.rows_unloaded, 12);
+        assert_eq!(changes.stats.get("numRowsLoaded"), Some(3));
+        assert_eq!(changes.stats.get("numRowsUnloaded"), Some(12));
+        assert_eq!(
+            serde_json::to_value(&changes)?["stats"],
+            serde_json::json!({
+                "numRowsInserted": 0,
+                "numRowsDeleted": 0,
+                "numRowsUpdated": 0,
+                "numDmlDuplicates": 0,
+                "numRowsUnloaded": 12,
+                "numRowsLoaded": 3,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_created_on() -> SnowflakeResult<()> {
+        let result: WireDMLResult = serde_json::from_value(serde_json::json!({
+            "message": "ok",
+            "createdOn": 1672534861123u64,
+            "stats": {},
+        }))?;
+        let latency = Latency::default().with_created_on(result.created_on);
+        assert_eq!(
+            latency.created_on,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1672534861123))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn summarize_changes() {
+        let changes = |rows_inserted, rows_updated| Changes {
+            message: "ok".into(),
+            rows_inserted,
+            rows_deleted: 0,
+            rows_updated,
+            duplicates: 0,
+            stats: Default::default(),
+            latency: Default::default(),
+        };
+        let first: ChangesSummary = vec![changes(2, 0), changes(3, 1)].into_iter().collect();
+        let second: ChangesSummary = std::iter::once(changes(0, 4)).collect();
+        let summary = first.merge(second);
+        assert_eq!(summary.rows_inserted, 5);
+        assert_eq!(summary.rows_updated, 5);
+        assert_eq!(summary.rows_affected(), 10);
+        assert_eq!(summary.statements.len(), 3);
+        assert_eq!(summary.statements[2].rows_updated, 4);
+    }
 }
 
 //
 // Wire types
 //
 
-#[derive(Deserialize, Debug, Clone)]
+// Statements without a result set (like some DDL) may leave out any of these
+#[derive(Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct WireStatementMetaData {
+    #[serde(default, deserialize_with = "null_as_default")]
     pub num_rows: usize,
     //pub format: String,
-    pub row_type: Vec<ColumnType>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub row_type: Arc<[ColumnType]>,
     // The partition ino mostly doesn't matter, only the number of partitions
+    #[serde(default, deserialize_with = "null_as_default")]
     pub partition_info: Vec<WirePartitionInfo>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct WirePartitionInfo {
-    //pub row_count: usize,
+    #[serde(default)]
+    pub row_count: Option<usize>,
     //pub uncompressed_size: usize,
     //pub compressed_size: Option<usize>,
 }
@@ -400,10 +2572,15 @@ pub struct ColumnType {
     /// The name of the column
     pub name: String,
     /// The database the column is in
+    ///
+    /// This is empty for columns that don't come from a table, like the output of `SHOW` commands
+    #[serde(default, deserialize_with = "null_as_default")]
     pub database: String,
-    /// The schema the column is in
+    /// The schema the column is in (empty if the column doesn't come from a table)
+    #[serde(default, deserialize_with = "null_as_default")]
     pub schema: String,
-    /// The table the column is in
+    /// The table the column is in (empty if the column doesn't come from a table)
+    #[serde(default, deserialize_with = "null_as_default")]
     pub table: String,
     /// How many decimal digits of precision the column has
     /// (this is usually 38)
@@ -416,38 +2593,106 @@ pub struct ColumnType {
     // The number of decimal digits of scale the column has (after the decimal point, usually 0)
     pub scale: Option<i32>,
     // Whether the column can be null
+    #[serde(default = "nullable_by_default")]
     pub nullable: bool,
 }
 
-#[derive(Deserialize, Debug)]
-pub struct WireChanges {
-    #[serde(rename = "numRowsInserted")]
-    pub rows_inserted: usize,
-    #[serde(rename = "numRowsDeleted")]
-    pub rows_deleted: usize,
-    #[serde(rename = "numRowsUpdated")]
-    pub rows_updated: usize,
-    #[serde(rename = "numDmlDuplicates")]
-    pub duplicates: usize,
+/// Snowflake sometimes sends `null` rather than an empty value, like in the column metadata
+/// of `SHOW` commands, or the data of statements without a result set
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+fn nullable_by_default() -> bool {
+    true
 }
 
 #[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct WireDMLResult {
     pub message: String,
-    pub stats: WireChanges,
+    pub statement_handle: Option<String>,
+    pub created_on: Option<u64>,
+    // DDL (like `CREATE TABLE ... CLONE`) responds without stats
+    #[serde(default)]
+    pub stats: DmlStats,
+}
+
+/// A parsed response to a statement, with what it took to get it
+struct Executed<T> {
+    value: T,
+    bytes: usize,
+    retries: u32,
+    latency: Latency,
+    submission: Submission,
+}
+
+/// What Snowflake has said about one submission of a statement, so that its follow-up
+/// requests, like polling and cancelling it, reach the same statement
+#[derive(Debug, Default)]
+struct Submission {
+    /// The statement's handle, once Snowflake hands it off to async execution
+    handle: OnceLock<String>,
+    /// The account serving the statement, once a request has failed over to it
+    serving_account: OnceLock<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WireMultiStatementResult {
+    statement_handle: Option<String>,
+    #[serde(default)]
+    statement_handles: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WireChildResult {
+    #[serde(default)]
+    message: String,
+    stats: Option<DmlStats>,
 }
 
 impl WireQueryResponse {
-    fn hydrate(self, statement: Statement) -> QueryResponse {
+    /// Build the response to a statement that was submitted at `submitted`, which the wall
+    /// time of its transfer stats counts from
+    fn hydrate(mut self, statement: Statement, submitted: Instant) -> QueryResponse {
+        // The first partition is always inline, even when Snowflake doesn't describe it,
+        // like for DDL or an empty result, so there is always at least one (maybe empty)
+        let meta = &mut self.result_set_meta_data;
+        if meta.partition_info.is_empty() {
+            meta.partition_info.push(WirePartitionInfo {
+                row_count: Some(self.data.len()),
+            });
+            meta.num_rows = self.data.len();
+        }
         QueryResponse {
             result_set_meta_data: self.result_set_meta_data,
             data: self.data,
+            statement_handle: self.statement_handle,
             statement_status_url: self.statement_status_url,
+            transfer: Default::default(),
+            submitted,
+            latency: Latency::default(),
+            requeried_status_url: Default::default(),
             statement,
         }
     }
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WireStatementStatus {
+    pub(crate) code: String,
+    pub(crate) message: String,
+    #[serde(default)]
+    statement_handle: String,
+    statement_status_url: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 struct WirePartitionResponse {
     data: Arc<StringTable>,
@@ -457,19 +2702,52 @@ struct WirePartitionResponse {
 struct WireStatement {
     statement: String,
     timeout: Option<u64>,
-    database: String,
-    warehouse: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    database: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warehouse: Option<String>,
     role: Option<String>,
-    bindings: HashMap<String, Binding>,
+    #[serde(serialize_with = "serialize_bindings")]
+    bindings: Vec<Binding>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    parameters: HashMap<String, String>,
+}
+
+/// Compress a request body with gzip
+fn gzip(body: &[u8]) -> std::io::Result<bytes::Bytes> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(
+        Vec::with_capacity(body.len() / 4),
+        flate2::Compression::default(),
+    );
+    encoder.write_all(body)?;
+    Ok(encoder.finish()?.into())
+}
+
+/// Serialize bindings as the map Snowflake expects, keyed by their 1-based position
+fn serialize_bindings<S: serde::Serializer>(
+    bindings: &[Binding],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+    let mut map = serializer.serialize_map(Some(bindings.len()))?;
+    for (index, binding) in bindings.iter().enumerate() {
+        map.serialize_entry(&(index + 1), binding)?;
+    }
+    map.end()
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct WireQueryResponse {
+    #[serde(default, deserialize_with = "null_as_default")]
     result_set_meta_data: WireStatementMetaData,
+    #[serde(default, deserialize_with = "null_as_default")]
     data: Arc<StringTable>,
     // code: String,
+    statement_handle: String,
     statement_status_url: String,
+    created_on: Option<u64>,
     // request_id: String,
     // sql_state: String,
     // message: String,
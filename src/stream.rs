@@ -0,0 +1,56 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+
+use crate::errors::SnowflakeResult;
+use crate::partition::Partition;
+use crate::row::Row;
+
+/// A stream of results fetched from Snowflake, like the partitions or rows of a
+/// [`QueryResponse`](crate::QueryResponse)
+///
+/// Unlike `impl Stream`, this can be named, so it can be stored in a struct field or returned
+/// from a trait method. It is `Send` and `Unpin`, so it can be handed to other tasks, and to
+/// frameworks like axum and tonic that want a response body stream.
+///
+/// Streams returned by methods like [`QueryResponse::rows`](crate::QueryResponse::rows) borrow
+/// the response. The ones returned by methods like
+/// [`QueryResponse::into_row_stream`](crate::QueryResponse::into_row_stream) own it, so they
+/// are `'static`.
+pub struct ResultStream<'a, T> {
+    inner: BoxStream<'a, SnowflakeResult<T>>,
+}
+
+/// A stream of the partitions of a [`QueryResponse`](crate::QueryResponse)
+pub type PartitionStream<'a> = ResultStream<'a, Partition>;
+
+/// A stream of the rows of a [`QueryResponse`](crate::QueryResponse)
+pub type RowStream<'a> = ResultStream<'a, Row>;
+
+impl<'a, T> ResultStream<'a, T> {
+    pub(crate) fn new(inner: impl Stream<Item = SnowflakeResult<T>> + Send + 'a) -> Self {
+        ResultStream {
+            inner: inner.boxed(),
+        }
+    }
+}
+
+impl<T> Stream for ResultStream<'_, T> {
+    type Item = SnowflakeResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> std::fmt::Debug for ResultStream<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultStream").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use crate::cells::Cell;
+use crate::row::{ColumnIndex, Row};
+use crate::statement::ColumnType;
+
+/// The cells of a partition, stored row after row in a single allocation
+///
+/// `Vec<Vec<Cell>>` spends an allocation and 24 bytes of bookkeeping on every row, which adds
+/// up when a result is held in memory for a while. A table keeps one boxed slice of cells and
+/// the shared column metadata, and hands out rows as slices.
+///
+/// These are created by [`Partition::cell_table`](crate::Partition::cell_table).
+#[derive(Debug, Clone)]
+pub struct CellTable {
+    columns: Arc<[ColumnType]>,
+    num_rows: usize,
+    cells: Box<[Cell]>,
+}
+
+impl CellTable {
+    pub(crate) fn new(columns: Arc<[ColumnType]>, num_rows: usize, cells: Vec<Cell>) -> CellTable {
+        debug_assert_eq!(cells.len(), num_rows * columns.len());
+        CellTable {
+            columns,
+            num_rows,
+            cells: cells.into_boxed_slice(),
+        }
+    }
+
+    /// The columns of the table, in order
+    pub fn columns(&self) -> &[ColumnType] {
+        &self.columns
+    }
+
+    /// Get the number of rows in the table
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Whether the table has no rows
+    pub fn is_empty(&self) -> bool {
+        self.num_rows == 0
+    }
+
+    /// Get the cells of a row, or `None` if the row is out of bounds
+    pub fn row(&self, row: usize) -> Option<&[Cell]> {
+        let width = self.columns.len();
+        (row < self.num_rows).then(|| &self.cells[row * width..(row + 1) * width])
+    }
+
+    /// Get a cell by row and by column position or name, or `None` if there is no such cell
+    ///
+    /// Column names are matched like in [`Row::get`].
+    pub fn get<I: ColumnIndex>(&self, row: usize, column: I) -> Option<&Cell> {
+        let column = column.position(&self.columns)?;
+        self.row(row).map(|cells| &cells[column])
+    }
+
+    /// Iterate over the rows, as slices of cells
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[Cell]> + '_ {
+        let width = self.columns.len();
+        (0..self.num_rows).map(move |row| &self.cells[row * width..(row + 1) * width])
+    }
+
+    /// Copy a row out into a [`Row`], for APIs that take rows
+    pub fn to_row(&self, row: usize) -> Option<Row> {
+        self.row(row)
+            .map(|cells| Row::new(self.columns.clone(), cells.to_vec()))
+    }
+
+    /// Take the cells, row after row
+    pub fn into_cells(self) -> Vec<Cell> {
+        self.cells.into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::SnowflakeResult;
+    use crate::Partition;
+
+    use super::*;
+
+    #[test]
+    fn tables_are_row_major() -> SnowflakeResult<()> {
+        let column = |name: &str, data_type: &str| -> ColumnType {
+            serde_json::from_value(serde_json::json!({"name": name, "type": data_type})).unwrap()
+        };
+        let columns = vec![column("ID", "fixed"), column("NAME", "text")];
+        let data = (0..3)
+            .map(|ix| vec![Some(ix.to_string()), Some(format!("name {ix}"))])
+            .collect();
+        let partition = Partition::from_raw(columns, data);
+        let table = partition.slice(1..).cell_table()?;
+        assert_eq!(table.num_rows(), 2);
+        assert!(matches!(
+            table.row(0),
+            Some([Cell::Int(1), Cell::Varchar(_)])
+        ));
+        assert!(table.row(2).is_none());
+        assert!(matches!(table.get(1, "name"), Some(Cell::Varchar(name)) if &**name == "name 2"));
+        assert!(table.get(1, 2).is_none());
+        assert_eq!(table.rows().len(), 2);
+        assert_eq!(table.to_row(1).unwrap().get_as::<i64, _>("ID").unwrap(), 2);
+
+        let owned = partition.cell_table()?;
+        assert_eq!(
+            format!("{:?}", owned.rows().collect::<Vec<_>>()),
+            format!(
+                "{:?}",
+                owned.clone().into_cells().chunks(2).collect::<Vec<_>>()
+            )
+        );
+        assert_eq!(owned.num_rows(), 3);
+        Ok(())
+    }
+}
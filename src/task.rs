@@ -0,0 +1,367 @@
+use crate::cells::Cell;
+use crate::errors::{SnowflakeError, SnowflakeResult};
+use crate::row::Row;
+use crate::statement::quote_object_name;
+use crate::SnowflakeClient;
+
+/// A task, which runs a statement on a schedule or after other tasks
+///
+/// These are created by [`SnowflakeClient::task`], or by running a [`CreateTask`].
+/// The name is quoted if it needs to be, like in [`SnowflakeClient::get_ddl`].
+#[derive(Debug, Clone)]
+pub struct Task {
+    client: SnowflakeClient,
+    name: String,
+}
+
+impl Task {
+    pub(crate) fn new(client: &SnowflakeClient, name: &str) -> Task {
+        Task {
+            client: client.clone(),
+            name: quote_object_name(name),
+        }
+    }
+
+    /// The name of the task, quoted if it needs to be
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn run(&self, sql: String) -> SnowflakeResult<()> {
+        self.client.prepare(&sql).query().await?;
+        Ok(())
+    }
+
+    /// Start running the task on its schedule, with `ALTER TASK ... RESUME`
+    ///
+    /// Tasks are created suspended. In a graph of tasks, resume the children before the root.
+    pub async fn resume(&self) -> SnowflakeResult<()> {
+        self.run(format!("ALTER TASK {} RESUME", self.name)).await
+    }
+
+    /// Stop running the task on its schedule, with `ALTER TASK ... SUSPEND`
+    ///
+    /// A run that has already started is allowed to finish.
+    pub async fn suspend(&self) -> SnowflakeResult<()> {
+        self.run(format!("ALTER TASK {} SUSPEND", self.name)).await
+    }
+
+    /// Run the task once now, with `EXECUTE TASK`, even if it is suspended
+    ///
+    /// This only schedules the run; check [`Task::history`] to see how it went.
+    pub async fn execute(&self) -> SnowflakeResult<()> {
+        self.run(format!("EXECUTE TASK {}", self.name)).await
+    }
+
+    /// Drop the task, if it exists
+    pub async fn drop_task(&self) -> SnowflakeResult<()> {
+        self.run(format!("DROP TASK IF EXISTS {}", self.name)).await
+    }
+
+    /// The most recent runs of the task, newest first, from `INFORMATION_SCHEMA.TASK_HISTORY`
+    ///
+    /// This includes runs that are scheduled but haven't started yet. The history is looked up
+    /// in the client's database, by the last part of the task's name.
+    pub async fn history(&self, limit: u32) -> SnowflakeResult<Vec<TaskRun>> {
+        use futures::TryStreamExt;
+        let name = self.name.rsplit('.').next().unwrap_or(&self.name);
+        let name = match name
+            .strip_prefix('"')
+            .and_then(|name| name.strip_suffix('"'))
+        {
+            Some(quoted) => quoted.replace("\"\"", "\""),
+            None => name.to_owned(),
+        };
+        let response = self
+            .client
+            .prepare(
+                "SELECT * FROM TABLE(INFORMATION_SCHEMA.TASK_HISTORY(TASK_NAME => ?, RESULT_LIMIT => ?)) \
+                 ORDER BY SCHEDULED_TIME DESC",
+            )
+            .add_binding(name)
+            .add_binding(limit)
+            .query()
+            .await?;
+        let rows: Vec<Row> = response.rows().try_collect().await?;
+        rows.into_iter().map(TaskRun::try_from).collect()
+    }
+}
+
+/// A `CREATE TASK` statement
+///
+/// These are created by [`SnowflakeClient::create_task`]. The schedule and comment are quoted
+/// for you, but the warehouse, condition, and statement are used as written:
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// let task = client
+///     .create_task("etl.public.refresh_totals", "CALL refresh_totals()")
+///     .with_warehouse("ETL_WH")
+///     .with_schedule("USING CRON 0 * * * * UTC")
+///     .execute()
+///     .await?;
+/// task.resume().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CreateTask {
+    task: Task,
+    statement: String,
+    or_replace: bool,
+    warehouse: Option<String>,
+    schedule: Option<String>,
+    comment: Option<String>,
+    after: Vec<String>,
+    when: Option<String>,
+}
+
+impl CreateTask {
+    pub(crate) fn new(client: &SnowflakeClient, name: &str, statement: &str) -> CreateTask {
+        CreateTask {
+            task: Task::new(client, name),
+            statement: statement.to_owned(),
+            or_replace: false,
+            warehouse: None,
+            schedule: None,
+            comment: None,
+            after: vec![],
+            when: None,
+        }
+    }
+
+    /// Replace the task if it already exists
+    pub fn or_replace(mut self) -> CreateTask {
+        self.or_replace = true;
+        self
+    }
+
+    /// Run the task on this warehouse, instead of on serverless compute
+    pub fn with_warehouse(mut self, warehouse: &str) -> CreateTask {
+        self.warehouse = Some(warehouse.to_owned());
+        self
+    }
+
+    /// Run the task on a schedule, like `5 MINUTE` or `USING CRON 0 9 * * MON-FRI UTC`
+    pub fn with_schedule(mut self, schedule: &str) -> CreateTask {
+        self.schedule = Some(schedule.to_owned());
+        self
+    }
+
+    /// Describe the task
+    pub fn with_comment(mut self, comment: &str) -> CreateTask {
+        self.comment = Some(comment.to_owned());
+        self
+    }
+
+    /// Run the task after another task finishes, instead of on a schedule
+    ///
+    /// Call this once for each predecessor; the task runs after all of them.
+    pub fn with_after(mut self, predecessor: &str) -> CreateTask {
+        self.after.push(quote_object_name(predecessor));
+        self
+    }
+
+    /// Only run the task when a condition holds, like `SYSTEM$STREAM_HAS_DATA('orders_stream')`
+    pub fn with_when(mut self, condition: &str) -> CreateTask {
+        self.when = Some(condition.to_owned());
+        self
+    }
+
+    /// The `CREATE TASK` statement that will be run
+    pub fn sql(&self) -> String {
+        let mut sql = format!(
+            "CREATE {}TASK {}",
+            if self.or_replace { "OR REPLACE " } else { "" },
+            self.task.name
+        );
+        if let Some(warehouse) = &self.warehouse {
+            sql += &format!(" WAREHOUSE = {warehouse}");
+        }
+        if let Some(schedule) = &self.schedule {
+            sql += &format!(" SCHEDULE = {}", literal(schedule));
+        }
+        if let Some(comment) = &self.comment {
+            sql += &format!(" COMMENT = {}", literal(comment));
+        }
+        if !self.after.is_empty() {
+            sql += &format!(" AFTER {}", self.after.join(", "));
+        }
+        if let Some(when) = &self.when {
+            sql += &format!(" WHEN {when}");
+        }
+        sql + " AS " + &self.statement
+    }
+
+    /// Create the task, which starts out suspended
+    pub async fn execute(self) -> SnowflakeResult<Task> {
+        self.task.run(self.sql()).await?;
+        Ok(self.task)
+    }
+}
+
+/// Quote text as a string literal
+fn literal(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// The state of a [`TaskRun`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskState {
+    Scheduled,
+    Executing,
+    Succeeded,
+    Failed,
+    /// The run failed, and the task was suspended after too many failures in a row
+    FailedAndAutoSuspended,
+    Cancelled,
+    /// The run was skipped, e.g. because the previous run was still going
+    Skipped,
+    /// A state this crate doesn't know about yet
+    Other(String),
+}
+
+impl From<&str> for TaskState {
+    fn from(state: &str) -> TaskState {
+        match state {
+            "SCHEDULED" => TaskState::Scheduled,
+            "EXECUTING" => TaskState::Executing,
+            "SUCCEEDED" => TaskState::Succeeded,
+            "FAILED" => TaskState::Failed,
+            "FAILED_AND_AUTO_SUSPENDED" => TaskState::FailedAndAutoSuspended,
+            "CANCELLED" => TaskState::Cancelled,
+            "SKIPPED" => TaskState::Skipped,
+            other => TaskState::Other(other.to_owned()),
+        }
+    }
+}
+
+/// One run of a task, as listed by `TASK_HISTORY`
+///
+/// The timestamps are the `TIMESTAMP_LTZ` [`Cell`]s, so that their types don't depend on the
+/// enabled features. Convert them with [`FromCell`](crate::FromCell), like
+/// `DateTime::<Utc>::try_from(&run.scheduled_time)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskRun {
+    /// The name of the task, without its database and schema
+    pub name: String,
+    pub database_name: String,
+    pub schema_name: String,
+    /// The ID of the query the run executed, once it has started
+    pub query_id: Option<String>,
+    pub state: TaskState,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    /// When the run was (or is) scheduled to start
+    pub scheduled_time: Cell,
+    pub query_start_time: Option<Cell>,
+    pub completed_time: Option<Cell>,
+    /// The value set with `SYSTEM$SET_RETURN_VALUE`, if any
+    pub return_value: Option<String>,
+}
+
+impl TryFrom<Row> for TaskRun {
+    type Error = SnowflakeError;
+
+    fn try_from(row: Row) -> SnowflakeResult<TaskRun> {
+        // Error codes are numbers, but they are conventionally written as text like `002003`
+        let error_code = match row.get("ERROR_CODE") {
+            Some(Cell::Int(code)) => Some(format!("{code:06}")),
+            Some(Cell::Varchar(code)) => Some(code.to_string()),
+            _ => None,
+        };
+        Ok(TaskRun {
+            name: row.get_as("NAME")?,
+            database_name: row.get_as("DATABASE_NAME")?,
+            schema_name: row.get_as("SCHEMA_NAME")?,
+            query_id: row.get_as("QUERY_ID")?,
+            state: row.get_as::<String, _>("STATE")?.as_str().into(),
+            error_code,
+            error_message: row.get_as("ERROR_MESSAGE")?,
+            scheduled_time: row.get_as("SCHEDULED_TIME")?,
+            query_start_time: row.get_as("QUERY_START_TIME")?,
+            completed_time: row.get_as("COMPLETED_TIME")?,
+            return_value: row.get("RETURN_VALUE").and_then(|cell| match cell {
+                Cell::Varchar(value) => Some(value.to_string()),
+                _ => None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_task_sql() -> SnowflakeResult<()> {
+        let client = crate::test_client();
+        let create = client
+            .create_task("etl.public.refresh totals", "CALL refresh_totals()")
+            .or_replace()
+            .with_warehouse("ETL_WH")
+            .with_schedule("USING CRON 0 * * * * UTC")
+            .with_comment("Bob's task")
+            .with_after("etl.public.load")
+            .with_when("SYSTEM$STREAM_HAS_DATA('orders_stream')");
+        assert_eq!(
+            create.sql(),
+            "CREATE OR REPLACE TASK etl.public.\"refresh totals\" WAREHOUSE = ETL_WH \
+             SCHEDULE = 'USING CRON 0 * * * * UTC' COMMENT = 'Bob\\'s task' \
+             AFTER etl.public.load WHEN SYSTEM$STREAM_HAS_DATA('orders_stream') \
+             AS CALL refresh_totals()"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_task_runs() -> SnowflakeResult<()> {
+        let column = |name: &str, data_type: &str| -> crate::ColumnType {
+            serde_json::from_value(serde_json::json!({"name": name, "type": data_type})).unwrap()
+        };
+        let row = Row::new(
+            vec![
+                column("QUERY_ID", "text"),
+                column("NAME", "text"),
+                column("DATABASE_NAME", "text"),
+                column("SCHEMA_NAME", "text"),
+                column("STATE", "text"),
+                column("ERROR_CODE", "fixed"),
+                column("ERROR_MESSAGE", "text"),
+                column("SCHEDULED_TIME", "timestamp_ltz"),
+                column("QUERY_START_TIME", "timestamp_ltz"),
+                column("COMPLETED_TIME", "timestamp_ltz"),
+                column("RETURN_VALUE", "text"),
+            ]
+            .into(),
+            vec![
+                Cell::Null,
+                Cell::Varchar("REFRESH_TOTALS".into()),
+                Cell::Varchar("ETL".into()),
+                Cell::Varchar("PUBLIC".into()),
+                Cell::Varchar("FAILED_AND_AUTO_SUSPENDED".into()),
+                Cell::Int(2003),
+                Cell::Varchar("Object does not exist".into()),
+                crate::cells::RawCell::TimestampLtz
+                    .to_cell(&Some("1672534861.000000000".into()))
+                    .unwrap(),
+                Cell::Null,
+                Cell::Null,
+                Cell::Null,
+            ],
+        );
+        let run = TaskRun::try_from(row)?;
+        assert_eq!(run.name, "REFRESH_TOTALS");
+        assert_eq!(run.query_id, None);
+        assert_eq!(run.state, TaskState::FailedAndAutoSuspended);
+        assert_eq!(run.error_code.as_deref(), Some("002003"));
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            run.scheduled_time.as_timestamp().map(|at| at.timestamp()),
+            Some(1672534861)
+        );
+        assert_eq!(run.completed_time, None);
+        assert_eq!(run.return_value, None);
+        Ok(())
+    }
+}
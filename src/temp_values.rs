@@ -0,0 +1,133 @@
+use crate::bindings::{Binding, ToBinding};
+use crate::errors::SnowflakeResult;
+use crate::insert::BatchInsert;
+use crate::statement::QueryResponse;
+use crate::SnowflakeClient;
+
+/// A list of values that is too long for an `IN (?, ?, ...)` list, loaded into a temporary
+/// table so a query can join against it
+///
+/// These are created by [`SnowflakeClient::temp_values`]. The query refers to the table
+/// by a placeholder like `{ids}`, which is replaced by the table's generated name, and the
+/// table has a single column named `value`:
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient, ids: Vec<i64>) -> light_snowflake_connector::SnowflakeResult<()> {
+/// let response = client
+///     .temp_values("ids", "NUMBER", ids)
+///     .add_binding("active")
+///     .query("SELECT u.* FROM users u JOIN {ids} t ON u.id = t.value WHERE u.status = ?")
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Each request to the SQL API runs in its own session, so creating the table, inserting
+/// the values, running the query, and dropping the table are sent together as one
+/// multi-statement request. The inserts are split to respect the binding limit of
+/// [`BatchInsert`], but everything has to fit in that one request. If anything fails,
+/// the table still disappears with the session.
+#[derive(Debug, Clone)]
+pub struct TempValues {
+    client: SnowflakeClient,
+    placeholder: String,
+    column_type: String,
+    batch: BatchInsert,
+    bindings: Vec<Binding>,
+}
+
+impl TempValues {
+    pub(crate) fn new<I>(
+        client: &SnowflakeClient,
+        placeholder: &str,
+        column_type: &str,
+        values: I,
+    ) -> TempValues
+    where
+        I: IntoIterator,
+        I::Item: ToBinding,
+    {
+        let name = format!("TEMP_VALUES_{}", uuid::Uuid::new_v4().simple());
+        let batch = values.into_iter().fold(
+            client.batch_insert(&name).with_columns(&["value"]),
+            |batch, value| batch.add_row([value]),
+        );
+        TempValues {
+            client: client.clone(),
+            placeholder: format!("{{{placeholder}}}"),
+            column_type: column_type.to_owned(),
+            batch,
+            bindings: vec![],
+        }
+    }
+
+    /// Add a binding for a `?` in the query (not for the values, which are bound separately)
+    pub fn add_binding<T: ToBinding>(mut self, value: T) -> TempValues {
+        self.bindings.push(value.to_binding());
+        self
+    }
+
+    /// The statements to send, and the index of the query among them
+    fn statements(&self, query: &str) -> (Vec<String>, Vec<Binding>, usize) {
+        let table = self.batch.table();
+        let mut statements = vec![format!(
+            "CREATE TEMPORARY TABLE {table} (value {})",
+            self.column_type
+        )];
+        let mut bindings = vec![];
+        for chunk in self.batch.plan() {
+            statements.push(chunk.sql);
+            bindings.extend(chunk.bindings);
+        }
+        let index = statements.len();
+        statements.push(query.replace(&self.placeholder, table));
+        bindings.extend(self.bindings.iter().cloned());
+        statements.push(format!("DROP TABLE IF EXISTS {table}"));
+        (statements, bindings, index)
+    }
+
+    /// Load the values and run the query against them
+    pub async fn query(&self, query: &str) -> SnowflakeResult<QueryResponse> {
+        let (statements, bindings, index) = self.statements(query);
+        let statement = bindings
+            .into_iter()
+            .fold(
+                self.client.prepare(&statements.join(";\n")),
+                crate::Statement::add_binding,
+            )
+            .with_parameter("MULTI_STATEMENT_COUNT", statements.len().to_string());
+        statement.query_multi(index).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_values_statements() -> SnowflakeResult<()> {
+        let client = crate::test_client();
+        let values = client
+            .temp_values("ids", "NUMBER", 0..5)
+            .add_binding("active");
+        let table = values.batch.table().to_owned();
+        let (statements, bindings, index) = values
+            .statements("SELECT * FROM users WHERE id IN (SELECT value FROM {ids}) AND status = ?");
+        assert_eq!(index, 2);
+        assert_eq!(
+            statements[0],
+            format!("CREATE TEMPORARY TABLE {table} (value NUMBER)")
+        );
+        assert_eq!(
+            statements[1],
+            format!("INSERT INTO {table} (value) VALUES (?), (?), (?), (?), (?)")
+        );
+        assert_eq!(
+            statements[2],
+            format!("SELECT * FROM users WHERE id IN (SELECT value FROM {table}) AND status = ?")
+        );
+        assert_eq!(statements[3], format!("DROP TABLE IF EXISTS {table}"));
+        assert_eq!(bindings.len(), 6);
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+
+use crate::errors::SnowflakeResult;
+
+/// A W3C trace context, sent as `traceparent` and `tracestate` headers
+///
+/// This lets Snowflake requests be stitched into distributed traces by proxies
+/// and observability tools that understand [W3C Trace Context](https://www.w3.org/TR/trace-context/).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// The `traceparent` header, like `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`
+    pub traceparent: String,
+    /// The optional vendor-specific `tracestate` header
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Create a trace context from a `traceparent` header value
+    pub fn new(traceparent: impl Into<String>) -> TraceContext {
+        TraceContext {
+            traceparent: traceparent.into(),
+            tracestate: None,
+        }
+    }
+
+    /// Add a `tracestate` header value
+    pub fn with_tracestate(mut self, tracestate: impl Into<String>) -> TraceContext {
+        self.tracestate = Some(tracestate.into());
+        self
+    }
+
+    /// The trace id from the `traceparent` header, if it is well formed
+    pub fn trace_id(&self) -> Option<&str> {
+        self.traceparent
+            .split('-')
+            .nth(1)
+            .filter(|id| id.len() == 32)
+    }
+
+    pub(crate) fn apply(&self, headers: &mut HeaderMap) -> SnowflakeResult<()> {
+        headers.insert("traceparent", self.traceparent.parse()?);
+        if let Some(tracestate) = &self.tracestate {
+            headers.insert("tracestate", tracestate.parse()?);
+        }
+        Ok(())
+    }
+}
+
+/// A function that reads the current trace context, e.g. from the current span
+///
+/// Attach one to a client with
+/// [`SnowflakeClient::with_trace_context_provider`](crate::SnowflakeClient::with_trace_context_provider).
+#[derive(Clone)]
+pub struct TraceContextProvider(Arc<dyn Fn() -> Option<TraceContext> + Send + Sync>);
+
+impl TraceContextProvider {
+    /// Wrap a function that returns the current trace context, if there is one
+    pub fn new(provider: impl Fn() -> Option<TraceContext> + Send + Sync + 'static) -> Self {
+        TraceContextProvider(Arc::new(provider))
+    }
+
+    pub(crate) fn current(&self) -> Option<TraceContext> {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for TraceContextProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TraceContextProvider")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_headers() -> SnowflakeResult<()> {
+        let mut headers = HeaderMap::new();
+        TraceContext::new("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+            .with_tracestate("vendor=value")
+            .apply(&mut headers)?;
+        assert_eq!(
+            headers["traceparent"],
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+        assert_eq!(headers["tracestate"], "vendor=value");
+        Ok(())
+    }
+}
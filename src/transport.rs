@@ -0,0 +1,238 @@
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime};
+
+use reqwest::header::HeaderMap;
+
+use crate::audit::{AuditOutcome, AuditRecord};
+use crate::errors::{parse_response, SnowflakeError, SnowflakeResult};
+use crate::failover::FailoverEvent;
+use crate::statement::{normalize_identifier, WireStatementStatus};
+use crate::trace::TraceContext;
+use crate::{jwt, SnowflakeClient};
+
+/// The error code Snowflake uses when it rejects a JWT
+const JWT_INVALID_CODE: &str = "390144";
+
+/// Sends requests to Snowflake with a client's key pair, headers, and failover
+///
+/// Statements borrow one for each request they send, with their own headers and timeout,
+/// and the [`Catalog`](crate::Catalog) uses one for its REST endpoints.
+pub(crate) struct Transport<'a> {
+    pub(crate) client: &'a SnowflakeClient,
+    /// Extra headers for every request, which give way to any the request sets itself
+    pub(crate) headers: Option<&'a HeaderMap>,
+    /// The trace context to send, instead of the one from the client's provider
+    pub(crate) trace_context: Option<&'a TraceContext>,
+    /// The timeout for each request that doesn't set its own
+    pub(crate) timeout: Duration,
+    /// The account to send every request to, once a request has failed over to it
+    ///
+    /// Statements keep sending their follow-up requests, like polling, fetching partitions, and
+    /// cancelling, to the account that accepted them, since only that account has the result.
+    pub(crate) serving_account: Option<&'a OnceLock<String>>,
+}
+
+impl<'a> Transport<'a> {
+    /// A transport with only the client's settings
+    pub(crate) fn new(client: &'a SnowflakeClient, timeout: Duration) -> Transport<'a> {
+        Transport {
+            client,
+            headers: None,
+            trace_context: None,
+            timeout,
+            serving_account: None,
+        }
+    }
+
+    /// The trace context to send, or else the one from the client's provider
+    pub(crate) fn trace_context(&self) -> Option<TraceContext> {
+        self.trace_context.cloned().or_else(|| {
+            let provider = self.client.options().trace_context_provider.as_ref()?;
+            provider.current()
+        })
+    }
+
+    /// The headers of every request to this account, including a token
+    ///
+    /// Set `fresh_token` after Snowflake rejected a token, so a shared pool replaces it.
+    fn request_headers(&self, account: &str, fresh_token: bool) -> SnowflakeResult<HeaderMap> {
+        use reqwest::header::*;
+        let account = account.to_ascii_uppercase();
+        let user = self.client.user().to_ascii_uppercase();
+        let token = match &self.client.options().connection_pool {
+            Some(pool) => pool.token(self.client.key_pair(), &account, &user, fresh_token)?,
+            None => jwt::create_token(self.client.key_pair(), &account, &user)?,
+        };
+        let bearer = format!("Bearer {}", token);
+        let mut authorization: HeaderValue = bearer.parse()?;
+        authorization.set_sensitive(true);
+        #[cfg(feature = "zeroize")]
+        drop((
+            zeroize::Zeroizing::new(token),
+            zeroize::Zeroizing::new(bearer),
+        ));
+
+        let mut headers = HeaderMap::with_capacity(5);
+        headers.append(CONTENT_TYPE, "application/json".parse()?);
+        headers.append(AUTHORIZATION, authorization);
+        headers.append(
+            "X-Snowflake-Authorization-Token-Type",
+            "KEYPAIR_JWT".parse()?,
+        );
+        headers.append(ACCEPT, "application/json".parse()?);
+        headers.append(
+            USER_AGENT,
+            concat!(env!("CARGO_PKG_NAME"), '/', env!("CARGO_PKG_VERSION")).parse()?,
+        );
+        for (name, value) in self.headers.into_iter().flatten() {
+            headers.append(name, value.clone());
+        }
+        if let Some(trace_context) = self.trace_context() {
+            trace_context.apply(&mut headers)?;
+        }
+        Ok(headers)
+    }
+
+    /// Send a request to the account's host, with a freshly signed token
+    ///
+    /// `build` receives the client and the host to send the request to,
+    /// and it may be called more than once:
+    ///
+    /// * If the primary account is unreachable and a [`Failover`](crate::Failover)
+    ///   is configured, the request is sent to the secondary account instead, which then
+    ///   becomes the serving account.
+    /// * If Snowflake rejects the token (e.g. because of clock drift), this retries
+    ///   once with a new token before giving up.
+    pub(crate) async fn request(
+        &self,
+        build: impl Fn(reqwest::Client, &str) -> reqwest::RequestBuilder,
+    ) -> SnowflakeResult<reqwest::Response> {
+        if let Some(account) = self.serving_account.and_then(OnceLock::get) {
+            return self.request_to(account, &build).await;
+        }
+        let primary = self.client.account();
+        match self.request_to(primary, &build).await {
+            Err(SnowflakeError::Request(error)) if error.is_connect() => {
+                let Some(failover) = &self.client.options().failover else {
+                    return Err(error.into());
+                };
+                failover.notify(&FailoverEvent {
+                    primary,
+                    secondary: &failover.account,
+                    error: &error,
+                });
+                if let Some(serving_account) = self.serving_account {
+                    let _ = serving_account.set(failover.account.clone());
+                }
+                self.request_to(&failover.account, &build).await
+            }
+            result => result,
+        }
+    }
+
+    async fn request_to(
+        &self,
+        account: &str,
+        build: &impl Fn(reqwest::Client, &str) -> reqwest::RequestBuilder,
+    ) -> SnowflakeResult<reqwest::Response> {
+        let response = self.send_to(account, build, false).await?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let status = response.json::<WireStatementStatus>().await?;
+        if status.code != JWT_INVALID_CODE {
+            return Err(SnowflakeError::ServerError {
+                code: status.code,
+                message: status.message,
+            });
+        }
+        log::warn!("Snowflake rejected the JWT, retrying with a new one");
+        self.send_to(account, build, true).await
+    }
+
+    /// Send one request to an account, through the client's connection pool if it has one
+    async fn send_to(
+        &self,
+        account: &str,
+        build: &impl Fn(reqwest::Client, &str) -> reqwest::RequestBuilder,
+        fresh_token: bool,
+    ) -> SnowflakeResult<reqwest::Response> {
+        let host = format!(
+            "https://{}.snowflakecomputing.com",
+            account.to_ascii_lowercase()
+        );
+        let (http, request) = build(self.client.http()?.clone(), &host).build_split();
+        let mut request = request?;
+        // Like a client's default headers, these give way to any the request sets itself
+        let mut headers = self.request_headers(account, fresh_token)?;
+        for name in request.headers().keys() {
+            headers.remove(name);
+        }
+        for (name, value) in request.headers() {
+            headers.append(name, value.clone());
+        }
+        *request.headers_mut() = headers;
+        request.timeout_mut().get_or_insert(self.timeout);
+        Ok(http.execute(request).await?)
+    }
+
+    /// Send a request to one of Snowflake's other REST endpoints, like `/api/v2/databases`
+    ///
+    /// These are held to the client's [`StatementPolicy`](crate::StatementPolicy) like the
+    /// statements they stand in for, and are recorded by its audit hook.
+    pub(crate) async fn rest<T: serde::de::DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> SnowflakeResult<T> {
+        let started = (SystemTime::now(), Instant::now());
+        let result = async {
+            if let Some(policy) = &self.client.options().statement_policy {
+                policy.check_request(&method, path)?;
+            }
+            let response = self
+                .request(|client, host| {
+                    let request = client.request(method.clone(), format!("{}{}", host, path));
+                    match body {
+                        Some(body) => request.json(body),
+                        None => request,
+                    }
+                })
+                .await?;
+            Ok(parse_response(response).await?.0)
+        }
+        .await;
+        self.audit(started, &method, path, &result);
+        result
+    }
+
+    /// Send an audit record for a REST request, if the client has an audit hook
+    ///
+    /// The fingerprint is the method and the path, without the query string.
+    fn audit<T>(
+        &self,
+        (timestamp, started): (SystemTime, Instant),
+        method: &reqwest::Method,
+        path: &str,
+        result: &SnowflakeResult<T>,
+    ) {
+        let Some(hook) = &self.client.options().audit_hook else {
+            return;
+        };
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        hook.record(&AuditRecord {
+            timestamp,
+            elapsed: started.elapsed(),
+            user: self.client.user().to_owned(),
+            role: self.client.role().map(normalize_identifier),
+            fingerprint: format!("{method} {path}"),
+            request_id: uuid::Uuid::new_v4(),
+            query_id: None,
+            outcome: match result {
+                Ok(_) => AuditOutcome::Succeeded,
+                Err(error) => AuditOutcome::Failed(error.to_string()),
+            },
+        });
+    }
+}
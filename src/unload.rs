@@ -0,0 +1,244 @@
+use crate::errors::SnowflakeResult;
+use crate::row::Row;
+use crate::SnowflakeClient;
+
+/// A `COPY INTO <location>` statement, which unloads the results of a query to files in
+/// a stage or external location
+///
+/// These are created by [`SnowflakeClient::unload`]. The location, query, and options are
+/// used as written, so quote them yourself if they need quoting. For example:
+///
+/// ```rust,no_run
+/// # async fn example(client: light_snowflake_connector::SnowflakeClient) -> light_snowflake_connector::SnowflakeResult<()> {
+/// let summary = client
+///     .unload("@exports/users/", "SELECT * FROM users")
+///     .with_format_type("PARQUET")
+///     .with_option("MAX_FILE_SIZE", "104857600")
+///     .with_option("HEADER", "TRUE")
+///     .execute()
+///     .await?;
+/// println!("{} rows in {} files", summary.rows_unloaded, summary.files.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Unload {
+    client: SnowflakeClient,
+    location: String,
+    query: String,
+    partition_by: Option<String>,
+    format_name: Option<String>,
+    format_options: Vec<(String, String)>,
+    copy_options: Vec<(String, String)>,
+}
+
+/// A file written by an [`Unload`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnloadedFile {
+    /// The path of the file, relative to the location
+    pub name: String,
+    /// The size of the file in bytes
+    pub size: u64,
+    /// The number of rows in the file
+    pub rows: u64,
+}
+
+/// What an [`Unload`] wrote
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnloadSummary {
+    /// The files that were written, in the order Snowflake listed them
+    pub files: Vec<UnloadedFile>,
+    /// The total number of rows unloaded
+    pub rows_unloaded: u64,
+    /// The total size of the files in bytes
+    pub bytes_written: u64,
+}
+
+impl Unload {
+    pub(crate) fn new(client: &SnowflakeClient, location: &str, query: &str) -> Unload {
+        Unload {
+            client: client.clone(),
+            location: location.to_owned(),
+            query: query.to_owned(),
+            partition_by: None,
+            format_name: None,
+            format_options: vec![],
+            copy_options: vec![],
+        }
+    }
+
+    /// Use a named file format, like `my_db.public.my_csv_format`
+    ///
+    /// This replaces any format type or options, since Snowflake doesn't allow both.
+    pub fn with_named_format(mut self, format_name: &str) -> Unload {
+        self.format_name = Some(format_name.to_owned());
+        self.format_options.clear();
+        self
+    }
+
+    /// Set the type of the files, like `CSV`, `JSON`, or `PARQUET`
+    pub fn with_format_type(self, format_type: &str) -> Unload {
+        self.with_format_option("TYPE", format_type)
+    }
+
+    /// Set a file format option, like `("COMPRESSION", "GZIP")` or `("FIELD_DELIMITER", "'|'")`
+    ///
+    /// This replaces any named format. Setting the same option twice keeps the last value.
+    pub fn with_format_option(mut self, name: &str, value: &str) -> Unload {
+        self.format_name = None;
+        set(&mut self.format_options, name, value);
+        self
+    }
+
+    /// Set a copy option, like `("HEADER", "TRUE")`, `("SINGLE", "TRUE")`,
+    /// `("MAX_FILE_SIZE", "16777216")`, or `("OVERWRITE", "TRUE")`
+    ///
+    /// Setting the same option twice keeps the last value. `DETAILED_OUTPUT` is always
+    /// enabled, since it's needed to list the files that were written.
+    pub fn with_option(mut self, name: &str, value: &str) -> Unload {
+        set(&mut self.copy_options, name, value);
+        self
+    }
+
+    /// Split the files into paths by an expression, like `'date=' || TO_VARCHAR(created_on::date)`
+    pub fn with_partition_by(mut self, expression: &str) -> Unload {
+        self.partition_by = Some(expression.to_owned());
+        self
+    }
+
+    /// The `COPY INTO` statement that will be run
+    pub fn sql(&self) -> String {
+        let mut sql = format!("COPY INTO {} FROM ({})", self.location, self.query);
+        if let Some(partition_by) = &self.partition_by {
+            sql += &format!(" PARTITION BY ({partition_by})");
+        }
+        if let Some(format_name) = &self.format_name {
+            sql += &format!(
+                " FILE_FORMAT = (FORMAT_NAME = '{}')",
+                format_name.replace('\'', "\\'")
+            );
+        } else if !self.format_options.is_empty() {
+            sql += &format!(" FILE_FORMAT = ({})", options(&self.format_options));
+        }
+        if !self.copy_options.is_empty() {
+            sql += &format!(" {}", options(&self.copy_options));
+        }
+        sql + " DETAILED_OUTPUT = TRUE"
+    }
+
+    /// Run the unload, returning the files that were written
+    pub async fn execute(&self) -> SnowflakeResult<UnloadSummary> {
+        use futures::TryStreamExt;
+        let response = self.client.prepare(&self.sql()).query().await?;
+        let rows: Vec<Row> = response.rows().try_collect().await?;
+        UnloadSummary::from_rows(&rows)
+    }
+}
+
+impl UnloadSummary {
+    /// Parse the result of `COPY INTO <location>`
+    ///
+    /// With `DETAILED_OUTPUT`, there is one row per file. When nothing was unloaded,
+    /// Snowflake returns a single row of totals instead, which is all zeros.
+    fn from_rows(rows: &[Row]) -> SnowflakeResult<UnloadSummary> {
+        let mut summary = UnloadSummary::default();
+        for row in rows {
+            if row.get("FILE_NAME").is_none() {
+                summary.rows_unloaded += row.get_as::<u64, _>("rows_unloaded")?;
+                summary.bytes_written += row.get_as::<u64, _>("output_bytes")?;
+                continue;
+            }
+            let file = UnloadedFile {
+                name: row.get_as("FILE_NAME")?,
+                size: row.get_as("FILE_SIZE")?,
+                rows: row.get_as("ROW_COUNT")?,
+            };
+            summary.rows_unloaded += file.rows;
+            summary.bytes_written += file.size;
+            summary.files.push(file);
+        }
+        Ok(summary)
+    }
+}
+
+/// Add or replace an option, matching names case-insensitively
+fn set(options: &mut Vec<(String, String)>, name: &str, value: &str) {
+    options.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+    options.push((name.to_owned(), value.to_owned()));
+}
+
+fn options(options: &[(String, String)]) -> String {
+    options
+        .iter()
+        .map(|(name, value)| format!("{name} = {value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::Cell;
+
+    #[test]
+    fn unload_sql() -> SnowflakeResult<()> {
+        let client = crate::test_client();
+        let unload = client
+            .unload("@exports/users/", "SELECT * FROM users")
+            .with_format_type("CSV")
+            .with_format_option("COMPRESSION", "GZIP")
+            .with_option("HEADER", "TRUE")
+            .with_option("header", "FALSE")
+            .with_partition_by("TO_VARCHAR(created_on::date)");
+        assert_eq!(
+            unload.sql(),
+            "COPY INTO @exports/users/ FROM (SELECT * FROM users) \
+             PARTITION BY (TO_VARCHAR(created_on::date)) \
+             FILE_FORMAT = (TYPE = CSV COMPRESSION = GZIP) header = FALSE DETAILED_OUTPUT = TRUE"
+        );
+        assert_eq!(
+            unload.with_named_format("my_format").sql(),
+            "COPY INTO @exports/users/ FROM (SELECT * FROM users) \
+             PARTITION BY (TO_VARCHAR(created_on::date)) \
+             FILE_FORMAT = (FORMAT_NAME = 'my_format') header = FALSE DETAILED_OUTPUT = TRUE"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_unload_results() -> SnowflakeResult<()> {
+        let columns = |names: &[&str]| -> std::sync::Arc<[crate::ColumnType]> {
+            names
+                .iter()
+                .map(|name| {
+                    serde_json::from_value(serde_json::json!({"name": name, "type": "fixed"}))
+                        .unwrap()
+                })
+                .collect()
+        };
+        let files = columns(&["FILE_NAME", "FILE_SIZE", "ROW_COUNT"]);
+        let file = |name: &str, size, rows| {
+            Row::new(
+                files.clone(),
+                vec![Cell::Varchar(name.into()), Cell::Int(size), Cell::Int(rows)],
+            )
+        };
+        let summary = UnloadSummary::from_rows(&[
+            file("data_0_0_0.csv.gz", 100, 10),
+            file("data_0_0_1.csv.gz", 50, 5),
+        ])?;
+        assert_eq!(summary.files.len(), 2);
+        assert_eq!(summary.rows_unloaded, 15);
+        assert_eq!(summary.bytes_written, 150);
+
+        let totals = Row::new(
+            columns(&["rows_unloaded", "input_bytes", "output_bytes"]),
+            vec![Cell::Int(0), Cell::Int(0), Cell::Int(0)],
+        );
+        assert_eq!(
+            UnloadSummary::from_rows(&[totals])?,
+            UnloadSummary::default()
+        );
+        Ok(())
+    }
+}